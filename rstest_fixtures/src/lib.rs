@@ -1,4 +1,8 @@
+use std::any::Any;
+use std::cell::RefCell;
 use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::rc::Rc;
 
 pub trait TearDown {
     fn tear_down(self);
@@ -11,6 +15,18 @@ impl TearDown for EmptyGuard {
     fn tear_down(self) {}
 }
 
+impl TearDown for () {
+    fn tear_down(self) {}
+}
+
+impl<G: TearDown> TearDown for Option<G> {
+    fn tear_down(self) {
+        if let Some(guard) = self {
+            guard.tear_down();
+        }
+    }
+}
+
 impl<A: TearDown, B: TearDown> TearDown for (A, B) {
     fn tear_down(self) {
         self.0.tear_down();
@@ -18,41 +34,82 @@ impl<A: TearDown, B: TearDown> TearDown for (A, B) {
     }
 }
 
-pub struct Fixture<T, G: TearDown> {
+/// A fixture's value with its teardown guard. A fixture built by `with_teardown()` keeps its
+/// value till the end (`F` is the teardown that takes it): it can lend it or give a clone of it.
+pub struct Fixture<T, G: TearDown, F: FnOnce(T) = fn(T)> {
     inner: Option<T>,
     guard: Option<G>,
+    teardown: Option<F>,
 }
 
 impl<T, G: TearDown> Fixture<T, G> {
     pub fn new(inner: T, guard: G) -> Self {
-        Fixture { inner: Some(inner), guard: Some(guard) }
+        Fixture { inner: Some(inner), guard: Some(guard), teardown: None }
     }
 
     pub fn take(&mut self) -> T {
         self.inner.take().unwrap()
     }
 
+    /// Split the fixture in its value and a fixture that hold just the guard: the
+    /// guard's `tear_down()` will be called when the returned fixture is dropped.
+    pub fn detach(mut self) -> (T, Fixture<(), G>) {
+        let inner = self.take();
+        (inner, Fixture::new((), self.guard()))
+    }
+}
+
+impl<T, G: TearDown, F: FnOnce(T)> Fixture<T, G, F> {
     pub fn guard(&mut self) -> G {
         self.guard.take().unwrap()
     }
 
-    pub fn compose<OTHER: TearDown>(mut self, guard: OTHER) -> Fixture<T, (G, OTHER)> {
-        Fixture::new(self.take(), (self.guard(), guard))
+    /// Lend the value: it stays in the fixture till the teardown.
+    pub fn value_mut(&mut self) -> &mut T {
+        self.inner.as_mut().unwrap()
+    }
+
+    /// Give the value without calling the teardown: just the guard is torn down.
+    pub fn release(mut self) -> T {
+        self.teardown = None;
+        self.inner.take().unwrap()
+    }
+
+    pub fn compose<OTHER: TearDown>(mut self, guard: OTHER) -> Fixture<T, (G, OTHER), F> {
+        Fixture {
+            inner: self.inner.take(),
+            guard: Some((self.guard(), guard)),
+            teardown: self.teardown.take(),
+        }
     }
 }
 
-impl<T: Debug, G: TearDown> Debug for Fixture<T, G> {
+/// Wrap `inner` in a fixture that keeps it and calls `teardown` with it when dropped.
+pub fn with_teardown<T, F: FnOnce(T)>(inner: T, teardown: F) -> Fixture<T, (), F> {
+    Fixture { inner: Some(inner), guard: Some(()), teardown: Some(teardown) }
+}
+
+impl<T: Debug, G: TearDown, F: FnOnce(T)> Debug for Fixture<T, G, F> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         write!(f, "Fixture<{:?}>", self.inner)
     }
 }
 
-impl<T, G: TearDown> Drop for Fixture<T, G> {
+impl<T, G: TearDown, F: FnOnce(T)> Drop for Fixture<T, G, F> {
     fn drop(&mut self) {
+        if let (Some(inner), Some(teardown)) = (self.inner.take(), self.teardown.take()) {
+            teardown(inner);
+        }
         self.guard.take().map(|g| g.tear_down());
     }
 }
 
+impl<T, G: TearDown, F: FnOnce(T)> TearDown for Fixture<T, G, F> {
+    fn tear_down(self) {
+        // The guard's tear_down() is called when the fixture is dropped
+    }
+}
+
 impl<T> From<T> for Fixture<T, EmptyGuard> {
     fn from(inner: T) -> Self {
         Fixture::new(inner, Default::default())
@@ -73,6 +130,39 @@ impl<F: FnOnce()->()> From<F> for TearDownClosure<F> {
     }
 }
 
+/// The instances of the shared fixtures of a test: every test creates its own cache and gives
+/// it to the fixtures that it uses.
+pub type SharedCache = Rc<RefCell<Vec<Box<dyn Any>>>>;
+
+/// A shared instance: the fixture is identified by the type that defines it and its name.
+struct Shared<K: ?Sized, T> {
+    name: &'static str,
+    value: T,
+    _fixture: PhantomData<K>,
+}
+
+/// Return a clone of the instance of the fixture `name` (defined in `K`) shared in `cache`:
+/// the first call creates it by `init`.
+pub fn shared<K: ?Sized + 'static, T: Clone + 'static>(cache: &SharedCache, name: &'static str, init: impl FnOnce() -> T) -> T {
+    let cached = cache
+        .borrow()
+        .iter()
+        .filter_map(|v| v.downcast_ref::<Shared<K, T>>())
+        .find(|s| s.name == name)
+        .map(|s| s.value.clone());
+    if let Some(value) = cached {
+        return value;
+    }
+    // The shared fixtures can use other shared fixtures: don't hold the borrow here
+    let value = init();
+    cache.borrow_mut().push(Box::new(Shared::<K, T> {
+        name,
+        value: value.clone(),
+        _fixture: PhantomData,
+    }));
+    value
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -132,6 +222,44 @@ mod test {
         assert_eq!(*guard.borrow(), vec!["destroyed_f","destroyed_f2","destroyed_f1"]);
     }
 
+    #[test]
+    fn with_teardown_should_pass_the_lent_value_to_teardown() {
+        struct Handle(Vec<&'static str>);
+
+        let guard = RefCell::new(vec![]);
+        {
+            let mut f = with_teardown(Handle(vec!["open"]), |h: Handle| {
+                guard.borrow_mut().extend(h.0)
+            });
+            f.value_mut().0.push("used");
+        }
+        assert_eq!(*guard.borrow(), vec!["open", "used"]);
+    }
+
+    #[test]
+    fn with_teardown_should_tear_down_the_value_before_the_composed_guards() {
+        let guard = RefCell::new(vec![]);
+        {
+            let _f = with_teardown("value", |v| guard.borrow_mut().push(v))
+                .compose(TearDownClosure(|| guard.borrow_mut().push("dependency")));
+        }
+        assert_eq!(*guard.borrow(), vec!["value", "dependency"]);
+    }
+
+    #[test]
+    fn detached_guard_should_tear_down_when_dropped() {
+        let guard = RefCell::new(vec![]);
+        let (value, detached) =
+            Fixture::new(42, TearDownClosure(|| guard.borrow_mut().push("destroyed")))
+                .detach();
+        assert_eq!(42, value);
+        assert_eq!(*guard.borrow(), Vec::<&str>::new());
+
+        drop(detached);
+
+        assert_eq!(*guard.borrow(), vec!["destroyed"]);
+    }
+
     #[test]
     fn after_moved_the_guard_fixture_can_be_released_without_invoke_tear_down() {
         let guard = RefCell::new(vec![]);
@@ -149,4 +277,51 @@ mod test {
         }
         assert_eq!(*guard.borrow(), Vec::<&str>::new());
     }
+
+    #[test]
+    fn shared_should_create_the_instance_just_once() {
+        let calls = RefCell::new(0);
+        let init = || { *calls.borrow_mut() += 1; Rc::new(RefCell::new(vec![1])) };
+
+        let cache = SharedCache::default();
+
+        let first = shared::<EmptyGuard, _>(&cache, "first", init);
+        first.borrow_mut().push(2);
+        let second = shared::<EmptyGuard, _>(&cache, "first", init);
+
+        assert_eq!(*second.borrow(), vec![1, 2]);
+        assert_eq!(1, *calls.borrow());
+    }
+
+    #[test]
+    fn shared_should_distinguish_the_fixtures_and_the_types() {
+        let cache = SharedCache::default();
+
+        assert_eq!(1, shared::<EmptyGuard, _>(&cache, "value", || 1));
+        assert_eq!(2, shared::<EmptyGuard, _>(&cache, "other", || 2));
+        assert_eq!(3, shared::<(), _>(&cache, "value", || 3));
+        assert_eq!("4", shared::<EmptyGuard, _>(&cache, "value", || "4"));
+        assert_eq!(1, shared::<EmptyGuard, _>(&cache, "value", || 5));
+    }
+
+    #[test]
+    fn shared_fixtures_can_use_other_shared_fixtures() {
+        let cache = SharedCache::default();
+
+        let value = shared::<EmptyGuard, _>(&cache, "outer", || {
+            shared::<EmptyGuard, _>(&cache, "inner", || 21) * 2
+        });
+
+        assert_eq!(42, value);
+        assert_eq!(21, shared::<EmptyGuard, _>(&cache, "inner", || 0));
+    }
+
+    #[test]
+    fn shared_should_create_an_instance_for_each_cache() {
+        let (cache, other) = (SharedCache::default(), SharedCache::default());
+
+        assert_eq!(1, shared::<EmptyGuard, _>(&cache, "value", || 1));
+        assert_eq!(2, shared::<EmptyGuard, _>(&other, "value", || 2));
+        assert_eq!(1, shared::<EmptyGuard, _>(&cache, "value", || 3));
+    }
 }
@@ -3,28 +3,147 @@ use std::collections::HashMap;
 
 use proc_macro2::TokenStream;
 use syn::spanned::Spanned;
-use syn::ItemFn;
+use syn::visit::Visit;
+use syn::{Ident, ItemFn, ItemImpl, ItemMod};
 
 use crate::parse::{
+    combinations::{check_filters, check_zipped, Matrix},
     fixture::FixtureInfo,
-    rstest::{RsTestData, RsTestInfo},
+    rstest::{RsTestData, RsTestInfo, RsTestItem},
+    testcase::TestCase,
 };
 use crate::refident::MaybeIdent;
+use crate::render::fixture::fixture_value_type;
 
-use super::utils::fn_args_has_ident;
+use super::utils::{contains_impl_trait, fn_args_has_ident};
 
 pub(crate) fn rstest(test: &ItemFn, info: &RsTestInfo) -> TokenStream {
     missed_arguments(test, info.data.items.iter())
-        .chain(duplicate_arguments(info.data.items.iter()))
+        .chain(duplicate_arguments(without_case_defaults(&info.data)))
         .chain(invalid_cases(&info.data))
         .chain(case_args_without_cases(&info.data))
+        .chain(invalid_name_template(info))
+        .chain(duplicate_case_names(info))
+        .chain(invalid_type_lists(test, &info.data))
+        .map(|e| e.to_compile_error())
+        .collect()
+}
+
+/// The `#[zip]` arguments and the `#[exclude]` and `#[only]` filters should be checked when
+/// all the values lists (also the parametrized fixtures' ones) are known.
+pub(crate) fn combinations(info: &RsTestInfo) -> TokenStream {
+    let matrix = Matrix::from_test(&info.data, &info.attributes);
+    let zipped = info.attributes.zipped().collect::<Vec<_>>();
+    let filters = info.data.filters().collect::<Vec<_>>();
+    check_zipped(&matrix, &zipped)
+        .into_iter()
+        .chain(check_filters(&matrix, &filters))
         .map(|e| e.to_compile_error())
         .collect()
 }
 
+pub(crate) fn suite(args: &TokenStream, suite: &ItemMod) -> TokenStream {
+    let mut errors = vec![];
+    if !args.is_empty() {
+        errors.push(syn::Error::new_spanned(
+            args,
+            "#[rstest_suite] doesn't take any argument",
+        ));
+    }
+    if suite.content.is_none() {
+        errors.push(syn::Error::new_spanned(
+            suite,
+            "#[rstest_suite] should be applied to a module with a body: `mod name { ... }`",
+        ));
+    }
+    errors.into_iter().map(|e| e.to_compile_error()).collect()
+}
+
+/// `#[fixture]` on an `impl` block: its associated functions can be fixtures.
+pub(crate) fn fixture_impl(args: &TokenStream, item_impl: &ItemImpl) -> TokenStream {
+    let mut errors = vec![];
+    if !args.is_empty() {
+        errors.push(syn::Error::new_spanned(
+            args,
+            "#[fixture] on an impl block doesn't take any argument",
+        ));
+    }
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        errors.push(syn::Error::new_spanned(
+            path,
+            "#[fixture] cannot be applied to a trait implementation: use an inherent impl block",
+        ));
+    }
+    errors.into_iter().map(|e| e.to_compile_error()).collect()
+}
+
+/// The fixtures' arguments are injected: the methods' receiver cannot be.
+pub(crate) fn receiver(fixture: &ItemFn) -> TokenStream {
+    match fixture.sig.receiver() {
+        Some(receiver) => syn::Error::new_spanned(
+            receiver,
+            "Cannot apply #[fixture] to a method with a `self` receiver: use an associated function.",
+        )
+        .to_compile_error(),
+        None => Default::default(),
+    }
+}
+
+/// A fixture that uses `Self` in its signature is an associated function: its items can be
+/// rendered in the `impl` only if the `impl` block is marked by `#[fixture]` too.
+pub(crate) fn unmarked_impl(fixture: &ItemFn) -> TokenStream {
+    #[derive(Default)]
+    struct SelfType(Option<Ident>);
+
+    impl<'ast> Visit<'ast> for SelfType {
+        fn visit_path(&mut self, path: &'ast syn::Path) {
+            match path.segments.first() {
+                Some(segment) if segment.ident == "Self" => {
+                    self.0.get_or_insert_with(|| segment.ident.clone());
+                }
+                _ => syn::visit::visit_path(self, path),
+            }
+        }
+    }
+
+    let mut self_type = SelfType::default();
+    self_type.visit_signature(&fixture.sig);
+    match self_type.0 {
+        Some(self_type) => syn::Error::new(
+            self_type.span(),
+            "Cannot use `Self` in a fixture outside an impl block marked with #[fixture]: mark the impl block with #[fixture] to define associated fixtures.",
+        )
+        .to_compile_error(),
+        None => Default::default(),
+    }
+}
+
+/// The fixtures defined as associated functions cannot define items outside the `impl`.
+pub(crate) fn associated_fixture(fixture: &ItemFn, info: &FixtureInfo) -> TokenStream {
+    if info.is_parametrized() {
+        syn::Error::new(
+            fixture.sig.ident.span(),
+            "Cannot use #[values] or #[params] in associated fixture.",
+        )
+        .to_compile_error()
+    } else {
+        Default::default()
+    }
+}
+
 pub(crate) fn fixture(test: &ItemFn, info: &FixtureInfo) -> TokenStream {
     missed_arguments(test, info.data.items.iter())
         .chain(duplicate_arguments(info.data.items.iter()))
+        .chain(async_once(test, info))
+        .chain(generics_once(test, info))
+        .chain(impl_return_once(test, info))
+        .chain(async_teardown(test, info))
+        .chain(once_teardown(info))
+        .chain(async_params(test, info))
+        .chain(once_params(info))
+        .chain(async_shared(test, info))
+        .chain(generics_shared(test, info))
+        .chain(teardown_shared(test, info))
         .map(|e| e.to_compile_error())
         .collect()
 }
@@ -153,22 +272,211 @@ fn duplicate_arguments<'a, I: MaybeIdent + Spanned + 'a>(
     )
 }
 
-fn invalid_cases(params: &RsTestData) -> Errors {
-    let n_args = params.case_args().count();
-    Box::new(
-        params
-            .cases()
-            .filter(move |case| case.args.len() != n_args)
-            .map(|case| {
-                syn::Error::new_spanned(
-                    &case,
-                    "Wrong case signature: should match the given parameters list.",
-                )
-            }),
-    )
+fn async_shared<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match (test.sig.asyncness, info.attributes.get_shared()) {
+        (Some(_asyncness), Some(shared)) => Box::new(std::iter::once(syn::Error::new(
+            shared.span(),
+            "Cannot apply #[shared] to async fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn generics_shared<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match (
+        test.sig.generics.type_params().next(),
+        info.attributes.get_shared(),
+    ) {
+        (Some(_), Some(shared)) => Box::new(std::iter::once(syn::Error::new(
+            shared.span(),
+            "Cannot apply #[shared] on generic fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
+    }
 }
 
-fn case_args_without_cases(params: &RsTestData) -> Errors {
+/// The shared instance is cloned: the teardown would be called for every clone.
+fn teardown_shared<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    let has_teardown =
+        info.attributes.get_teardown().is_some() || fixture_value_type(&test.sig.output).is_some();
+    match info.attributes.get_shared() {
+        Some(shared) if has_teardown => Box::new(std::iter::once(syn::Error::new(
+            shared.span(),
+            "Cannot apply #[shared] to a fixture with teardown.",
+        ))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn async_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match (test.sig.asyncness, info.attributes.get_once()) {
+        (Some(_asyncness), Some(once)) => Box::new(std::iter::once(syn::Error::new(
+            once.span(),
+            "Cannot apply #[once] to async fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn generics_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match (
+        test.sig.generics.params.is_empty(),
+        info.attributes.get_once(),
+    ) {
+        (false, Some(once)) => Box::new(std::iter::once(syn::Error::new(
+            once.span(),
+            "Cannot apply #[once] on generic fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn impl_return_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match (&test.sig.output, info.attributes.get_once()) {
+        (syn::ReturnType::Type(_, t), Some(once)) if contains_impl_trait(t) => {
+            Box::new(std::iter::once(syn::Error::new(
+                once.span(),
+                "Cannot apply #[once] on fixture that returns an `impl` type.",
+            )))
+        }
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn async_teardown<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match (test.sig.asyncness, info.attributes.get_teardown()) {
+        (Some(_asyncness), Some((teardown, _))) => Box::new(std::iter::once(syn::Error::new(
+            teardown.span(),
+            "Cannot apply #[teardown] to async fixture.",
+        ))),
+        (Some(_asyncness), None) if fixture_value_type(&test.sig.output).is_some() => {
+            let output = match &test.sig.output {
+                syn::ReturnType::Type(_, t) => t.span(),
+                rt => rt.span(),
+            };
+            Box::new(std::iter::once(syn::Error::new(
+                output,
+                "Cannot return `rstest_fixtures::Fixture` from async fixture.",
+            )))
+        }
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn once_teardown<'a>(info: &FixtureInfo) -> Errors<'a> {
+    match (info.attributes.get_once(), info.attributes.get_teardown()) {
+        (Some(_once), Some((teardown, _))) => Box::new(std::iter::once(syn::Error::new(
+            teardown.span(),
+            "Cannot apply #[teardown] to #[once] fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn async_params<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match test.sig.asyncness {
+        Some(asyncness) if info.is_parametrized() => Box::new(std::iter::once(syn::Error::new(
+            asyncness.span(),
+            "Cannot use #[values] or #[params] in async fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn once_params<'a>(info: &FixtureInfo) -> Errors<'a> {
+    match info.attributes.get_once() {
+        Some(once) if info.is_parametrized() => Box::new(std::iter::once(syn::Error::new(
+            once.span(),
+            "Cannot apply #[once] to parametrized fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+/// The default values of the `#[case]` arguments are not duplicates: they are used when a
+/// case omits the argument.
+fn without_case_defaults(params: &RsTestData) -> impl Iterator<Item = &RsTestItem> {
+    let case_args = params.case_args().collect::<Vec<_>>();
+    params.items.iter().filter(move |item| match item {
+        RsTestItem::ArgumentValue(value) => !case_args.contains(&&value.name),
+        _ => true,
+    })
+}
+
+/// The named values should be `#[case]` arguments that are not already given.
+fn invalid_named_values<'a>(case: &'a TestCase, case_args: &[&'a Ident]) -> Vec<syn::Error> {
+    let mut given = case_args
+        .iter()
+        .take(case.args.len())
+        .cloned()
+        .collect::<Vec<_>>();
+    case.named
+        .iter()
+        .filter_map(|value| {
+            if !case_args.contains(&&value.name) {
+                let expected = case_args
+                    .iter()
+                    .map(|arg| format!("'{}'", arg))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Some(syn::Error::new(
+                    value.name.span(),
+                    format!(
+                        "'{}' is not a #[case] argument: should be one of {}",
+                        value.name, expected
+                    ),
+                ))
+            } else if given.contains(&&value.name) {
+                Some(syn::Error::new(
+                    value.name.span(),
+                    format!("Duplicate value for '{}' case argument.", value.name),
+                ))
+            } else {
+                given.push(&value.name);
+                None
+            }
+        })
+        .collect()
+}
+
+/// A case cannot give more values than its arguments. The omitted trailing arguments and the
+/// `_` ones are resolved like any other test's argument: by their default values or fixtures.
+fn invalid_cases(params: &RsTestData) -> Errors<'_> {
+    let case_args = params.case_args().collect::<Vec<_>>();
+    Box::new(params.cases().flat_map(move |case| {
+        if case.args.len() > case_args.len() {
+            return vec![syn::Error::new_spanned(
+                &case,
+                "Wrong case signature: should match the given parameters list.",
+            )];
+        }
+        invalid_named_values(case, &case_args)
+    }))
+}
+
+/// The types lists should replace the test's type parameters, each one just once.
+fn invalid_type_lists<'a>(test: &'a ItemFn, params: &'a RsTestData) -> Errors<'a> {
+    let mut defined = vec![];
+    Box::new(params.type_lists().filter_map(move |list| {
+        let ident = &list.ident;
+        if !test.sig.generics.type_params().any(|tp| &tp.ident == ident) {
+            Some(syn::Error::new(
+                ident.span(),
+                format!("'{}' is not a type parameter of the test function", ident),
+            ))
+        } else if defined.contains(&ident) {
+            Some(syn::Error::new(
+                ident.span(),
+                format!("Duplicate types list: '{}' is already defined.", ident),
+            ))
+        } else {
+            defined.push(ident);
+            None
+        }
+    }))
+}
+
+fn case_args_without_cases(params: &RsTestData) -> Errors<'_> {
     if !params.has_cases() {
         return Box::new(
             params
@@ -178,3 +486,59 @@ fn case_args_without_cases(params: &RsTestData) -> Errors {
     }
     Box::new(std::iter::empty())
 }
+
+/// A name rendered by the template can be the same of the default name of another case.
+fn duplicate_case_names(info: &RsTestInfo) -> Errors<'_> {
+    let template = match info.attributes.name_template() {
+        Some(template) => template,
+        None => return Box::new(std::iter::empty()),
+    };
+    let names = crate::render::names::cases_names(&info.data, &info.attributes);
+    let mut occurrences = HashMap::new();
+    names
+        .iter()
+        .for_each(|name| *occurrences.entry(name.as_str()).or_insert(0) += 1);
+    let span = template.template.span();
+    let errors = names
+        .iter()
+        // Report every duplicate name just once
+        .filter(|name| occurrences.remove(name.as_str()).unwrap_or_default() > 1)
+        .map(|name| {
+            syn::Error::new(
+                span,
+                format!(
+                    "Duplicate test name '{}': the name template gives to a case the name of another one",
+                    name
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+    Box::new(errors.into_iter())
+}
+
+fn invalid_name_template(info: &RsTestInfo) -> Errors<'_> {
+    let template = match info.attributes.name_template() {
+        Some(template) => template,
+        None => return Box::new(std::iter::empty()),
+    };
+    let case_args = info
+        .data
+        .case_args()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let span = template.template.span();
+    let errors = template
+        .args()
+        .filter(|arg| !case_args.iter().any(|a| a == arg))
+        .map(|arg| {
+            syn::Error::new(
+                span,
+                format!(
+                    "Invalid placeholder '{{{}}}' in name template: should be a #[case] argument",
+                    arg
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+    Box::new(errors.into_iter())
+}
@@ -244,11 +244,18 @@ mod render;
 mod resolver;
 mod utils;
 
-use syn::{parse_macro_input, ItemFn};
+use syn::{parse_macro_input, parse_quote, ImplItem, ImplItemMethod, ItemFn, ItemImpl, ItemMod};
 
-use crate::parse::{fixture::FixtureInfo, future::ReplaceFutureAttribute, rstest::RsTestInfo};
+use crate::parse::{
+    fixture::FixtureInfo,
+    future::ReplaceFutureAttribute,
+    params::{ParamsCallback, ParamsGroup, ParamsTarget},
+    rstest::RsTestInfo,
+    suite::SuiteInstance,
+};
 use parse::ExtendWithFunctionAttrs;
 use quote::ToTokens;
+use utils::attr_ends_with;
 
 /// Define a fixture that you can use in all `rstest`'s test arguments. You should just mark your
 /// function as `#[fixture]` and then use it as a test's argument. Fixture functions can also
@@ -334,6 +341,188 @@ use quote::ToTokens;
 /// }
 /// ```
 ///
+/// An `async` fixture can use other `async` fixtures too: mark the argument with
+/// `#[future(awt)]` and it will be awaited before your fixture's body is executed
+/// (`#[awt]` on the function awaits all `#[future]` arguments).
+///
+/// ```
+/// use rstest::*;
+///
+/// #[fixture]
+/// async fn base() -> u32 { 21 }
+///
+/// #[fixture]
+/// async fn double(#[future(awt)] base: u32) -> u32 { base * 2 }
+///
+/// #[rstest]
+/// async fn the_test(#[future(awt)] double: u32) {
+///     assert_eq!(42, double)
+/// }
+/// ```
+///
+/// # Once Fixture
+///
+/// Expensive fixtures (i.e. load a big file or build a database schema) can be computed just
+/// once for the whole test binary by marking them with the `#[once]` attribute. The fixture
+/// function is called the first time it's needed and every test gets a `&'static`
+/// reference to the same value:
+///
+/// ```
+/// use rstest::*;
+///
+/// #[fixture]
+/// #[once]
+/// fn once_fixture() -> i32 { 42 }
+///
+/// #[rstest]
+/// fn single(once_fixture: &i32) {
+///     assert_eq!(&42, once_fixture)
+/// }
+/// ```
+///
+/// Tests can run in different threads, so the returned type must implement `Sync`. You
+/// cannot use `#[once]` on `async` or generic fixtures, or on fixtures that return an `impl`
+/// type. Note that the value is computed by the first call: arguments given by
+/// [partial injection](#partial-injection) in other tests will be ignored.
+///
+/// # Teardown
+///
+/// A fixture can clean up what it built when the test ends by using the `Fixture` and
+/// `TearDown` types from `rstest_fixtures` crate (you should add it to your
+/// `dev-dependencies`). You can give a closure to `#[teardown(...)]` attribute: it will
+/// be called with the fixture's value, so the fixture keeps it and the test (or the fixture)
+/// that uses it takes it by reference (`&T` or `&mut T`) or, if `T` is `Clone`, by value: the
+/// teardown gets a clone of it then. Otherwise the fixture can return a
+/// `rstest_fixtures::Fixture<T, G>` with its own guard: it must be written by this path.
+///
+/// ```rust,ignore
+/// use rstest::*;
+/// use rstest_fixtures::TearDownClosure;
+///
+/// #[fixture]
+/// #[teardown(|db| db.drop_schema())]
+/// fn db() -> Db {
+///     Db::create_schema("test")
+/// }
+///
+/// #[fixture]
+/// fn server() -> rstest_fixtures::Fixture<Server, TearDownClosure<fn()>> {
+///     rstest_fixtures::Fixture::new(
+///         Server::start(),
+///         TearDownClosure::from(Server::stop_all as fn()),
+///     )
+/// }
+///
+/// #[fixture]
+/// fn repo(db: &Db) -> Repo {
+///     Repo::new(db.url())
+/// }
+///
+/// #[rstest]
+/// fn should_query(db: &mut Db, repo: Repo, server: Server) {
+///     // db is lent by its fixture, repo and server are plain values here
+/// }
+/// ```
+///
+/// The guards live till the end of the test, even if it panics: also the ones of the
+/// fixtures used by a fixture without teardown (like `repo` above). Guards are dropped in
+/// reverse order: the teardown of a fixture runs before the ones of the fixtures that it
+/// uses. `#[teardown]` cannot be used on `async` or `#[once]` fixtures and an `async`
+/// fixture cannot return a `Fixture`. Note that the `get()`, `default()`, `partial_n()` and
+/// `build()` functions return the type written in the fixture's signature when you call them
+/// directly: no teardown runs, neither the fixture's one nor the ones of the fixtures that it
+/// uses.
+///
+/// # Shared Fixtures
+///
+/// Every fixture injection computes a new value: if a test uses `repo` and `service`
+/// and both take a `db` argument, they get two different databases. Mark the argument by
+/// `#[shared]` to resolve it by a single instance for the whole test: the first one that
+/// needs it creates the value and the others get a clone of it. Like
+/// [teardown](#teardown) you need `rstest_fixtures` in your `dev-dependencies`.
+///
+/// ```rust,ignore
+/// use rstest::*;
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// #[fixture]
+/// fn db() -> Rc<RefCell<Db>> {
+///     Rc::new(RefCell::new(Db::create_schema("test")))
+/// }
+///
+/// #[fixture]
+/// fn repo(#[shared] db: Rc<RefCell<Db>>) -> Repo {
+///     Repo::new(db)
+/// }
+///
+/// #[fixture]
+/// fn service(#[shared] db: Rc<RefCell<Db>>) -> Service {
+///     Service::new(db)
+/// }
+///
+/// #[rstest]
+/// fn should_see_the_same_data(#[shared] db: Rc<RefCell<Db>>, repo: Repo, service: Service) {
+///     // repo, service and the test use the same db
+/// }
+/// ```
+///
+/// You can also mark the fixture itself by `#[shared]`: every argument that is resolved by
+/// its default value shares the same instance. The fixture's type must implement `Clone`
+/// and be `'static` (use `Rc` or `Arc` to share a real instance rather than equal copies).
+/// Every generated test has its own instances, and the `default()`, `partial_n()` and
+/// `build()` functions of a fixture create new ones each time they are called.
+/// `#[shared]` cannot be used together with `#[with(...)]`, nor on `async` or generic
+/// fixtures, or on fixtures with a teardown.
+///
+/// # Parametrized Fixtures
+///
+/// You can give to a fixture a list of values by `#[values(...)]` attribute: they are
+/// assigned in turn to the fixture's first argument. Every test that marks the argument
+/// with `#[params]` will be expanded in a test for each value.
+///
+/// ```
+/// use rstest::*;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Backend { Sqlite, Postgres }
+///
+/// #[fixture]
+/// #[values(Backend::Sqlite, Backend::Postgres)]
+/// fn backend(kind: Backend) -> Backend {
+///     kind
+/// }
+///
+/// #[fixture]
+/// fn db(#[params] backend: Backend) -> String {
+///     format!("{:?}", backend)
+/// }
+///
+/// #[rstest]
+/// fn use_backend(#[params] backend: Backend) {
+///     // backend_1_Sqlite and backend_2_Postgres tests
+/// }
+///
+/// #[rstest]
+/// fn use_db(#[params] db: String) {
+///     // db_1_Sqlite and db_2_Postgres tests
+/// }
+/// ```
+///
+/// A fixture that uses a parametrized fixture by `#[params]` is parametrized too and
+/// multiply its tests for each combination of the values of all parametrized fixtures
+/// that it uses. If a value is a path (like `Backend::Sqlite`) its last segment is
+/// appended to the test name.
+///
+/// The procedural macro cannot look at the fixture definition from the test: every
+/// parametrized fixture defines a macro with the fixture's name that the test calls
+/// to know its values. So parametrized fixtures should be defined in the same crate
+/// and imported in the test's module (a `use` of the fixture also imports its macro).
+/// `#[params]` on a fixture that is not parametrized fails with a *cannot find macro*
+/// error. Every test or fixture that injects a parametrized fixture, also through another
+/// fixture, should mark the argument with `#[params]`: otherwise the compiler reports that
+/// a `backend_is_a_parametrized_fixture_mark_the_argument_with_params` bound is not
+/// satisfied. The fixture's `default()`, `partial_n()` and `build()` functions use its
+/// first values. Parametrized fixtures cannot be `async` or `#[once]`.
 ///
 /// # Partial Injection
 ///
@@ -363,6 +552,29 @@ use quote::ToTokens;
 /// attribute will inject `v1, ..., vn` expression as fixture arguments: all remaining arguments
 /// will be resolved as fixtures.
 ///
+/// You can also give the values by the arguments' names: `#[with(name = value, ...)]`
+/// injects just the named arguments and resolves all the others by their fixtures or
+/// default values. Positional values, if any, must precede the named ones.
+///
+/// ```
+/// use rstest::*;
+///
+/// #[fixture]
+/// fn user(#[default("Alice")] name: &str, #[default(22)] age: u8) -> (String, u8) {
+///     (name.to_owned(), age)
+/// }
+///
+/// #[fixture]
+/// fn older(#[with(age = 40)] user: (String, u8)) -> (String, u8) { user }
+///
+/// #[rstest]
+/// fn the_test(older: (String, u8), #[with("Bob", age = 30)] user: (String, u8)) {
+///     assert_eq!(("Alice".to_owned(), 40), older);
+///     assert_eq!(("Bob".to_owned(), 30), user);
+/// }
+/// ```
+/// An unknown name is reported as a *no method named `...` found* error on the
+/// name itself. Named values are resolved by the fixture's [builder](#builder).
 ///
 /// Sometimes the return type cannot be infered so you must define it: For the few times you may
 /// need to do it, you can use the `#[default(type)]`, `#[partial_n(type)]` function attribute
@@ -399,7 +611,114 @@ use quote::ToTokens;
 ///     assert_eq!((42.0, -42), fx.next().unwrap())
 /// }
 /// ```
-/// `partial_i` is the fixture used when you inject the first `i` arguments in test call.
+/// `partial_i` is the fixture used when you inject the first `i` arguments in test call:
+/// it sets them by the fixture's [builder](#builder) if it can resolve the other ones.
+///
+/// # Rename
+///
+/// You can use `#[from(fixture_path)]` to inject a fixture in an argument with a different
+/// name: the fixture can be referred by its path (no `use` needed) and the same fixture
+/// can be injected more times with different `#[with(...)]` arguments.
+///
+/// ```
+/// use rstest::*;
+///
+/// mod users {
+///     use rstest::*;
+///
+///     #[fixture]
+///     pub fn user(#[default("Alice")] name: &str) -> String {
+///         name.to_owned()
+///     }
+/// }
+///
+/// #[fixture]
+/// fn couple(#[from(users::user)] first: String, #[from(users::user)] #[with("Bob")] second: String) -> (String, String) {
+///     (first, second)
+/// }
+///
+/// #[rstest]
+/// fn should_resolve_renamed(couple: (String, String)) {
+///     assert_eq!(("Alice".to_owned(), "Bob".to_owned()), couple);
+/// }
+/// ```
+///
+/// # Builder
+///
+/// Outside of `rstest` (helper functions, benches, doctests) you can call a fixture by
+/// `fixture::get(...)` with all its arguments or by `fixture::default()`. If you need to
+/// override just some arguments use `fixture::builder()`: every argument has a `with_arg()`
+/// setter and `build()` resolves the arguments that you didn't set like `default()` does.
+///
+/// ```
+/// use rstest::*;
+///
+/// #[fixture]
+/// fn name() -> &'static str { "Alice" }
+///
+/// #[fixture]
+/// fn user(name: &str, #[default(22)] age: u8, #[default("guest")] role: &str) -> String {
+///     format!("{} ({}, {})", name, age, role)
+/// }
+///
+/// fn admin() -> String {
+///     user::builder().with_role("admin").build()
+/// }
+///
+/// assert_eq!("Alice (22, admin)", admin());
+/// assert_eq!("Bob (22, guest)", user::builder().with_name("Bob").build());
+/// ```
+///
+/// The `build()` method of an `async` fixture is `async` too. The arguments with an
+/// `impl Trait` type or that use the fixture's type parameters take the type of the given
+/// value: `build()` cannot resolve the missing ones if their type parameters are fixed by the
+/// given arguments or by the fixture's output, so you must set them too. Moreover `build()`
+/// resolves just the ones that follow the last one that you set: set also the ones before it.
+///
+/// # Associated Fixtures
+///
+/// If your helpers are associated functions of some type you can mark the `impl` block with
+/// `#[fixture]` and use `#[fixture]` on its associated functions: you refer them by
+/// `#[from(Type::name)]`, by the qualified path `#[from(<Type>::name)]` or by `Self::name`
+/// in the same `impl`.
+///
+/// ```
+/// use rstest::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// pub struct TestServer { port: u16, started: bool }
+///
+/// #[fixture]
+/// impl TestServer {
+///     #[fixture]
+///     pub fn stopped(#[default(8080)] port: u16) -> Self {
+///         Self { port, started: false }
+///     }
+///
+///     #[fixture]
+///     pub fn started(#[from(Self::stopped)] mut server: Self) -> Self {
+///         server.started = true;
+///         server
+///     }
+/// }
+///
+/// #[rstest]
+/// fn check_server(
+///     #[from(TestServer::started)] server: TestServer,
+///     #[from(<TestServer>::stopped)] #[with(42)] other: TestServer
+/// ) {
+///     assert!(server.started);
+///     assert_eq!(42, other.port);
+/// }
+/// ```
+///
+/// The other associated functions are left as they are and the fixtures can be called
+/// directly too. A fixture cannot take `self`, cannot be parametrized by `#[values]` or
+/// `#[params]` and has no [builder](#builder). The type in `Type::name` is recognized by the
+/// naming conventions: a path like `server::started` refers to a module's fixture while in
+/// `Server::started` the `UpperCamelCase` name is a type (write `<server>::started` for a
+/// type with a different name). If you forget to mark the `impl` block, the compiler reports
+/// the `Self` used by the fixture's signature.
 ///
 /// # Old _compact_ syntax
 ///
@@ -456,14 +775,35 @@ pub fn fixture(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let mut info: FixtureInfo = parse_macro_input!(args as FixtureInfo);
-    let mut fixture = parse_macro_input!(input as ItemFn);
+    if let Ok(item_impl) = syn::parse::<ItemImpl>(input.clone()) {
+        return fixtures_impl(args.into(), item_impl).into();
+    }
+    let info: FixtureInfo = parse_macro_input!(args as FixtureInfo);
+    let fixture = parse_macro_input!(input as ItemFn);
+
+    expand_fixture(info, fixture, false).into()
+}
 
-    let replace_result = ReplaceFutureAttribute::replace(&mut fixture);
+fn expand_fixture(
+    mut info: FixtureInfo,
+    mut fixture: ItemFn,
+    associated: bool,
+) -> proc_macro2::TokenStream {
+    let errors = error::receiver(&fixture);
+    if !errors.is_empty() {
+        return errors;
+    }
+    let replace_result = ReplaceFutureAttribute::replace(&mut fixture)
+        .map(|awaited| info.attributes.set_awaited(awaited));
     let extend_result = info.extend_with_function_attrs(&mut fixture);
 
     let mut errors = error::fixture(&fixture, &info);
 
+    if associated {
+        error::associated_fixture(&fixture, &info).to_tokens(&mut errors);
+    } else {
+        error::unmarked_impl(&fixture).to_tokens(&mut errors);
+    }
     if let Err(attrs_errors) = replace_result {
         attrs_errors.to_tokens(&mut errors);
     }
@@ -471,12 +811,57 @@ pub fn fixture(
         attrs_errors.to_tokens(&mut errors);
     }
 
-    if errors.is_empty() {
-        render::fixture(fixture, info).into()
-    } else {
+    if !errors.is_empty() {
         errors
+    } else if associated {
+        render::fixture::render_associated(fixture, info)
+    } else {
+        render::fixture(fixture, info)
+    }
+}
+
+/// `#[fixture]` on an `impl` block: render the associated functions marked by `#[fixture]`
+/// as fixtures.
+fn fixtures_impl(
+    args: proc_macro2::TokenStream,
+    mut item_impl: ItemImpl,
+) -> proc_macro2::TokenStream {
+    let errors = error::fixture_impl(&args, &item_impl);
+    if !errors.is_empty() {
+        return errors;
+    }
+    item_impl.items = item_impl
+        .items
+        .into_iter()
+        .map(|item| match item {
+            ImplItem::Method(method) => ImplItem::Verbatim(associated_fixture(method)),
+            item => item,
+        })
+        .collect();
+    item_impl.into_token_stream()
+}
+
+fn associated_fixture(mut method: ImplItemMethod) -> proc_macro2::TokenStream {
+    let is_fixture = |attr: &syn::Attribute| attr_ends_with(attr, &parse_quote! { fixture });
+    let attr = match method.attrs.iter().position(is_fixture) {
+        Some(position) => method.attrs.remove(position),
+        None => return method.into_token_stream(),
+    };
+    let info = if attr.tokens.is_empty() {
+        Ok(FixtureInfo::default())
+    } else {
+        attr.parse_args::<FixtureInfo>()
+    };
+    let fixture = ItemFn {
+        attrs: method.attrs,
+        vis: method.vis,
+        sig: method.sig,
+        block: Box::new(method.block),
+    };
+    match info {
+        Ok(info) => expand_fixture(info, fixture, true),
+        Err(err) => err.to_compile_error(),
     }
-    .into()
 }
 
 /// The attribute that you should use for your tests. Your
@@ -556,6 +941,30 @@ pub fn fixture(
 /// }
 /// ```
 ///
+/// `#[with(age = 30)]` (or `user(age = 30)` in the compact syntax) overrides just the
+/// `age` argument: see [named values](attr.fixture.html#partial-injection).
+///
+/// If you need the same fixture more than once or a fixture defined in another module
+/// you can use `#[from(fixture_path)]` to choose the fixture that resolve the argument:
+///
+/// ```
+/// # struct User(String, u8);
+/// # impl User { fn name(&self) -> &str {&self.0} }
+/// use rstest::*;
+///
+/// #[fixture]
+/// fn user(
+///     #[default("Alice")] name: impl AsRef<str>,
+///     #[default(22)] age: u8
+/// ) -> User { User(name.as_ref().to_owned(), age) }
+///
+/// #[rstest]
+/// fn check_users(#[from(user)] #[with("Bob")] admin: User, #[from(user)] guest: User) {
+///     assert_eq!("Bob", admin.name());
+///     assert_eq!("Alice", guest.name());
+/// }
+/// ```
+///
 /// ## Test Parametrized Cases
 ///
 /// If you would execute your test for a set of input data cases
@@ -726,6 +1135,169 @@ pub fn fixture(
 /// fn fail(#[case] v: u32) { assert_eq!(0, v) }
 /// ```
 ///
+/// ### Expected Results
+///
+/// Instead of declaring an `expected` argument and checking it in the test's body you can
+/// write the expected result at the end of the case's arguments: the test returns a
+/// value and the generated test compares it with the expected one by `assert_eq!`.
+///
+/// ```
+/// # use rstest::rstest;
+/// #[rstest]
+/// #[case(2, 3 => 5)]
+/// #[case(-1, 1 => 0)]
+/// fn sum(#[case] a: i32, #[case] b: i32) -> i32 {
+///     a + b
+/// }
+/// ```
+///
+/// Other than a value you can use:
+///
+/// - `=> panics` or `=> panics "message"`: the case should panic (like
+/// `#[should_panic(expected = "message")]`)
+/// - `=> matches Pattern` or `=> matches Pattern if guard`: the returned value should
+/// match the pattern
+/// - `=> err` or `=> err(Pattern)`: the test returns a `Result` that should be an error
+/// (that matches the pattern)
+///
+/// ```
+/// # use rstest::rstest;
+/// # #[derive(Debug)]
+/// # enum Error { Empty, NotANumber }
+/// #[rstest]
+/// #[case("42" => matches Ok(42))]
+/// #[case("42000" => matches Ok(n) if n > 1000)]
+/// #[case("" => err(Error::Empty))]
+/// #[case("x" => err)]
+/// #[case("0" => panics "zero")]
+/// fn parse(#[case] input: &str) -> Result<u32, Error> {
+///     match input {
+///         "" => Err(Error::Empty),
+///         "0" => panic!("zero"),
+///         s => s.parse().map_err(|_| Error::NotANumber),
+///     }
+/// }
+/// ```
+///
+/// The returned value should implement `Debug` (and `PartialEq` to compare it with a
+/// value). Rust's attributes cannot contain `=>` after the parenthesis so the expected
+/// result goes inside them, but in the compact syntax you can write it also as
+/// `case(2, 3) => 5`.
+///
+/// ### Omitted Case Arguments
+///
+/// A case can omit its trailing arguments and use `_` in place of the other ones: these
+/// arguments are resolved like any other test's argument. So they take the value given by the
+/// `#[default(expr)]` argument attribute if any, otherwise the one of the fixture with the
+/// same name.
+///
+/// ```
+/// # use rstest::*;
+/// #[fixture]
+/// fn separator() -> &'static str {
+///     "-"
+/// }
+///
+/// #[rstest]
+/// #[case("a", "b", "c", "+" => "a+b+c")]
+/// #[case("a", _, "c", "+" => "a+y+c")]
+/// #[case("a" => "a-y-z")]
+/// fn join(
+///     #[case] first: &str,
+///     #[case] #[default("y")] second: &str,
+///     #[case] #[default("z")] third: &str,
+///     #[case] separator: &str,
+/// ) -> String {
+///     [first, second, third].join(separator)
+/// }
+/// ```
+///
+/// If an omitted argument has no fixture, the compiler reports the missed fixture on the case.
+///
+/// `#[default(expr)]` can be used also on the arguments that are not `#[case]` arguments
+/// to give them a value without writing a fixture.
+///
+/// ### Named Case Values
+///
+/// The case's values can be given by the `#[case]` argument's name: `name = value`. The
+/// named values can follow the positional ones and can be written in any order.
+///
+/// ```
+/// # use rstest::rstest;
+/// #[rstest]
+/// #[case(width = 2, height = 3 => 6)]
+/// #[case(2, depth = 4, height = 3 => 24)]
+/// fn volume(
+///     #[case] width: u32,
+///     #[case] height: u32,
+///     #[case] #[default(1)] depth: u32,
+/// ) -> u32 {
+///     width * height * depth
+/// }
+/// ```
+///
+/// ### Cases table
+///
+/// When the cases have a lot of arguments you can write them as a table by
+/// `#[cases(table = r"...")]`: the first row contains the `#[case]` arguments' names (in
+/// any order) and every other row is a case. Each cell is parsed as a Rust expression,
+/// the markdown's separator rows like `|---|---|` are skipped and the `name` column
+/// (if it's not a case argument) becomes the case's description.
+///
+/// ```
+/// # use rstest::rstest;
+/// # fn fibonacci(input: u32) -> u32 { match input { 0 => 0, 1 => 1, n => fibonacci(n - 2) + fibonacci(n - 1) } }
+/// #[rstest]
+/// #[cases(table = r"
+///     | input | expected | name  |
+///     |-------|----------|-------|
+///     | 0     | 0        | zero  |
+///     | 1     | 1        | one   |
+///     | 2 * 5 | 55       | ten   |
+/// ")]
+/// fn fibonacci_test(#[case] input: u32, #[case] expected: u32) {
+///     assert_eq!(expected, fibonacci(input))
+/// }
+/// ```
+///
+/// Use `\|` if you need a `|` in an expression (for instance in a closure). You can
+/// mix tables and `#[case]` attributes: the table's cases follow the other ones.
+///
+/// ### Load cases from data files
+///
+/// With `#[cases_from("path")]` the cases are read at compile time from a CSV, TOML or JSON
+/// file (the path is relative to the crate's root) and every row becomes a case:
+/// the columns are mapped to the `#[case]` arguments by name.
+///
+/// ```ignore
+/// # use rstest::rstest;
+/// # fn fibonacci(input: u32) -> u32 { 0 }
+/// #[rstest]
+/// #[cases_from("vectors/fib.csv")]
+/// fn fibonacci_test(#[case] input: u32, #[case] expected: u32) {
+///     assert_eq!(expected, fibonacci(input))
+/// }
+/// ```
+///
+/// where `vectors/fib.csv` is
+///
+/// ```text
+/// input,expected,name
+/// 0,0,zero
+/// 10,`5 * 11`,ten
+/// ```
+///
+/// String cells are injected as string literals and so converted by the
+/// [magic conversion](#magic-conversion), while cells enclosed in backticks are parsed
+/// as Rust expressions. The `name` column (if it's not a case argument) becomes the
+/// case's description, otherwise the description is `row_<n>` where `<n>` is the
+/// file's line for CSV and the row's index for the other formats. A TOML file should
+/// contain `[[case]]` tables and a JSON file an array of objects: numbers and booleans
+/// are used as they are. Rows can share the same description because the test names
+/// are prefixed by the case's index. Every error in the data file (also an empty file)
+/// is reported with the file's line or the row's index, and the tests are compiled
+/// again when the file changes.
+///
 /// ## Values Lists
 ///
 /// Another useful way to write a test and execute it for some values
@@ -778,6 +1350,243 @@ pub fn fixture(
 /// Also value list implements the magic conversion feature: every time the value type
 /// implements `FromStr` trait you can use a literal string to define it.
 ///
+/// You can name the values by `name => value`: the named values' tests use the given
+/// name instead of the argument's name followed by the value's index.
+///
+/// ```
+/// # use rstest::*;
+/// #[rstest]
+/// fn should_handle(#[values(empty => "", ascii => "abc", emoji => "🦀", "x y")] input: &str) {
+///     assert!(input.len() < 5)
+/// }
+/// ```
+///
+/// generates `should_handle::empty`, `should_handle::ascii`, `should_handle::emoji` and
+/// `should_handle::input_4`. The names should be unique valid identifiers and work also in
+/// the compact syntax `input => [empty => "", ascii => "abc"]`.
+///
+/// Every value can also have some attributes like `#[should_panic]` or `#[ignore]`: they
+/// are added to all the tests that use the value, after the test function's and the
+/// case's attributes.
+///
+/// ```
+/// # use rstest::*;
+/// #[rstest]
+/// fn should_divide(
+///     #[values(1, 2, #[should_panic] 0, #[ignore] 1_000_000)] divisor: u32,
+///     #[values(10, 20)] dividend: u32,
+/// ) {
+///     assert!(dividend / divisor < 100)
+/// }
+/// ```
+///
+/// ### Filter the Combinations
+///
+/// Some combinations of values could be meaningless: `#[exclude(arg = value, ...)]` removes
+/// the combinations that match all the given conditions and `#[only(arg = value, ...)]`
+/// keeps just the combinations that match at least one `#[only]` rule. A condition's value
+/// matches if it's the same expression used in the values list or the value's name or
+/// label.
+///
+/// ```
+/// # use rstest::*;
+/// # fn connect(tls: bool, port: u16) -> Result<(), ()> { Ok(()) }
+/// #[rstest]
+/// #[exclude(tls = false, port = 443)]
+/// #[exclude(tls = true, port = http)]
+/// fn should_connect(
+///     #[values(true, false)] tls: bool,
+///     #[values(http => 80, 443, 8080)] port: u16
+/// ) {
+///     assert!(connect(tls, port).is_ok())
+/// }
+/// ```
+///
+/// The filters are applied at compile time, so the excluded combinations don't generate
+/// any test. A condition that refers to an argument without values list or to a value that
+/// is not in the list, a rule that doesn't match any combination and rules that remove
+/// all the combinations are compile errors.
+///
+/// ### Pairwise Combinations
+///
+/// The cartesian product of a lot of values lists can generate thousands of tests: with
+/// `#[rstest(combinations = pairwise)]` just a subset of the combinations is generated
+/// where every couple of values of two different lists is tested at least once. Use
+/// `combinations = nwise(n)` to cover all the `n` values' tuples instead of just the
+/// couples.
+///
+/// ```
+/// # use rstest::*;
+/// # fn check(a: u8, b: u8, c: u8, d: u8, e: u8) -> bool { true }
+/// #[rstest(combinations = pairwise)]
+/// fn should_check(
+///     #[values(0, 1, 2, 3, 4, 5)] a: u8,
+///     #[values(0, 1, 2, 3, 4, 5)] b: u8,
+///     #[values(0, 1, 2, 3, 4, 5)] c: u8,
+///     #[values(0, 1, 2, 3, 4, 5)] d: u8,
+///     #[values(0, 1, 2, 3, 4, 5)] e: u8,
+/// ) {
+///     assert!(check(a, b, c, d, e))
+/// }
+/// ```
+///
+/// generates less than 50 tests instead of 7776. The tests keep the same names of the
+/// complete matrix (like `should_check::a_2::b_4::c_1::d_6::e_3`) and the algorithm is
+/// deterministic, so the same values lists always select the same combinations. The
+/// combinations removed by `#[exclude]` and `#[only]` are never selected.
+///
+/// ### Zipped Values Lists
+///
+/// When some values lists are parallel (like inputs and the expected outputs) mark them by
+/// `#[zip]`: the zipped lists are not combined but the i-th test takes the i-th value of
+/// every list, just like a `#[case]`, while the values stay next to their arguments.
+///
+/// ```
+/// # use rstest::*;
+/// #[rstest]
+/// fn should_double(
+///     #[zip]
+///     #[values(0, 1, 21)]
+///     input: u32,
+///     #[zip]
+///     #[values(0, 2, 42)]
+///     expected: u32,
+///     #[values(1, 10)] scale: u32,
+/// ) {
+///     assert_eq!(expected * scale, 2 * input * scale)
+/// }
+/// ```
+///
+/// The zipped lists should have the same length and are combined with the other values
+/// lists as a single one: the tests above are named like `should_double::zip_2::scale_1`.
+/// The zipped tests' names are followed by the values' names (if any) and labels or, with
+/// `names = values`, by the values. Use `#[rstest(combinations = zip)]` to zip all the
+/// values lists. `#[exclude]` and `#[only]` filters and pairwise combinations work also
+/// with the zipped lists.
+///
+/// ### Files Path as Input Arguments
+///
+/// If you need to run a test for each file in a folder you can use `#[files("glob")]`
+/// argument's attribute: the glob is resolved at compile time relative to the crate's root
+/// (`CARGO_MANIFEST_DIR`) and the argument takes the absolute `PathBuf` of every matched file.
+/// The test's name contains the sanitized file name, you can use `#[files]` more than once
+/// and exclude some files by `#[exclude("regex")]` where the regex is matched against the
+/// path relative to the crate's root.
+///
+/// ```ignore
+/// # use rstest::*;
+/// # use std::path::PathBuf;
+/// # fn parse(path: PathBuf) -> Result<(), ()> { Ok(()) }
+/// #[rstest]
+/// fn parse_golden_files(
+///     #[files("tests/data/*.json")]
+///     #[exclude("broken_")]
+///     path: PathBuf
+/// ) {
+///     assert!(parse(path).is_ok())
+/// }
+/// ```
+///
+/// With `#[mode = str]` or `#[mode = bytes]` the argument takes the file's content by
+/// `include_str!()` or `include_bytes!()` (as `&[u8]`) instead of its path.
+///
+/// In `str` and `bytes` modes changing a matched file rebuilds the test, while in the
+/// default mode the test reads the file at run time and the files are not loaded by the
+/// compiler. A procedural macro cannot ask cargo to watch a folder, so a new file is
+/// picked up just when the test's crate is compiled again: if you need it, add a build
+/// script that tracks the files' folder.
+///
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     println!("cargo:rerun-if-changed=tests/data");
+/// }
+/// ```
+///
+/// `#[files]` arguments can be combined with `#[values]` ones and cases like every
+/// values list.
+///
+/// ## Type-Parametrized Tests
+///
+/// A generic test can be instantiated for some types by `#[types(T = [t1, ..., tn])]`:
+/// the tests for the type `ti` are placed in a module named by the type parameter and
+/// the type (like `T_u8`) and call the test function by turbofish.
+///
+/// ```
+/// # use rstest::*;
+/// # use std::fmt::Debug;
+/// #[fixture]
+/// fn zero<T: Default>() -> T {
+///     T::default()
+/// }
+///
+/// #[rstest]
+/// #[types(T = [u8, i64, String])]
+/// fn default_is_zero<T: Default + PartialEq + Debug>(zero: T) {
+///     assert_eq!(T::default(), zero);
+/// }
+/// ```
+///
+/// generates `default_is_zero::T_u8`, `default_is_zero::T_i64` and
+/// `default_is_zero::T_String`: the generic fixtures are resolved for the same type. You
+/// can give more lists in the same attribute or in more `#[types]` attributes (the test is
+/// instantiated for every combination of the types) and combine them with cases and
+/// values lists: the cases' and values' tests are placed in the type's module and the
+/// [magic conversion](attr.rstest.html#magic-conversion) works on the concrete types.
+///
+/// ```
+/// # use rstest::rstest;
+/// # use std::str::FromStr;
+/// #[rstest]
+/// #[types(T = [u8, u16, i64])]
+/// #[case("42")]
+/// #[case::big("255")]
+/// fn parse<T: FromStr>(#[case] input: &str) {
+///     assert!(input.parse::<T>().is_ok());
+/// }
+/// ```
+///
+/// ## Tests' Names
+///
+/// By default the generated tests are named just by their index, like `case_2` or
+/// `state_2`. With `#[rstest(names = values)]` a rendering of the simple values
+/// (literals, negative numbers and paths like `State::Processing`) is appended to the
+/// names: other expressions are just skipped.
+///
+/// ```
+/// # use rstest::rstest;
+/// # #[derive(Debug)] enum State { Init, Processing }
+/// #[rstest(names = values)]
+/// #[case(-42, State::Init)]
+/// #[case(7, State::Processing)]
+/// fn process(#[case] input: i32, #[case] state: State, #[values(1, 2)] step: u32) {
+///     // ...
+/// }
+/// ```
+///
+/// generates `process::case_1_neg_42_Init::step_1_1`, `process::case_2_7_Processing::step_2_2`
+/// and so on. You can also give a template for the cases' names where every `{arg}`
+/// placeholder is replaced by the `#[case]` argument's value (use `{{` and `}}` for the
+/// braces):
+///
+/// ```
+/// # use rstest::rstest;
+/// # fn fibonacci(input: u32) -> u32 { match input { 0 => 0, 1 => 1, n => fibonacci(n - 2) + fibonacci(n - 1) } }
+/// #[rstest(name = "fib_{input}_yields_{expected}")]
+/// #[case(0, 0)]
+/// #[case(1, 1)]
+/// #[case(10, 55)]
+/// fn fibonacci_test(#[case] input: u32, #[case] expected: u32) {
+///     assert_eq!(expected, fibonacci(input))
+/// }
+/// ```
+///
+/// generates `fibonacci_test::fib_10_yields_55` and so on. The names are always valid
+/// identifiers: when the rendered name is not valid or more cases have the same name,
+/// the case's index is prepended (like `case_2_fib_1_yields_1`) and when a placeholder's
+/// value is not a simple one the name is just `case_<n>`. A case's description always
+/// wins over values and templates.
+///
 /// ## Use Parametrize definition in more tests
 ///
 /// If you need to use a test list for more than one test you can use
@@ -831,18 +1640,55 @@ pub fn fixture(
 /// }
 /// ```
 ///
-/// Currently only `async-std` is supported out of the box. But if you need to use
-/// another runtime that provide it's own test attribute (i.e. `tokio::test` or
-/// `actix_rt::test`) you can use it in your `async` test like described in
+/// By default `rstest` uses `async-std`, but you can select the default runtime by
+/// `rstest`'s features: `async-std` (the default one), `tokio` or `smol`. Features are
+/// additive, so if more than one is enabled (maybe by another crate) there is no default
+/// runtime and every `async` test should select its own one. For instance, in a Tokio only
+/// codebase:
+///
+/// ```toml
+/// [dev-dependencies]
+/// rstest = { version = "0.9", default-features = false, features = ["tokio"] }
+/// ```
+///
+/// You can also choose the runtime for a single test by `#[rstest(runtime = <name>)]`
+/// where `<name>` is one of `async_std`, `tokio`, `tokio_multi_thread` or `smol`. If you
+/// need another runtime that provide it's own test attribute (i.e. `actix_rt::test`) you
+/// can use it in your `async` test like described in
 /// [Inject Test Attribute](attr.rstest.html#inject-test-attribute).
 ///
-/// To use this feature, you need to enable `attributes` in the `async-std`
-/// features list in your `Cargo.toml`:
+/// You should add the selected runtime to your dependencies: for `async-std` you need
+/// to enable `attributes` in its features list in your `Cargo.toml`:
 ///
 /// ```toml
 /// async-std = { version = "1.5", features = ["attributes"] }
 /// ```
 ///
+/// ### Await Future Arguments
+///
+/// Use `#[future]` to take an `async` fixture or case value as `impl Future` and
+/// `#[future(awt)]` to let `rstest` await it for you: the test's body sees just the
+/// value. If you want to await all `#[future]` arguments just mark the test with `#[awt]`.
+///
+/// ```
+/// use rstest::*;
+/// # async fn async_sum(a: u32, b: u32) -> u32 { a + b }
+///
+/// #[fixture]
+/// async fn base() -> u32 { 42 }
+///
+/// #[rstest]
+/// #[case(async { 2 }, 44)]
+/// #[case(async { 0 }, 42)]
+/// #[awt]
+/// async fn my_async_test(#[future] #[case] a: u32, #[case] expected: u32, #[future] base: u32) {
+///     assert_eq!(expected, async_sum(a, base).await);
+/// }
+/// ```
+///
+/// Arguments are awaited one after the other in the order they are declared: use
+/// `#[awt(join)]` to await them concurrently.
+///
 /// ## Inject Test Attribute
 ///
 /// If you would like to use another `test` attribute for your test you can simply
@@ -866,6 +1712,38 @@ pub fn fixture(
 /// in this case the `#[actix_rt::test]` attribute will replace the standard `#[test]`
 /// attribute.
 ///
+/// ## Test Timeout
+///
+/// You can set a timeout for your test by `#[timeout(duration)]` attribute: if the test
+/// runs longer than `duration` it fails and the failure message tells the test's name and
+/// the elapsed time. It works both for sync tests (the test's body is executed in a new
+/// thread, so its result should be `Send`) and `async` tests: they use the timer of their
+/// runtime (with `tokio` enable its `time` feature) or a timer thread if the test is marked
+/// by the attribute of another runtime. You can also use it on a single case:
+/// the case's timeout overrides the function's one.
+///
+/// ```
+/// use rstest::*;
+/// use std::time::Duration;
+///
+/// fn fibonacci(input: u32) -> u32 {
+///     match input {
+///         0 => 0,
+///         1 => 1,
+///         n => fibonacci(n - 2) + fibonacci(n - 1)
+///     }
+/// }
+///
+/// #[rstest]
+/// #[case::fast(10, 55)]
+/// #[timeout(Duration::from_secs(1))]
+/// #[case::slow(30, 832040)]
+/// #[timeout(Duration::from_millis(50))]
+/// fn fibonacci_test(#[case] input: u32, #[case] expected: u32) {
+///     assert_eq!(expected, fibonacci(input))
+/// }
+/// ```
+///
 /// ## Putting all Together
 ///
 /// All these features can be used together with a mixture of fixture variables,
@@ -990,11 +1868,24 @@ pub fn fixture(
 ///
 /// - `arg_i` could be one of the follow
 ///   - `ident` that match to one of function arguments for parametrized cases
-///   - `case[::description](v1, ..., vl)` a test case
-///   - `fixture(v1, ..., vl)` where fixture is one of function arguments
-/// that and `v1, ..., vl` is a partial list of fixture's arguments
+///   - `case[::description](v1, ..., vl[, name = v, ...]) [=> expected]` a test case
+/// (see [Named Case Values](attr.rstest.html#named-case-values) and
+/// [Expected Results](attr.rstest.html#expected-results))
+///   - `fixture(v1, ..., vl[, name = v, ...])` where fixture is one of function arguments
+/// that and `v1, ..., vl` is a partial list of fixture's arguments (`name = v` gives the
+/// fixture's argument `name`)
+///   - `fixture_path[(v1, ..., vl)] as ident` where `ident` is one of function arguments
+/// resolved by the fixture `fixture_path`
 ///   - `ident => [v1, ..., vl]` where `ident` is one of function arguments and
-/// `v1, ..., vl` is a list of values for ident
+/// `v1, ..., vl` is a list of values for ident (every value can be named by `name => v`
+/// and can have some attributes like `#[should_panic] v`)
+///   - `runtime = name` the runtime used to run an `async` test (see
+/// [Async](attr.rstest.html#async))
+///   - `combinations = all|pairwise|nwise(n)|zip` how the values lists are combined (see
+/// [Pairwise Combinations](attr.rstest.html#pairwise-combinations) and
+/// [Zipped Values Lists](attr.rstest.html#zipped-values-lists))
+///   - `names = values` or `name = "template"` how to name the generated tests (see
+/// [Tests' Names](attr.rstest.html#tests-names))
 /// - `attribute_j` a test attribute like `trace` or `notrace`
 ///
 /// ## Fixture Arguments
@@ -1016,6 +1907,25 @@ pub fn fixture(
 /// }
 /// ```
 ///
+/// And to resolve an argument by a different fixture:
+///
+/// ```
+/// # struct User(String, u8);
+/// # impl User { fn name(&self) -> &str {&self.0} }
+/// # use rstest::*;
+/// #
+/// # #[fixture]
+/// # fn user(
+/// #     #[default("Alice")] name: impl AsRef<str>,
+/// #     #[default(22)] age: u8
+/// # ) -> User { User(name.as_ref().to_owned(), age) }
+/// #
+/// #[rstest(user("Bob") as admin)]
+/// fn check_admin(admin: User) {
+///     assert_eq!("Bob", admin.name())
+/// }
+/// ```
+///
 /// ## Parametrized
 ///
 /// ```
@@ -1072,10 +1982,26 @@ pub fn rstest(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let mut test = parse_macro_input!(input as ItemFn);
-    let mut info = parse_macro_input!(args as RsTestInfo);
+    let test = parse_macro_input!(input as ItemFn);
+
+    rstest_impl(args.into(), test, None).into()
+}
+
+/// Render a test given the collected parametrized fixtures' `groups`: if `groups` is `None`
+/// and the test uses some parametrized fixtures render the handshake to collect them.
+fn rstest_impl(
+    args: proc_macro2::TokenStream,
+    mut test: ItemFn,
+    groups: Option<Vec<ParamsGroup>>,
+) -> proc_macro2::TokenStream {
+    let original = test.clone();
+    let mut info = match syn::parse2::<RsTestInfo>(args.clone()) {
+        Ok(info) => info,
+        Err(e) => return e.to_compile_error(),
+    };
 
-    let replace_result = ReplaceFutureAttribute::replace(&mut test);
+    let replace_result = ReplaceFutureAttribute::replace(&mut test)
+        .map(|awaited| info.attributes.set_awaited(awaited));
     let extend_result = info.extend_with_function_attrs(&mut test);
 
     let mut errors = error::rstest(&test, &info);
@@ -1086,17 +2012,146 @@ pub fn rstest(
     if let Err(attrs_errors) = extend_result {
         attrs_errors.to_tokens(&mut errors);
     }
+    if !errors.is_empty() {
+        return errors;
+    }
 
-    if errors.is_empty() {
-        if info.data.has_list_values() {
-            render::matrix(test, info)
-        } else if info.data.has_cases() {
-            render::parametrize(test, info)
-        } else {
-            render::single(test, info)
+    match groups {
+        None if info.attributes.has_params() => {
+            return render::params::render_test_handshake(args, &original, &info)
+        }
+        Some(groups) => {
+            if let Err(e) = render::params::expand_params(&mut info, groups) {
+                return e.to_compile_error();
+            }
         }
+        None => {}
+    }
+
+    let errors = error::combinations(&info);
+    if !errors.is_empty() {
+        return errors;
+    }
+
+    if info.data.has_type_lists() {
+        render::types::render(test, info)
+    } else if info.data.has_list_values() {
+        render::matrix(test, info)
+    } else if info.data.has_cases() {
+        render::parametrize(test, info)
     } else {
-        errors
+        render::single(test, info)
+    }
+}
+
+/// Parametrized fixtures' handshake entry point: it's not a public API and you should never
+/// use it directly.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __rstest_params(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ParamsCallback { groups, target } = parse_macro_input!(input as ParamsCallback);
+
+    match target {
+        ParamsTarget::Test { args, item } => match syn::parse2(item) {
+            Ok(test) => rstest_impl(args, test, Some(groups)),
+            Err(e) => e.to_compile_error(),
+        },
+        ParamsTarget::Fixture { vis, name, dims } => {
+            render::params::render_collected_fixture_macro(&vis, &name, dims, groups)
+        }
     }
     .into()
 }
+
+/// Define a suite of contract tests that can be applied to more implementations of the
+/// same trait. The suite is a module: its `#[rstest]` tests are generic in their first type
+/// parameter and `#[rstest_suite]` replaces it by a macro with the same name that
+/// instantiates the suite for an implementation type.
+///
+/// ```
+/// use rstest::*;
+/// # use std::collections::HashMap;
+///
+/// pub trait Storage {
+///     fn put(&mut self, key: &str, value: u32);
+///     fn get(&self, key: &str) -> Option<u32>;
+/// }
+///
+/// #[derive(Default)]
+/// pub struct InMemory(HashMap<String, u32>);
+/// # impl Storage for InMemory {
+/// #     fn put(&mut self, key: &str, value: u32) { self.0.insert(key.to_owned(), value); }
+/// #     fn get(&self, key: &str) -> Option<u32> { self.0.get(key).cloned() }
+/// # }
+///
+/// #[fixture]
+/// fn in_memory_store() -> InMemory {
+///     InMemory::default()
+/// }
+///
+/// #[rstest_suite]
+/// mod storage_contract {
+///     use super::*;
+///
+///     #[rstest]
+///     fn get_what_put<S: Storage>(mut store: S) {
+///         store.put("answer", 42);
+///
+///         assert_eq!(Some(42), store.get("answer"));
+///     }
+///
+///     #[rstest]
+///     #[case("answer")]
+///     #[case("")]
+///     fn get_nothing_from_empty_store<S: Storage>(store: S, #[case] key: &str) {
+///         assert_eq!(None, store.get(key));
+///     }
+/// }
+///
+/// storage_contract!(InMemory, fixture = in_memory_store);
+/// # fn main() {}
+/// ```
+///
+/// `storage_contract!(InMemory, fixture = in_memory_store)` generates the `in_memory` module
+/// (the type's name in `snake_case`) that contains the suite's items and the tests
+/// `in_memory::get_what_put`, `in_memory::get_nothing_from_empty_store::case_1` and so on.
+/// The tests call the generic ones by turbofish with the implementation type: cases, values
+/// lists and fixtures work like in every other test and the generic fixtures are resolved
+/// for the implementation type.
+///
+/// You can give the module's name by `storage_contract!(InMemory as memory)`: it's useful
+/// to instantiate the suite twice for the same type or when the default name clashes with
+/// other items (note that a fixture defines also a type with its name).
+///
+/// The `fixture` argument is optional: if given, the test's arguments of the type parameter
+/// type without any attribute are resolved by this fixture (like `#[from(fixture)]`),
+/// otherwise they are resolved by name as usual.
+///
+/// The suite's macro follows the `macro_rules!` scoping rules: you should invoke it after
+/// the suite's definition (or by path if the suite is `pub`, like
+/// `crate::contracts::storage_contract!(...)`). The instance module imports the invocation
+/// module's items by `use super::*`.
+#[proc_macro_attribute]
+pub fn rstest_suite(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let suite = parse_macro_input!(input as ItemMod);
+
+    let errors = error::suite(&args.into(), &suite);
+    if !errors.is_empty() {
+        return errors.into();
+    }
+
+    render::suite::render_suite_macro(&suite).into()
+}
+
+/// Suites' instances entry point: it's not a public API and you should never use it
+/// directly.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __rstest_suite(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let instance = parse_macro_input!(input as SuiteInstance);
+
+    render::suite::render_instance(instance).into()
+}
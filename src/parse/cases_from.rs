@@ -0,0 +1,536 @@
+/// `#[cases_from("path")]` function's attribute: load test cases from a CSV, TOML or JSON
+/// data file (relative to the crate's root) where every row is a case and the columns are
+/// mapped to the `#[case]` arguments by name.
+use std::path::Path;
+
+use syn::{visit_mut::VisitMut, Expr, Ident, ItemFn, LitStr};
+
+use crate::{
+    error::ErrorsVec,
+    utils::{attr_is, respan, sanitize},
+};
+
+use super::testcase::TestCase;
+
+/// The column used as case's description if it's not a case argument.
+const NAME_COLUMN: &str = "name";
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct CasesFrom {
+    pub(crate) path: LitStr,
+}
+
+/// A cell's value: strings are injected as string literals (so they can use the magic
+/// conversion) or parsed as expression if enclosed in backticks.
+#[derive(Debug, PartialEq)]
+enum Cell {
+    Str(String),
+    Code(String),
+}
+
+impl Cell {
+    fn from_str(value: &str) -> Self {
+        let trimmed = value.trim();
+        if trimmed.len() >= 2 && trimmed.starts_with('`') && trimmed.ends_with('`') {
+            Cell::Code(trimmed[1..trimmed.len() - 1].to_owned())
+        } else {
+            Cell::Str(value.to_owned())
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            Cell::Str(s) | Cell::Code(s) => s,
+        }
+    }
+}
+
+/// A data row: `position` is the line in the file for CSV and the row's index for the
+/// others formats.
+#[derive(Debug)]
+struct Row {
+    position: String,
+    id: usize,
+    cells: Vec<(String, Cell)>,
+}
+
+impl CasesFrom {
+    /// Read the data file relative to `base_dir` and build a test case for each row.
+    pub(crate) fn load(&self, case_args: &[Ident], base_dir: &Path) -> syn::Result<Vec<TestCase>> {
+        if case_args.is_empty() {
+            return Err(self.error("#[cases_from] needs some #[case] arguments"));
+        }
+        let path = base_dir.join(self.path.value());
+        let parse_rows = match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => Self::csv_rows,
+            Some("json") => Self::json_rows,
+            Some("toml") => Self::toml_rows,
+            _ => {
+                return Err(self
+                    .error("Unsupported data file format: should be a .csv, .toml or .json file"))
+            }
+        };
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| self.error(format!("Cannot read '{}': {}", path.display(), e)))?;
+        let rows = parse_rows(self, &content)?;
+        if rows.is_empty() {
+            return Err(self.error(format!(
+                "{}: no cases found: the data file should contain at least one case",
+                self.path.value()
+            )));
+        }
+        rows.iter()
+            .map(|row| self.test_case(row, case_args))
+            .collect()
+    }
+
+    fn error(&self, msg: impl std::fmt::Display) -> syn::Error {
+        syn::Error::new(self.path.span(), msg)
+    }
+
+    fn row_error(&self, row: &Row, msg: impl std::fmt::Display) -> syn::Error {
+        self.error(format!("{}:{}: {}", self.path.value(), row.position, msg))
+    }
+
+    fn csv_rows(&self, content: &str) -> syn::Result<Vec<Row>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(content.as_bytes());
+        let headers = reader
+            .headers()
+            .map_err(|e| self.csv_error(content, e))?
+            .clone();
+        reader
+            .records()
+            .map(|record| {
+                let record = record.map_err(|e| self.csv_error(content, e))?;
+                let line = csv_line(content, record.position());
+                Ok(Row {
+                    position: line.to_string(),
+                    id: line,
+                    cells: headers
+                        .iter()
+                        .zip(record.iter())
+                        .map(|(h, c)| (h.to_owned(), Cell::from_str(c)))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    fn csv_error(&self, content: &str, error: csv::Error) -> syn::Error {
+        let msg = match error.kind() {
+            csv::ErrorKind::UnequalLengths {
+                expected_len, len, ..
+            } => format!(
+                "found a record with {} fields but the header has {} fields",
+                len, expected_len
+            ),
+            _ => error.to_string(),
+        };
+        match error.position() {
+            Some(position) => self.error(format!(
+                "{}:{}: {}",
+                self.path.value(),
+                csv_line(content, Some(position)),
+                msg
+            )),
+            None => self.error(format!("{}: {}", self.path.value(), msg)),
+        }
+    }
+
+    /// A JSON file should contain an array of objects.
+    fn json_rows(&self, content: &str) -> syn::Result<Vec<Row>> {
+        use serde_json::Value;
+        let value: Value = serde_json::from_str(content)
+            .map_err(|e| self.error(format!("{}:{}", self.path.value(), e)))?;
+        let rows = match value {
+            Value::Array(rows) => rows,
+            _ => return Err(self.error("A JSON data file should contain an array of objects")),
+        };
+        rows.into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut data = Row {
+                    position: format!("row {}", i + 1),
+                    id: i + 1,
+                    cells: vec![],
+                };
+                let object = match row {
+                    Value::Object(object) => object,
+                    _ => return Err(self.row_error(&data, "should be an object")),
+                };
+                for (key, value) in object {
+                    let cell = match value {
+                        Value::String(s) => Cell::from_str(&s),
+                        Value::Number(n) => Cell::Code(n.to_string()),
+                        Value::Bool(b) => Cell::Code(b.to_string()),
+                        _ => {
+                            return Err(self.row_error(
+                                &data,
+                                format!(
+                                    "unsupported value for '{}': use a string, a number or a bool",
+                                    key
+                                ),
+                            ))
+                        }
+                    };
+                    data.cells.push((key, cell));
+                }
+                Ok(data)
+            })
+            .collect()
+    }
+
+    /// A TOML file should contain an array of `[[case]]` tables.
+    fn toml_rows(&self, content: &str) -> syn::Result<Vec<Row>> {
+        use toml::Value;
+        let value: Value = toml::from_str(content)
+            .map_err(|e| self.error(format!("{}: {}", self.path.value(), e)))?;
+        let rows = match value.get("case") {
+            Some(Value::Array(rows)) => rows.clone(),
+            _ => return Err(self.error("A TOML data file should contain [[case]] tables")),
+        };
+        rows.into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut data = Row {
+                    position: format!("case {}", i + 1),
+                    id: i + 1,
+                    cells: vec![],
+                };
+                let table = match row {
+                    Value::Table(table) => table,
+                    _ => return Err(self.row_error(&data, "should be a table")),
+                };
+                for (key, value) in table {
+                    let cell = match value {
+                        Value::String(s) => Cell::from_str(&s),
+                        Value::Integer(n) => Cell::Code(n.to_string()),
+                        Value::Float(f) => Cell::Code(format!("{:?}", f)),
+                        Value::Boolean(b) => Cell::Code(b.to_string()),
+                        _ => {
+                            return Err(self.row_error(
+                                &data,
+                                format!(
+                                    "unsupported value for '{}': use a string, a number or a bool",
+                                    key
+                                ),
+                            ))
+                        }
+                    };
+                    data.cells.push((key, cell));
+                }
+                Ok(data)
+            })
+            .collect()
+    }
+
+    fn expression(&self, row: &Row, column: &str, cell: &Cell) -> syn::Result<Expr> {
+        match cell {
+            Cell::Str(s) => Ok(Expr::Lit(syn::ExprLit {
+                attrs: vec![],
+                lit: LitStr::new(s, self.path.span()).into(),
+            })),
+            Cell::Code(code) => {
+                let tokens: proc_macro2::TokenStream = code.parse().map_err(|_| {
+                    self.row_error(
+                        row,
+                        format!("invalid expression for '{}': {}", column, code),
+                    )
+                })?;
+                syn::parse2(respan(tokens, self.path.span())).map_err(|e| {
+                    self.row_error(
+                        row,
+                        format!("invalid expression for '{}': {}: {}", column, code, e),
+                    )
+                })
+            }
+        }
+    }
+
+    fn test_case(&self, row: &Row, case_args: &[Ident]) -> syn::Result<TestCase> {
+        let name_is_arg = case_args.iter().any(|a| a == NAME_COLUMN);
+        if let Some((column, _)) = row
+            .cells
+            .iter()
+            .find(|(c, _)| !case_args.iter().any(|a| a == c) && (c != NAME_COLUMN || name_is_arg))
+        {
+            return Err(self.row_error(
+                row,
+                format!("unknown column '{}': it's not a #[case] argument", column),
+            ));
+        }
+        let args = case_args
+            .iter()
+            .map(|arg| {
+                let (column, cell) = row
+                    .cells
+                    .iter()
+                    .find(|(c, _)| arg == c)
+                    .ok_or_else(|| self.row_error(row, format!("missed column '{}'", arg)))?;
+                self.expression(row, column, cell)
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        let name = row
+            .cells
+            .iter()
+            .find(|(c, _)| !name_is_arg && c == NAME_COLUMN)
+            .map(|(_, cell)| sanitize(cell.text()))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("row_{}", row.id));
+        Ok(TestCase {
+            args,
+            named: vec![],
+            attrs: vec![],
+            description: Some(Ident::new(&name, self.path.span())),
+            expected: None,
+        })
+    }
+}
+
+/// The record's line in the file: the reader's position includes the skipped empty lines.
+fn csv_line(content: &str, position: Option<&csv::Position>) -> usize {
+    let offset = position.map(|p| p.byte()).unwrap_or_default() as usize;
+    let skipped = content[offset..]
+        .chars()
+        .take_while(|&c| c == '\r' || c == '\n')
+        .count();
+    content[..offset + skipped].matches('\n').count() + 1
+}
+
+/// Simple struct used to visit function attributes and extract the `#[cases_from]` ones
+/// and eventualy parsing errors
+#[derive(Default)]
+struct CasesFromFunctionExtractor(Vec<CasesFrom>, Vec<syn::Error>);
+
+impl VisitMut for CasesFromFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (cases_from, remain): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| attr_is(attr, "cases_from"));
+        node.attrs = remain;
+        for attr in cases_from {
+            match attr.parse_args::<LitStr>() {
+                Ok(path) => self.0.push(CasesFrom { path }),
+                Err(_) => self.1.push(syn::Error::new_spanned(
+                    attr,
+                    r#"Invalid cases_from syntax: should be #[cases_from("<path>")]"#,
+                )),
+            }
+        }
+    }
+}
+
+pub(crate) fn extract_cases_from(item_fn: &mut ItemFn) -> Result<Vec<CasesFrom>, ErrorsVec> {
+    let mut cases_from_extractor = CasesFromFunctionExtractor::default();
+    cases_from_extractor.visit_item_fn_mut(item_fn);
+
+    if cases_from_extractor.1.len() > 0 {
+        Err(cases_from_extractor.1.into())
+    } else {
+        Ok(cases_from_extractor.0)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use mytest::rstest;
+    use rstest_test::assert_in;
+    use temp_testdir::TempDir;
+
+    fn load(file: &str, content: &str, args: &[&str]) -> syn::Result<Vec<TestCase>> {
+        let root = TempDir::default();
+        std::fs::write(root.join(file), content).unwrap();
+        let cases_from = CasesFrom {
+            path: LitStr::new(file, proc_macro2::Span::call_site()),
+        };
+        let args = args.iter().map(ident).collect::<Vec<_>>();
+
+        cases_from.load(&args, &root)
+    }
+
+    fn descriptions(cases: &[TestCase]) -> Vec<String> {
+        cases
+            .iter()
+            .map(|c| c.description.as_ref().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn extract_cases_from_attributes() {
+        let mut item_fn: ItemFn = r#"
+            #[other]
+            #[cases_from("a.csv")]
+            #[cases_from("b.json")]
+            fn test(#[case] a: u32) {}
+        "#
+        .ast();
+
+        let cases_from = extract_cases_from(&mut item_fn).unwrap();
+
+        assert_eq!(
+            vec!["a.csv", "b.json"],
+            cases_from
+                .iter()
+                .map(|c| c.path.value())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(attrs("#[other]"), item_fn.attrs);
+    }
+
+    #[test]
+    fn map_csv_columns_by_name_and_use_line_as_description() {
+        let cases = load(
+            "fib.csv",
+            "expected, input\n0, 0\n\n1, `1 + 0`\n",
+            &["input", "expected"],
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                to_args!([r#""0""#, r#""0""#]),
+                to_args!(["1 + 0", r#""1""#])
+            ],
+            cases.iter().map(|c| c.args.clone()).collect::<Vec<_>>()
+        );
+        assert_eq!(vec!["row_2", "row_4"], descriptions(&cases));
+    }
+
+    #[test]
+    fn use_name_column_as_description() {
+        let cases = load(
+            "data.csv",
+            "name,input\nfirst one,1\n42 is the answer,42\n",
+            &["input"],
+        )
+        .unwrap();
+
+        assert_eq!(vec!["first_one", "_42_is_the_answer"], descriptions(&cases));
+    }
+
+    #[test]
+    fn accept_names_that_sanitize_to_the_same_description() {
+        let cases = load("data.csv", "name,a\nfoo bar,1\nfoo-bar,2\n", &["a"]).unwrap();
+
+        assert_eq!(vec!["foo_bar", "foo_bar"], descriptions(&cases));
+    }
+
+    #[test]
+    fn use_name_column_as_argument_if_it_is_a_case_argument() {
+        let cases = load("data.csv", "name,input\nfoo,1\n", &["name", "input"]).unwrap();
+
+        assert_eq!(to_args!([r#""foo""#, r#""1""#]), cases[0].args);
+        assert_eq!(vec!["row_2"], descriptions(&cases));
+    }
+
+    #[test]
+    fn load_json_rows() {
+        let cases = load(
+            "data.json",
+            r#"[
+                {"input": 1, "expected": "one", "name": "first"},
+                {"input": -2.5, "expected": "`vec![2]`", "ok": true}
+            ]"#,
+            &["input", "expected", "ok"],
+        );
+
+        let error = cases.unwrap_err().to_string();
+        assert_in!(error, "data.json:row 1: missed column 'ok'");
+
+        let cases = load(
+            "data.json",
+            r#"[{"input": 1, "expected": "one", "name": "first"}, {"input": -2.5, "expected": "`vec![2]`"}]"#,
+            &["input", "expected"],
+        )
+        .unwrap();
+
+        assert_eq!(to_args!(["1", r#""one""#]), cases[0].args);
+        assert_eq!(to_args!(["-2.5", "vec![2]"]), cases[1].args);
+        assert_eq!(vec!["first", "row_2"], descriptions(&cases));
+    }
+
+    #[test]
+    fn load_toml_cases() {
+        let cases = load(
+            "data.toml",
+            r#"
+            [[case]]
+            input = 3
+            expected = "2"
+            valid = true
+
+            [[case]]
+            name = "big"
+            input = 1.5
+            expected = "`u64::MAX`"
+            valid = false
+            "#,
+            &["input", "expected", "valid"],
+        )
+        .unwrap();
+
+        assert_eq!(to_args!(["3", r#""2""#, "true"]), cases[0].args);
+        assert_eq!(to_args!(["1.5", "u64::MAX", "false"]), cases[1].args);
+        assert_eq!(vec!["row_1", "big"], descriptions(&cases));
+    }
+
+    mod raise_error {
+        use super::{assert_eq, *};
+
+        #[rstest]
+        #[case::no_case_args("data.csv", "a\n1\n", &[], "#[cases_from] needs some #[case] arguments")]
+        #[case::unsupported_format("data.txt", "a\n1\n", &["a"], "Unsupported data file format")]
+        #[case::wrong_row_len("data.csv", "a,b\n1,2\n3\n", &["a", "b"], "data.csv:3: found a record with 1 fields but the header has 2 fields")]
+        #[case::missed_column("data.csv", "a\n1\n2\n", &["a", "b"], "data.csv:2: missed column 'b'")]
+        #[case::unknown_column("data.csv", "a,c\n1,2\n", &["a"], "data.csv:2: unknown column 'c': it's not a #[case] argument")]
+        #[case::invalid_expression("data.csv", "a\n1\n`a +`\n", &["a"], "data.csv:3: invalid expression for 'a': a +")]
+        #[case::invalid_tokens("data.csv", "a\n`(`\n", &["a"], "data.csv:2: invalid expression for 'a': (")]
+        #[case::invalid_json("data.json", "[{\n\"a\": }]", &["a"], "data.json:expected value at line 2")]
+        #[case::not_json_array("data.json", r#"{"a": 1}"#, &["a"], "should contain an array of objects")]
+        #[case::not_json_object("data.json", r#"[1]"#, &["a"], "data.json:row 1: should be an object")]
+        #[case::unsupported_json_value("data.json", r#"[{"a": [1]}]"#, &["a"], "data.json:row 1: unsupported value for 'a'")]
+        #[case::invalid_toml("data.toml", "[[case]]\na = ", &["a"], "data.toml: ")]
+        #[case::no_toml_cases("data.toml", "a = 1", &["a"], "should contain [[case]] tables")]
+        #[case::unsupported_toml_value("data.toml", "[[case]]\na = [1]", &["a"], "data.toml:case 1: unsupported value for 'a'")]
+        #[case::empty_csv("data.csv", "a\n", &["a"], "data.csv: no cases found")]
+        #[case::empty_json("data.json", "[]", &["a"], "data.json: no cases found")]
+        fn loading(
+            #[case] file: &str,
+            #[case] content: &str,
+            #[case] args: &[&str],
+            #[case] expected: &str,
+        ) {
+            let error = load(file, content, args).unwrap_err();
+
+            assert_in!(error.to_string(), expected);
+        }
+
+        #[test]
+        fn if_file_does_not_exist() {
+            let cases_from = CasesFrom {
+                path: LitStr::new("none.csv", proc_macro2::Span::call_site()),
+            };
+
+            let error = cases_from
+                .load(&[ident("a")], &TempDir::default())
+                .unwrap_err();
+
+            assert_in!(error.to_string(), "Cannot read");
+        }
+
+        #[test]
+        fn if_cases_from_has_no_path() {
+            let mut item_fn: ItemFn = r#"#[cases_from(a)] fn test() {}"#.ast();
+
+            let errors = extract_cases_from(&mut item_fn).unwrap_err();
+
+            assert_eq!(1, errors.len());
+            assert_in!(errors[0].to_string(), "Invalid cases_from syntax");
+        }
+    }
+}
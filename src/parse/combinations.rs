@@ -0,0 +1,743 @@
+/// How the values lists' combinations are generated: `combinations = <mode>` in `rstest`
+/// attribute, the `#[zip]` arguments that take their values together and the
+/// `#[exclude(arg = value, ...)]` and `#[only(arg = value, ...)]` test's attributes that
+/// prune them.
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    punctuated::Punctuated,
+    visit_mut::VisitMut,
+    Expr, Ident, ItemFn, Token,
+};
+
+use super::{
+    rstest::{RsTestAttributes, RsTestData},
+    vlist::ValueList,
+};
+use crate::{error::ErrorsVec, utils::attr_is};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum CombinationsMode {
+    /// The cartesian product of all values lists
+    All,
+    /// A covering array where every `n` values of `n` different lists are used together
+    NWise(usize),
+    /// All values lists are zipped: the i-th test takes the i-th value of every list
+    Zip,
+}
+
+impl Default for CombinationsMode {
+    fn default() -> Self {
+        CombinationsMode::All
+    }
+}
+
+impl CombinationsMode {
+    pub(crate) const KEYWORD: &'static str = "combinations";
+
+    pub(crate) fn is_combinations_definition(input: ParseStream) -> bool {
+        input.peek2(Token![=])
+            && input
+                .fork()
+                .parse::<Ident>()
+                .map(|ident| ident == Self::KEYWORD)
+                .unwrap_or_default()
+    }
+
+    /// `all`, `pairwise`, `nwise(n)` with `n` at least 2 or `zip`.
+    pub(crate) fn from_expr(mode: &Expr) -> Result<Self> {
+        let unknown = || {
+            syn::Error::new_spanned(
+                mode,
+                format!(
+                    "Unknown combinations mode '{}': should be all, pairwise, nwise(<n>) or zip",
+                    mode.to_token_stream()
+                ),
+            )
+        };
+        match mode {
+            Expr::Path(path) if path.path.is_ident("all") => Ok(CombinationsMode::All),
+            Expr::Path(path) if path.path.is_ident("pairwise") => Ok(CombinationsMode::NWise(2)),
+            Expr::Path(path) if path.path.is_ident("zip") => Ok(CombinationsMode::Zip),
+            Expr::Call(call) => match (call.func.as_ref(), call.args.first()) {
+                (
+                    Expr::Path(path),
+                    Some(Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Int(n),
+                        ..
+                    })),
+                ) if path.path.is_ident("nwise") && call.args.len() == 1 => {
+                    match n.base10_parse::<usize>()? {
+                        n if n >= 2 => Ok(CombinationsMode::NWise(n)),
+                        _ => Err(syn::Error::new(
+                            n.span(),
+                            "nwise(<n>) needs at least 2 values lists: use all",
+                        )),
+                    }
+                }
+                _ => Err(unknown()),
+            },
+            _ => Err(unknown()),
+        }
+    }
+}
+
+/// `combinations = <mode>`: the mode's expression is stored and validated by parser.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct CombinationsDefinition(pub(crate) Expr);
+
+impl Parse for CombinationsDefinition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let keyword: Ident = input.parse()?;
+        if keyword != CombinationsMode::KEYWORD {
+            return Err(syn::Error::new(keyword.span(), "Expected combinations"));
+        }
+        let _: Token![=] = input.parse()?;
+        let mode: Expr = input.parse()?;
+        CombinationsMode::from_expr(&mode)?;
+        Ok(Self(mode))
+    }
+}
+
+impl ToTokens for CombinationsDefinition {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let keyword = format_ident!("{}", CombinationsMode::KEYWORD);
+        let mode = &self.0;
+        tokens.extend(quote! { #keyword = #mode })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum FilterKind {
+    Exclude,
+    Only,
+}
+
+impl FilterKind {
+    const EXCLUDE: &'static str = "exclude";
+    const ONLY: &'static str = "only";
+
+    fn from_attr(attr: &syn::Attribute) -> Option<Self> {
+        if attr_is(attr, Self::EXCLUDE) {
+            Some(FilterKind::Exclude)
+        } else if attr_is(attr, Self::ONLY) {
+            Some(FilterKind::Only)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            FilterKind::Exclude => Self::EXCLUDE,
+            FilterKind::Only => Self::ONLY,
+        }
+    }
+}
+
+/// `arg = value`: `value` matches the argument's value if they are the same expression or
+/// if it's the value's name or label.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Condition {
+    pub(crate) arg: Ident,
+    pub(crate) value: Expr,
+}
+
+impl Condition {
+    pub(crate) fn matches(&self, vlist: &ValueList, index: usize) -> bool {
+        let value = self.value.to_token_stream().to_string();
+        value == vlist.values[index].to_token_stream().to_string()
+            || (vlist.is_named(index) && value == vlist.name(index))
+            || vlist.label(index) == Some(value.as_str())
+    }
+}
+
+impl Parse for Condition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let arg = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        let value = input.parse()?;
+        Ok(Self { arg, value })
+    }
+}
+
+impl ToTokens for Condition {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { arg, value } = self;
+        tokens.extend(quote! { #arg = #value })
+    }
+}
+
+/// A combination matches the filter if it matches all the filter's conditions.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct CombinationsFilter {
+    pub(crate) kind: FilterKind,
+    pub(crate) attr: Ident,
+    pub(crate) conditions: Vec<Condition>,
+}
+
+impl CombinationsFilter {
+    fn from_attr(kind: FilterKind, attr: &syn::Attribute) -> Result<Self> {
+        let conditions = attr
+            .parse_args_with(Punctuated::<Condition, Token![,]>::parse_terminated)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        let attr = attr.path.get_ident().cloned().unwrap();
+        if conditions.is_empty() {
+            return Err(syn::Error::new(
+                attr.span(),
+                format!(
+                    "#[{}] needs at least a condition like `arg = value`",
+                    kind.name()
+                ),
+            ));
+        }
+        Ok(Self {
+            kind,
+            attr,
+            conditions,
+        })
+    }
+}
+
+impl ToTokens for CombinationsFilter {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let attr = &self.attr;
+        let conditions = &self.conditions;
+        tokens.extend(quote! { #attr(#(#conditions),*) })
+    }
+}
+
+/// The values lists combined by the test: every values list is a dimension of the matrix
+/// but the zipped ones that are a single dimension where the i-th values are taken together.
+#[derive(Debug, Clone)]
+pub(crate) struct Matrix<'a> {
+    pub(crate) lists: Vec<&'a ValueList>,
+    /// The lists' indexes of every dimension: the zipped lists are in the dimension of the
+    /// first one.
+    pub(crate) dimensions: Vec<Vec<usize>>,
+}
+
+impl<'a> Matrix<'a> {
+    pub(crate) fn new(lists: Vec<&'a ValueList>, zipped: impl Fn(&ValueList) -> bool) -> Self {
+        let mut dimensions: Vec<Vec<usize>> = vec![];
+        let mut zip_dimension: Option<usize> = None;
+        for (index, vlist) in lists.iter().enumerate() {
+            match zip_dimension {
+                Some(dimension) if zipped(vlist) => dimensions[dimension].push(index),
+                None if zipped(vlist) => {
+                    zip_dimension = Some(dimensions.len());
+                    dimensions.push(vec![index]);
+                }
+                _ => dimensions.push(vec![index]),
+            }
+        }
+        Self { lists, dimensions }
+    }
+
+    /// The test's values lists: the ones marked by `#[zip]` (or all if `combinations = zip`)
+    /// are zipped.
+    pub(crate) fn from_test(data: &'a RsTestData, attributes: &RsTestAttributes) -> Self {
+        let zip_all = attributes.combinations() == CombinationsMode::Zip;
+        Self::new(data.list_values().collect(), |vlist| {
+            zip_all || attributes.zipped().any(|arg| arg == &vlist.arg)
+        })
+    }
+
+    pub(crate) fn dimension(&self, dimension: usize) -> impl Iterator<Item = &'a ValueList> + '_ {
+        self.dimensions[dimension]
+            .iter()
+            .map(move |&l| self.lists[l])
+    }
+
+    /// The dimensions' sizes: the zipped lists should have the same length but just the
+    /// values of the shortest one are used.
+    pub(crate) fn sizes(&self) -> Vec<usize> {
+        (0..self.dimensions.len())
+            .map(|d| {
+                self.dimension(d)
+                    .map(|vlist| vlist.values.len())
+                    .min()
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Visit in order the combinations whose every prefix is accepted by `accept`: the
+    /// rejected prefixes are not expanded and the walk stops when `visit` returns `false`.
+    pub(crate) fn walk(
+        &self,
+        accept: &dyn Fn(&[usize]) -> bool,
+        visit: &mut dyn FnMut(&[usize]) -> bool,
+    ) {
+        fn walk_from(
+            sizes: &[usize],
+            prefix: &mut Vec<usize>,
+            accept: &dyn Fn(&[usize]) -> bool,
+            visit: &mut dyn FnMut(&[usize]) -> bool,
+        ) -> bool {
+            if prefix.len() == sizes.len() {
+                return visit(prefix);
+            }
+            for index in 0..sizes[prefix.len()] {
+                prefix.push(index);
+                let go_on = !accept(prefix) || walk_from(sizes, prefix, accept, visit);
+                prefix.pop();
+                if !go_on {
+                    return false;
+                }
+            }
+            true
+        }
+        walk_from(&self.sizes(), &mut vec![], accept, visit);
+    }
+
+    /// If the `filter` matches all the combinations that start with `prefix` (`Some(true)`),
+    /// none of them (`Some(false)`) or it depends on the other dimensions (`None`).
+    fn matches_prefix(&self, filter: &CombinationsFilter, prefix: &[usize]) -> Option<bool> {
+        let mut decided = true;
+        for condition in &filter.conditions {
+            let list = match self
+                .lists
+                .iter()
+                .position(|vlist| vlist.arg == condition.arg)
+            {
+                Some(list) => list,
+                None => return Some(false),
+            };
+            let dimension = self
+                .dimensions
+                .iter()
+                .position(|lists| lists.contains(&list))
+                .unwrap();
+            match prefix.get(dimension) {
+                Some(&index) if !condition.matches(self.lists[list], index) => return Some(false),
+                Some(_) => {}
+                None => decided = false,
+            }
+        }
+        if decided {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+/// If some combinations that start with `prefix` can be accepted by the `filters`: for a
+/// whole combination it's exactly if the combination is accepted.
+pub(crate) fn accept_prefix(
+    matrix: &Matrix,
+    filters: &[&CombinationsFilter],
+    prefix: &[usize],
+) -> bool {
+    let matches = |kind| filters.iter().filter(move |f| f.kind == kind);
+    let has_only = filters.iter().any(|f| f.kind == FilterKind::Only);
+    !matches(FilterKind::Exclude).any(|f| matrix.matches_prefix(f, prefix) == Some(true))
+        && (!has_only
+            || matches(FilterKind::Only).any(|f| matrix.matches_prefix(f, prefix) != Some(false)))
+}
+
+/// All the matrix's combinations (as dimensions' indexes) that are not removed by the
+/// filters or `None` if there are no filters: the removed prefixes are not expanded.
+pub(crate) fn accepted_combinations(
+    matrix: &Matrix,
+    filters: &[&CombinationsFilter],
+) -> Option<Vec<Vec<usize>>> {
+    if filters.is_empty() {
+        return None;
+    }
+    let mut accepted = vec![];
+    matrix.walk(
+        &|prefix| accept_prefix(matrix, filters, prefix),
+        &mut |combination| {
+            accepted.push(combination.to_vec());
+            true
+        },
+    );
+    Some(accepted)
+}
+
+/// If some combination is accepted by `accept`.
+fn any_combination(matrix: &Matrix, accept: &dyn Fn(&[usize]) -> bool) -> bool {
+    let mut found = false;
+    matrix.walk(accept, &mut |_| {
+        found = true;
+        false
+    });
+    found
+}
+
+/// Check that every `#[zip]` argument is a values list and that the zipped lists have the
+/// same length.
+pub(crate) fn check_zipped(matrix: &Matrix, zipped: &[&Ident]) -> Vec<syn::Error> {
+    let mut errors = zipped
+        .iter()
+        .filter(|arg| !matrix.lists.iter().any(|vlist| &&vlist.arg == *arg))
+        .map(|arg| {
+            syn::Error::new(
+                arg.span(),
+                format!(
+                    "'{}' is not a #[values] argument: #[zip] can pair just values lists",
+                    arg
+                ),
+            )
+        })
+        .collect::<Vec<_>>();
+    for dimension in 0..matrix.dimensions.len() {
+        let mut lists = matrix.dimension(dimension);
+        let first = lists.next().unwrap();
+        errors.extend(
+            lists
+                .filter(|vlist| vlist.values.len() != first.values.len())
+                .map(|vlist| {
+                    syn::Error::new(
+                        vlist.arg.span(),
+                        format!(
+                            "Zipped values lists should have the same length: '{}' has {} values but '{}' has {}",
+                            vlist.arg,
+                            vlist.values.len(),
+                            first.arg,
+                            first.values.len()
+                        ),
+                    )
+                }),
+        );
+    }
+    errors
+}
+
+/// Check that every filter's condition refers to a values list argument and matches at
+/// least one of its values and that every filter matches some combinations.
+pub(crate) fn check_filters(matrix: &Matrix, filters: &[&CombinationsFilter]) -> Vec<syn::Error> {
+    let mut errors = vec![];
+    for filter in filters {
+        let mut valid = true;
+        for condition in &filter.conditions {
+            match matrix.lists.iter().find(|vlist| vlist.arg == condition.arg) {
+                None => {
+                    valid = false;
+                    errors.push(syn::Error::new(
+                        condition.arg.span(),
+                        format!(
+                            "'{}' is not a #[values] argument: #[{}] can filter just the values lists' combinations",
+                            condition.arg,
+                            filter.kind.name()
+                        ),
+                    ))
+                }
+                Some(vlist) if !(0..vlist.values.len()).any(|i| condition.matches(vlist, i)) => {
+                    valid = false;
+                    errors.push(syn::Error::new_spanned(
+                        &condition.value,
+                        format!(
+                            "No value of '{}' matches '{}'",
+                            condition.arg,
+                            condition.value.to_token_stream()
+                        ),
+                    ))
+                }
+                _ => {}
+            }
+        }
+        if valid
+            && !any_combination(matrix, &|prefix| {
+                matrix.matches_prefix(filter, prefix) != Some(false)
+            })
+        {
+            errors.push(syn::Error::new(
+                filter.attr.span(),
+                format!(
+                    "This #[{}] rule doesn't match any combination",
+                    filter.kind.name()
+                ),
+            ));
+        }
+    }
+    if errors.is_empty()
+        && !filters.is_empty()
+        && !any_combination(matrix, &|prefix| accept_prefix(matrix, filters, prefix))
+    {
+        errors.push(syn::Error::new(
+            filters[0].attr.span(),
+            "#[exclude] and #[only] rules remove all the combinations",
+        ));
+    }
+    errors
+}
+
+/// Simple struct used to visit function attributes and extract `#[exclude]` and `#[only]`
+/// filters and eventualy parsing errors
+#[derive(Default)]
+struct CombinationsFiltersFunctionExtractor(Vec<CombinationsFilter>, Vec<syn::Error>);
+
+impl VisitMut for CombinationsFiltersFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let mut remain = vec![];
+        for attr in attrs {
+            match FilterKind::from_attr(&attr) {
+                Some(kind) => match CombinationsFilter::from_attr(kind, &attr) {
+                    Ok(filter) => self.0.push(filter),
+                    Err(err) => self.1.push(err),
+                },
+                None => remain.push(attr),
+            }
+        }
+        node.attrs = remain;
+    }
+}
+
+pub(crate) fn extract_combinations_filters(
+    item_fn: &mut ItemFn,
+) -> std::result::Result<Vec<CombinationsFilter>, ErrorsVec> {
+    let mut filters_extractor = CombinationsFiltersFunctionExtractor::default();
+    filters_extractor.visit_item_fn_mut(item_fn);
+
+    if filters_extractor.1.len() > 0 {
+        Err(filters_extractor.1.into())
+    } else {
+        Ok(filters_extractor.0)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use mytest::rstest;
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case::all("combinations = all", CombinationsMode::All)]
+    #[case::pairwise("combinations = pairwise", CombinationsMode::NWise(2))]
+    #[case::nwise("combinations = nwise(3)", CombinationsMode::NWise(3))]
+    #[case::zip("combinations = zip", CombinationsMode::Zip)]
+    fn parse_combinations_mode(#[case] input: &str, #[case] expected: CombinationsMode) {
+        let definition: CombinationsDefinition = input.ast();
+
+        assert_eq!(
+            expected,
+            CombinationsMode::from_expr(&definition.0).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case::unknown("combinations = random", "Unknown combinations mode 'random'")]
+    #[case::no_strength("combinations = nwise", "Unknown combinations mode 'nwise'")]
+    #[case::more_args("combinations = nwise(2, 3)", "Unknown combinations mode")]
+    #[case::small_strength("combinations = nwise(1)", "nwise(<n>) needs at least 2")]
+    fn raise_error_on_invalid_combinations_mode(#[case] input: &str, #[case] expected: &str) {
+        let error = syn::parse_str::<CombinationsDefinition>(input).unwrap_err();
+
+        assert_in!(error.to_string(), expected);
+    }
+
+    fn filter(code: &str) -> CombinationsFilter {
+        let mut item_fn: ItemFn = format!("{} fn f() {{}}", code).ast();
+        extract_combinations_filters(&mut item_fn)
+            .unwrap()
+            .remove(0)
+    }
+
+    /// The cartesian product of the dimensions' indexes.
+    fn combinations(matrix: &Matrix) -> Vec<Vec<usize>> {
+        let mut combinations = vec![];
+        matrix.walk(&|_| true, &mut |combination| {
+            combinations.push(combination.to_vec());
+            true
+        });
+        combinations
+    }
+
+    fn lists() -> Vec<ValueList> {
+        vec![
+            values_list("tls", &["true", "false"]),
+            syn::parse_str("port => [http => 80, https => 443, 8080]").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn extract_filters_and_remove_attributes() {
+        let mut item_fn: ItemFn = r#"
+            #[first]
+            #[exclude(tls = false, port = 443)]
+            #[only(port = 80)]
+            #[last]
+            fn f() {}
+        "#
+        .ast();
+
+        let filters = extract_combinations_filters(&mut item_fn).unwrap();
+
+        assert_eq!(
+            vec![FilterKind::Exclude, FilterKind::Only],
+            filters.iter().map(|f| f.kind).collect::<Vec<_>>()
+        );
+        assert_eq!(2, filters[0].conditions.len());
+        assert_eq!(attrs("#[first] #[last]"), item_fn.attrs);
+    }
+
+    #[rstest]
+    #[case::literal("#[exclude(tls = false)]", &[(1, 0), (1, 1), (1, 2)])]
+    #[case::name("#[exclude(port = https)]", &[(0, 1), (1, 1)])]
+    #[case::all_conditions("#[exclude(tls = false, port = 443)]", &[(1, 1)])]
+    fn match_combinations(#[case] code: &str, #[case] expected: &[(usize, usize)]) {
+        let lists = lists();
+        let matrix = Matrix::new(lists.iter().collect(), |_| false);
+        let filter = filter(code);
+
+        let matched = combinations(&matrix)
+            .into_iter()
+            .filter(|indexes| matrix.matches_prefix(&filter, indexes) == Some(true))
+            .map(|indexes| (indexes[0], indexes[1]))
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected, matched.as_slice());
+    }
+
+    #[test]
+    fn accept_only_and_not_excluded_combinations() {
+        let lists = lists();
+        let matrix = Matrix::new(lists.iter().collect(), |_| false);
+        let filters = vec![
+            filter("#[only(port = 443)]"),
+            filter("#[only(port = 8080)]"),
+            filter("#[exclude(tls = false, port = https)]"),
+        ];
+
+        let accepted = accepted_combinations(&matrix, &filters.iter().collect::<Vec<_>>()).unwrap();
+
+        assert_eq!(vec![vec![0, 1], vec![0, 2], vec![1, 2]], accepted);
+    }
+
+    #[test]
+    fn not_expand_the_removed_prefixes() {
+        let lists = lists();
+        let matrix = Matrix::new(lists.iter().collect(), |_| false);
+        let filters = vec![
+            filter("#[only(tls = true)]"),
+            filter("#[exclude(port = 80)]"),
+        ];
+        let filters = filters.iter().collect::<Vec<_>>();
+        let visited = std::cell::RefCell::new(vec![]);
+
+        matrix.walk(
+            &|prefix| {
+                visited.borrow_mut().push(prefix.to_vec());
+                accept_prefix(&matrix, &filters, prefix)
+            },
+            &mut |_| true,
+        );
+
+        assert_eq!(
+            vec![vec![0], vec![0, 0], vec![0, 1], vec![0, 2], vec![1]],
+            visited.into_inner()
+        );
+    }
+
+    #[test]
+    fn accept_all_combinations_without_filters() {
+        let lists = lists();
+
+        assert_eq!(
+            None,
+            accepted_combinations(&Matrix::new(lists.iter().collect(), |_| false), &[])
+        );
+    }
+
+    fn zip_lists() -> Vec<ValueList> {
+        vec![
+            values_list("a", &["1", "2", "3"]),
+            values_list("b", &["true", "false"]),
+            values_list("c", &["\"x\"", "\"y\"", "\"z\""]),
+        ]
+    }
+
+    #[test]
+    fn zip_lists_in_a_single_dimension() {
+        let lists = zip_lists();
+
+        let matrix = Matrix::new(lists.iter().collect(), |vlist| vlist.arg != "b");
+
+        assert_eq!(vec![vec![0, 2], vec![1]], matrix.dimensions);
+        assert_eq!(vec![3, 2], matrix.sizes());
+        assert_eq!(6, combinations(&matrix).len());
+    }
+
+    #[test]
+    fn filter_zipped_combinations() {
+        let lists = zip_lists();
+        let matrix = Matrix::new(lists.iter().collect(), |vlist| vlist.arg != "b");
+        let filters = vec![filter("#[exclude(a = 2)]")];
+
+        let accepted = accepted_combinations(&matrix, &filters.iter().collect::<Vec<_>>()).unwrap();
+
+        assert_eq!(
+            vec![vec![0, 0], vec![0, 1], vec![2, 0], vec![2, 1]],
+            accepted
+        );
+    }
+
+    #[test]
+    fn raise_error_on_zipped_lists_with_different_lengths() {
+        let lists = zip_lists();
+        let matrix = Matrix::new(lists.iter().collect(), |_| true);
+
+        let errors = check_zipped(&matrix, &[]);
+
+        assert_eq!(1, errors.len());
+        assert_in!(
+            errors[0].to_string(),
+            "Zipped values lists should have the same length: 'b' has 2 values but 'a' has 3"
+        );
+    }
+
+    #[test]
+    fn raise_error_on_zip_argument_without_values() {
+        let lists = zip_lists();
+        let matrix = Matrix::new(lists.iter().collect(), |vlist| vlist.arg != "b");
+
+        let errors = check_zipped(&matrix, &[&ident("d")]);
+
+        assert_eq!(1, errors.len());
+        assert_in!(errors[0].to_string(), "'d' is not a #[values] argument");
+    }
+
+    #[rstest]
+    #[case::unknown_arg("#[exclude(host = 1)]", "'host' is not a #[values] argument")]
+    #[case::unknown_value("#[only(port = 444)]", "No value of 'port' matches '444'")]
+    #[case::no_combination(
+        "#[exclude(port = 80, port = 443)]",
+        "This #[exclude] rule doesn't match any combination"
+    )]
+    #[case::remove_all(
+        "#[exclude(tls = true)] #[exclude(tls = false)]",
+        "#[exclude] and #[only] rules remove all the combinations"
+    )]
+    fn raise_error(#[case] code: &str, #[case] expected: &str) {
+        let lists = lists();
+        let mut item_fn: ItemFn = format!("{} fn f() {{}}", code).ast();
+        let filters = extract_combinations_filters(&mut item_fn).unwrap();
+
+        let errors = check_filters(
+            &Matrix::new(lists.iter().collect(), |_| false),
+            &filters.iter().collect::<Vec<_>>(),
+        );
+
+        assert_eq!(1, errors.len());
+        assert_in!(errors[0].to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::empty("#[exclude()]", "#[exclude] needs at least a condition")]
+    #[case::no_value("#[only(port)]", "expected `=`")]
+    fn raise_parse_error(#[case] code: &str, #[case] expected: &str) {
+        let mut item_fn: ItemFn = format!("{} fn f() {{}}", code).ast();
+
+        let errors = extract_combinations_filters(&mut item_fn).unwrap_err();
+
+        assert_in!(errors[0].to_string(), expected);
+    }
+}
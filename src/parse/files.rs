@@ -0,0 +1,458 @@
+/// `#[files("glob")]` argument's attribute: the glob is expanded at compile time and every
+/// matched file becomes a value of the argument's values list. `#[exclude("regex")]` removes
+/// some files and `#[mode = path|str|bytes]` select what is injected.
+use std::path::{Path, PathBuf};
+
+use quote::ToTokens;
+use regex::Regex;
+use syn::{
+    ext::IdentExt,
+    parse::{ParseStream, Parser},
+    parse_quote, Attribute, Expr, FnArg, Ident, LitStr, Token,
+};
+
+use crate::{refident::MaybeIdent, utils::attr_is};
+
+use super::vlist::ValueList;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum FilesMode {
+    Path,
+    Str,
+    Bytes,
+}
+
+impl Default for FilesMode {
+    fn default() -> Self {
+        FilesMode::Path
+    }
+}
+
+impl FilesMode {
+    const NAMES: [(&'static str, FilesMode); 3] = [
+        ("path", FilesMode::Path),
+        ("str", FilesMode::Str),
+        ("bytes", FilesMode::Bytes),
+    ];
+
+    fn parse_attribute(attr: &Attribute) -> syn::Result<Self> {
+        fn parse_name(input: ParseStream) -> syn::Result<Ident> {
+            let _: Token![=] = input.parse()?;
+            input.call(Ident::parse_any)
+        }
+        let name = parse_name.parse2(attr.tokens.clone()).map_err(|_| {
+            syn::Error::new_spanned(attr, "Invalid mode syntax: should be #[mode = <name>]")
+        })?;
+        Self::NAMES
+            .iter()
+            .find(|(n, _)| name == n)
+            .map(|&(_, mode)| mode)
+            .ok_or_else(|| {
+                syn::Error::new(
+                    name.span(),
+                    format!("Unknown mode '{}': should be one of path, str, bytes", name),
+                )
+            })
+    }
+}
+
+/// The `#[files]`, `#[exclude]` and `#[mode]` attributes of an argument.
+#[derive(Debug, Default)]
+pub(crate) struct Files {
+    globs: Vec<LitStr>,
+    excludes: Vec<(LitStr, Regex)>,
+    mode: Option<(Attribute, FilesMode)>,
+}
+
+impl Files {
+    const FILES: &'static str = "files";
+    const EXCLUDE: &'static str = "exclude";
+    const MODE: &'static str = "mode";
+
+    fn is_files_attribute(attr: &Attribute) -> bool {
+        [Self::FILES, Self::EXCLUDE, Self::MODE]
+            .iter()
+            .any(|name| attr_is(attr, name))
+    }
+
+    fn from_attributes(attrs: Vec<Attribute>) -> Result<Self, Vec<syn::Error>> {
+        let mut files = Self::default();
+        let mut errors = vec![];
+        for attr in attrs {
+            if attr_is(&attr, Self::FILES) {
+                match attr.parse_args::<LitStr>() {
+                    Ok(glob) => files.globs.push(glob),
+                    Err(_) => errors.push(syn::Error::new_spanned(
+                        attr,
+                        r#"Invalid files syntax: should be #[files("<glob>")]"#,
+                    )),
+                }
+            } else if attr_is(&attr, Self::EXCLUDE) {
+                match attr.parse_args::<LitStr>() {
+                    Ok(exclude) => match Regex::new(&exclude.value()) {
+                        Ok(regex) => files.excludes.push((exclude, regex)),
+                        Err(err) => errors.push(syn::Error::new(
+                            exclude.span(),
+                            format!("Invalid exclude regex: {}", err),
+                        )),
+                    },
+                    Err(_) => errors.push(syn::Error::new_spanned(
+                        attr,
+                        r#"Invalid exclude syntax: should be #[exclude("<regex>")]"#,
+                    )),
+                }
+            } else if files.mode.is_some() {
+                errors.push(syn::Error::new_spanned(
+                    attr,
+                    "You cannot use #[mode] more than once",
+                ));
+            } else {
+                match FilesMode::parse_attribute(&attr) {
+                    Ok(mode) => files.mode = Some((attr, mode)),
+                    Err(err) => errors.push(err),
+                }
+            }
+        }
+        if files.globs.is_empty() {
+            let orphans = files
+                .excludes
+                .iter()
+                .map(|(e, _)| e.to_token_stream())
+                .chain(files.mode.iter().map(|(a, _)| a.to_token_stream()));
+            errors.extend(orphans.map(|tokens| {
+                syn::Error::new_spanned(
+                    tokens,
+                    "#[exclude] and #[mode] can be used just with #[files]",
+                )
+            }));
+        }
+        if errors.is_empty() {
+            Ok(files)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn mode(&self) -> FilesMode {
+        self.mode
+            .as_ref()
+            .map(|(_, mode)| *mode)
+            .unwrap_or_default()
+    }
+
+    /// All the files matched by the globs (relative to `base_dir`) and not excluded, sorted and
+    /// without duplicates.
+    fn collect(&self, base_dir: &Path) -> syn::Result<Vec<PathBuf>> {
+        let mut paths = vec![];
+        for glob in &self.globs {
+            let pattern = base_dir.join(glob.value());
+            let entries = glob::glob(&pattern.to_string_lossy()).map_err(|err| {
+                syn::Error::new(glob.span(), format!("Invalid glob pattern: {}", err))
+            })?;
+            let mut found = false;
+            for entry in entries {
+                let path = entry.map_err(|err| {
+                    syn::Error::new(glob.span(), format!("Cannot read the file: {}", err))
+                })?;
+                if !path.is_file() {
+                    continue;
+                }
+                found = true;
+                if !self.is_excluded(&path, base_dir) {
+                    paths.push(path);
+                }
+            }
+            if !found {
+                return Err(syn::Error::new(
+                    glob.span(),
+                    format!("The glob '{}' matched no files", glob.value()),
+                ));
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+
+    /// The regexes are matched against the path relative to `base_dir` with `/` as separator.
+    fn is_excluded(&self, path: &Path, base_dir: &Path) -> bool {
+        let relative = path
+            .strip_prefix(base_dir)
+            .unwrap_or(path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        self.excludes
+            .iter()
+            .any(|(_, regex)| regex.is_match(&relative))
+    }
+
+    fn value(&self, path: &Path) -> Expr {
+        let path = path.to_string_lossy().into_owned();
+        match self.mode() {
+            // The test reads the file at run time: there is no need to compile it again when
+            // the file changes.
+            FilesMode::Path => parse_quote! { std::path::PathBuf::from(#path) },
+            FilesMode::Str => parse_quote! { include_str!(#path) },
+            FilesMode::Bytes => parse_quote! { &include_bytes!(#path)[..] },
+        }
+    }
+
+    pub(crate) fn value_list(&self, arg: Ident, base_dir: &Path) -> syn::Result<ValueList> {
+        let paths = self.collect(base_dir)?;
+        if paths.is_empty() {
+            return Err(syn::Error::new(
+                self.globs[0].span(),
+                "All the files matched by #[files] are excluded",
+            ));
+        }
+        let labels = paths.iter().map(|p| Some(file_label(p))).collect();
+        let values = paths.iter().map(|p| self.value(p)).collect();
+        Ok(ValueList {
+            labels,
+            ..ValueList::new(arg, values)
+        })
+    }
+}
+
+/// The file name where every char that cannot be used in an identifier is replaced by `_`.
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Remove `#[files]`, `#[exclude]` and `#[mode]` attributes from the argument and, if
+/// `#[files]` is present, build the argument's values list from the globs resolved
+/// relative to the crate's root.
+pub(crate) fn extract_files(node: &mut FnArg) -> Result<Option<ValueList>, Vec<syn::Error>> {
+    let name = match node.maybe_ident() {
+        Some(name) => name.clone(),
+        None => return Ok(None),
+    };
+    let arg = match node {
+        FnArg::Typed(arg) => arg,
+        _ => return Ok(None),
+    };
+    let (attrs, remain): (Vec<_>, Vec<_>) = std::mem::take(&mut arg.attrs)
+        .into_iter()
+        .partition(Files::is_files_attribute);
+    arg.attrs = remain;
+    if attrs.is_empty() {
+        return Ok(None);
+    }
+    let files = Files::from_attributes(attrs)?;
+    files
+        .value_list(name, &super::manifest_dir())
+        .map(Some)
+        .map_err(|err| vec![err])
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use mytest::rstest;
+    use rstest_test::assert_in;
+    use temp_testdir::TempDir;
+
+    fn files(s: &str) -> Files {
+        Files::from_attributes(attrs(s)).unwrap()
+    }
+
+    fn files_error(s: &str) -> String {
+        Files::from_attributes(attrs(s))
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn create_files(root: &Path, paths: &[&str]) {
+        for path in paths {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "content").unwrap();
+        }
+    }
+
+    fn relative(root: &Path, paths: Vec<PathBuf>) -> Vec<String> {
+        paths
+            .into_iter()
+            .map(|p| {
+                p.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collect_sorted_files_from_more_globs() {
+        let root = TempDir::default();
+        create_files(
+            &root,
+            &["data/b.json", "data/a.json", "data/c.txt", "other/d.json"],
+        );
+
+        let files = files(r#"#[files("data/*.json")] #[files("**/*.json")]"#);
+
+        assert_eq!(
+            vec!["data/a.json", "data/b.json", "other/d.json"],
+            relative(&root, files.collect(&root).unwrap())
+        );
+    }
+
+    #[test]
+    fn skip_directories() {
+        let root = TempDir::default();
+        create_files(&root, &["data/a.json", "data/sub/b.json"]);
+
+        let files = files(r#"#[files("data/*")]"#);
+
+        assert_eq!(
+            vec!["data/a.json"],
+            relative(&root, files.collect(&root).unwrap())
+        );
+    }
+
+    #[test]
+    fn exclude_files_that_match_a_regex_on_relative_path() {
+        let root = TempDir::default();
+        create_files(&root, &["data/a.json", "data/skip_b.json", "data/c.json"]);
+
+        let files = files(r#"#[files("data/*.json")] #[exclude("skip_")] #[exclude("^data/c")]"#);
+
+        assert_eq!(
+            vec!["data/a.json"],
+            relative(&root, files.collect(&root).unwrap())
+        );
+    }
+
+    #[test]
+    fn build_labelled_values_list() {
+        let root = TempDir::default();
+        create_files(&root, &["data/first-file.json", "data/Second.json"]);
+
+        let vlist = files(r#"#[files("data/*.json")]"#)
+            .value_list(ident("path"), &root)
+            .unwrap();
+
+        assert_eq!(ident("path"), vlist.arg);
+        assert_eq!(2, vlist.values.len());
+        assert_eq!(Some("Second_json"), vlist.label(0));
+        assert_eq!(Some("first_file_json"), vlist.label(1));
+    }
+
+    #[rstest]
+    #[case::path("", "PathBuf :: from")]
+    #[case::str("#[mode = str]", "include_str !")]
+    #[case::bytes("#[mode = bytes]", "include_bytes !")]
+    fn inject_value_by_mode(#[case] mode: &str, #[case] expected: &str) {
+        let root = TempDir::default();
+        create_files(&root, &["a.txt"]);
+
+        let vlist = files(&format!(r#"#[files("*.txt")] {}"#, mode))
+            .value_list(ident("a"), &root)
+            .unwrap();
+
+        let value = vlist.values[0].to_token_stream().to_string();
+        assert_in!(value, expected);
+        assert_in!(value, "a.txt");
+    }
+
+    #[test]
+    fn extract_files_attributes_from_argument() {
+        let mut item_fn: syn::ItemFn =
+            r#"fn test(#[files("src/*.rs")] #[mode = str] #[other] content: &str) {}"#.ast();
+        let arg = item_fn.sig.inputs.first_mut().unwrap();
+
+        let vlist = extract_files(arg).unwrap().unwrap();
+
+        assert_eq!(ident("content"), vlist.arg);
+        assert!(vlist.labels.contains(&Some("lib_rs".to_owned())));
+        match arg {
+            FnArg::Typed(arg) => assert_eq!(attrs("#[other]"), arg.attrs),
+            _ => unreachable!(),
+        }
+    }
+
+    mod raise_error {
+        use super::{assert_eq, *};
+
+        #[test]
+        fn if_no_file_match_the_glob() {
+            let root = TempDir::default();
+
+            let error = files(r#"#[files("*.none")]"#).collect(&root).unwrap_err();
+
+            assert_eq!("The glob '*.none' matched no files", error.to_string());
+        }
+
+        #[test]
+        fn if_the_glob_matches_just_folders() {
+            let root = TempDir::default();
+            create_files(&root, &["data/a.txt"]);
+
+            let error = files(r#"#[files("dat*")]"#).collect(&root).unwrap_err();
+
+            assert_eq!("The glob 'dat*' matched no files", error.to_string());
+        }
+
+        #[test]
+        fn if_all_files_are_excluded() {
+            let root = TempDir::default();
+            create_files(&root, &["a.txt"]);
+
+            let error = files(r#"#[files("*.txt")] #[exclude("txt")]"#)
+                .value_list(ident("a"), &root)
+                .unwrap_err();
+
+            assert_in!(error.to_string(), "are excluded");
+        }
+
+        #[rstest]
+        #[case::invalid_glob(r#"#[files("a/**b")]"#, "Invalid glob pattern")]
+        #[case::invalid_files(r#"#[files(42)]"#, "Invalid files syntax")]
+        fn on_collect(#[case] input: &str, #[case] expected: &str) {
+            let root = TempDir::default();
+
+            let error = Files::from_attributes(attrs(input))
+                .map_err(|e| e[0].clone())
+                .and_then(|f| f.collect(&root))
+                .unwrap_err();
+
+            assert_in!(error.to_string(), expected);
+        }
+
+        #[rstest]
+        #[case::invalid_regex(r#"#[files("*")] #[exclude("(")]"#, "Invalid exclude regex")]
+        #[case::invalid_exclude(r#"#[files("*")] #[exclude]"#, "Invalid exclude syntax")]
+        #[case::unknown_mode(
+            r#"#[files("*")] #[mode = text]"#,
+            "Unknown mode 'text': should be one of path, str, bytes"
+        )]
+        #[case::invalid_mode(r#"#[files("*")] #[mode(str)]"#, "Invalid mode syntax")]
+        #[case::more_modes(
+            r#"#[files("*")] #[mode = str] #[mode = bytes]"#,
+            "You cannot use #[mode] more than once"
+        )]
+        #[case::exclude_without_files(
+            r#"#[exclude("a")]"#,
+            "#[exclude] and #[mode] can be used just with #[files]"
+        )]
+        #[case::mode_without_files(
+            r#"#[mode = str]"#,
+            "#[exclude] and #[mode] can be used just with #[files]"
+        )]
+        fn on_parse(#[case] input: &str, #[case] expected: &str) {
+            assert_in!(files_error(input), expected);
+        }
+    }
+}
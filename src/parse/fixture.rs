@@ -7,11 +7,12 @@ use syn::{
 };
 
 use super::{
-    extract_argument_attrs, extract_default_return_type, extract_defaults, extract_fixtures,
-    extract_partials_return_type, parse_vector_trailing_till_double_comma, Attributes,
-    ExtendWithFunctionAttrs, Fixture, Positional,
+    extract_argument_attrs, extract_default_return_type, extract_defaults, extract_fixture_values,
+    extract_fixtures, extract_once, extract_params, extract_partials_return_type, extract_shared,
+    extract_teardown, parse_fixture_path, parse_vector_trailing_till_double_comma, Attributes,
+    ExtendWithFunctionAttrs, Fixture, FixtureArgs, Positional,
 };
-use crate::parse::Attribute;
+use crate::parse::{future::AwaitedFutures, vlist::ValueList, Attribute};
 use crate::{error::ErrorsVec, refident::RefIdent, utils::attr_is};
 use proc_macro2::TokenStream;
 use quote::{format_ident, ToTokens};
@@ -22,6 +23,13 @@ pub(crate) struct FixtureInfo {
     pub(crate) attributes: FixtureModifiers,
 }
 
+impl FixtureInfo {
+    /// The fixture has its own values or uses some parametrized fixture by `#[params]`.
+    pub(crate) fn is_parametrized(&self) -> bool {
+        self.data.value_list().is_some() || self.attributes.params().next().is_some()
+    }
+}
+
 impl Parse for FixtureModifiers {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         Ok(input.parse::<Attributes>()?.into())
@@ -53,18 +61,29 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
             fixtures,
             defaults,
             default_return_type,
-            partials_return_type
+            partials_return_type,
+            once,
+            shared,
+            teardown,
+            values,
+            params
         ) = merge_errors!(
             extract_fixtures(item_fn),
             extract_defaults(item_fn),
             extract_default_return_type(item_fn),
-            extract_partials_return_type(item_fn)
+            extract_partials_return_type(item_fn),
+            extract_once(item_fn),
+            extract_shared(item_fn),
+            extract_teardown(item_fn),
+            extract_fixture_values(item_fn),
+            extract_params(item_fn)
         )?;
         self.data.items.extend(
             fixtures
                 .into_iter()
                 .map(|f| f.into())
-                .chain(defaults.into_iter().map(|d| d.into())),
+                .chain(defaults.into_iter().map(|d| d.into()))
+                .chain(values.into_iter().map(|v| v.into())),
         );
         if let Some(return_type) = default_return_type {
             self.attributes.set_default_return_type(return_type);
@@ -72,6 +91,16 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
         for (id, return_type) in partials_return_type {
             self.attributes.set_partial_return_type(id, return_type);
         }
+        if let Some(once) = once {
+            self.attributes.set_once(once);
+        }
+        if let Some(shared) = shared {
+            self.attributes.set_shared(shared);
+        }
+        if let Some((name, teardown)) = teardown {
+            self.attributes.set_teardown(name, teardown);
+        }
+        self.attributes.add_params(params);
         Ok(())
     }
 }
@@ -83,19 +112,71 @@ pub(crate) struct FixturesFunctionExtractor(pub(crate) Vec<Fixture>, pub(crate)
 
 impl VisitMut for FixturesFunctionExtractor {
     fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        let mut fixtures = vec![];
         for r in extract_argument_attrs(
             node,
             |a| attr_is(a, "with"),
             |a, name| {
-                a.parse_args::<Positional>()
-                    .map(|p| Fixture::new(name.clone(), p))
+                a.parse_args::<FixtureArgs>()
+                    .map(|args| Fixture::new(name.clone(), args.positional).with_named(args.named))
             },
         ) {
             match r {
-                Ok(fixture) => self.0.push(fixture),
+                Ok(fixture) => fixtures.push(fixture),
                 Err(err) => self.1.push(err),
             }
         }
+        let mut resolves = vec![];
+        for r in extract_argument_attrs(
+            node,
+            |a| attr_is(a, "from"),
+            |a, name| {
+                a.parse_args_with(parse_fixture_path)
+                    .map(|p| (a.clone(), name.clone(), p))
+            },
+        ) {
+            match r {
+                Ok(resolve) => resolves.push(resolve),
+                Err(err) => self.1.push(err),
+            }
+        }
+        let mut resolves = resolves.into_iter();
+        if let Some((_, name, resolve)) = resolves.next() {
+            match fixtures.first_mut() {
+                Some(fixture) => fixture.resolve = Some(resolve),
+                None => fixtures.push(Fixture::new(name, Positional(vec![])).with_resolve(resolve)),
+            }
+        }
+        self.1.extend(resolves.map(|(attr, _, _)| {
+            syn::Error::new_spanned(attr, "You cannot use #[from] more than once")
+        }));
+        let shared = extract_argument_attrs(
+            node,
+            |a| attr_is(a, FixtureModifiers::SHARED_ATTR),
+            |a, name| Ok((a, name.clone())),
+        )
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>();
+        if let Some((attr, name)) = shared.first() {
+            match fixtures.pop() {
+                Some(fixture) if !fixture.positional.0.is_empty() || !fixture.named.is_empty() => {
+                    self.1.push(syn::Error::new_spanned(
+                        attr,
+                        "Cannot use #[shared] with #[with]: the shared instance is the default one",
+                    ));
+                    fixtures.push(fixture);
+                }
+                Some(fixture) => fixtures.push(fixture.shared()),
+                None => fixtures.push(Fixture::new(name.clone(), Positional(vec![])).shared()),
+            }
+            if !attr.tokens.is_empty() {
+                self.1.push(syn::Error::new_spanned(
+                    attr,
+                    "Invalid shared syntax: should be just #[shared]",
+                ));
+            }
+        }
+        self.0.extend(fixtures);
     }
 }
 
@@ -144,6 +225,16 @@ impl FixtureData {
             _ => None,
         })
     }
+
+    pub(crate) fn value_list(&self) -> Option<&ValueList> {
+        self.items
+            .iter()
+            .filter_map(|f| match f {
+                FixtureItem::ValueList(ref value_list) => Some(value_list),
+                _ => None,
+            })
+            .next()
+    }
 }
 
 impl Parse for FixtureData {
@@ -158,7 +249,7 @@ impl Parse for FixtureData {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub(crate) struct ArgumentValue {
     pub name: Ident,
     pub expr: Expr,
@@ -168,12 +259,21 @@ impl ArgumentValue {
     pub(crate) fn new(name: Ident, expr: Expr) -> Self {
         Self { name, expr }
     }
+
+    /// The input starts with a `name = value` (and not with a comparison or a `name => ...`).
+    pub(crate) fn peek(input: ParseStream) -> bool {
+        input.peek(Ident)
+            && input.peek2(Token![=])
+            && !input.peek2(Token![==])
+            && !input.peek2(Token![=>])
+    }
 }
 
 #[derive(PartialEq, Debug)]
 pub(crate) enum FixtureItem {
     Fixture(Fixture),
     ArgumentValue(ArgumentValue),
+    ValueList(ValueList),
 }
 
 impl From<Fixture> for FixtureItem {
@@ -197,6 +297,7 @@ impl RefIdent for FixtureItem {
         match self {
             FixtureItem::Fixture(Fixture { ref name, .. }) => name,
             FixtureItem::ArgumentValue(ArgumentValue { ref name, .. }) => name,
+            FixtureItem::ValueList(ValueList { ref arg, .. }) => arg,
         }
     }
 }
@@ -213,6 +314,12 @@ impl From<ArgumentValue> for FixtureItem {
     }
 }
 
+impl From<ValueList> for FixtureItem {
+    fn from(value_list: ValueList) -> Self {
+        FixtureItem::ValueList(value_list)
+    }
+}
+
 impl Parse for ArgumentValue {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let name = input.parse()?;
@@ -227,6 +334,11 @@ wrap_attributes!(FixtureModifiers);
 impl FixtureModifiers {
     pub(crate) const DEFAULT_RET_ATTR: &'static str = "default";
     pub(crate) const PARTIAL_RET_ATTR: &'static str = "partial_";
+    pub(crate) const ONCE_ATTR: &'static str = "once";
+    pub(crate) const SHARED_ATTR: &'static str = "shared";
+    pub(crate) const TEARDOWN_ATTR: &'static str = "teardown";
+    pub(crate) const VALUES_ATTR: &'static str = "values";
+    pub(crate) const PARAMS_ATTR: &'static str = "params";
 
     pub(crate) fn extract_default_type(&self) -> Option<syn::ReturnType> {
         self.extract_type(Self::DEFAULT_RET_ATTR)
@@ -250,6 +362,69 @@ impl FixtureModifiers {
         ))
     }
 
+    pub(crate) fn set_once(&mut self, once: Ident) {
+        self.inner.attributes.push(Attribute::Attr(once))
+    }
+
+    pub(crate) fn get_once(&self) -> Option<&Ident> {
+        self.iter()
+            .filter_map(|m| match m {
+                Attribute::Attr(name) if name == Self::ONCE_ATTR => Some(name),
+                _ => None,
+            })
+            .next()
+    }
+
+    pub(crate) fn is_once(&self) -> bool {
+        self.get_once().is_some()
+    }
+
+    pub(crate) fn set_shared(&mut self, shared: Ident) {
+        self.inner.attributes.push(Attribute::Attr(shared))
+    }
+
+    pub(crate) fn get_shared(&self) -> Option<&Ident> {
+        self.iter()
+            .filter_map(|m| match m {
+                Attribute::Attr(name) if name == Self::SHARED_ATTR => Some(name),
+                _ => None,
+            })
+            .next()
+    }
+
+    pub(crate) fn set_teardown(&mut self, name: Ident, teardown: syn::Expr) {
+        self.inner.attributes.push(Attribute::Expr(name, teardown))
+    }
+
+    pub(crate) fn get_teardown(&self) -> Option<(&Ident, &syn::Expr)> {
+        self.iter()
+            .filter_map(|m| match m {
+                Attribute::Expr(name, e) if name == Self::TEARDOWN_ATTR => Some((name, e)),
+                _ => None,
+            })
+            .next()
+    }
+
+    pub(crate) fn add_params(&mut self, params: Vec<Ident>) {
+        if params.is_empty() {
+            return;
+        }
+        self.inner.attributes.push(Attribute::Tagged(
+            format_ident!("{}", Self::PARAMS_ATTR),
+            params,
+        ));
+    }
+
+    /// The arguments marked by `#[params]`: the parametrized fixtures that this one uses.
+    pub(crate) fn params(&self) -> impl Iterator<Item = &Ident> {
+        self.iter()
+            .filter_map(|m| match m {
+                Attribute::Tagged(name, args) if name == Self::PARAMS_ATTR => Some(args),
+                _ => None,
+            })
+            .flatten()
+    }
+
     fn extract_type(&self, attr_name: &str) -> Option<syn::ReturnType> {
         self.iter()
             .filter_map(|m| match m {
@@ -258,6 +433,15 @@ impl FixtureModifiers {
             })
             .next()
     }
+
+    /// Record the `#[future]` arguments that should be awaited before call the function.
+    pub(crate) fn set_awaited(&mut self, awaited: AwaitedFutures) {
+        self.inner.attributes.extend(awaited.to_attribute())
+    }
+
+    pub(crate) fn awaited(&self) -> AwaitedFutures {
+        AwaitedFutures::from_attributes(self.iter())
+    }
 }
 
 #[cfg(test)]
@@ -339,6 +523,41 @@ mod should {
             assert_eq!(expected, data);
         }
 
+        #[test]
+        fn renamed_fixtures() {
+            let data = parse_fixture(
+                r#"my_fixture(42) as other, crate::fixtures::db as conn, <Server>::started as s"#,
+            );
+
+            let expected = FixtureInfo {
+                data: vec![
+                    fixture("other", vec!["42"])
+                        .with_resolve(parse_quote! { my_fixture })
+                        .into(),
+                    fixture("conn", vec![])
+                        .with_resolve(parse_quote! { crate::fixtures::db })
+                        .into(),
+                    fixture("s", vec![])
+                        .with_resolve(parse_quote! { <Server>::started })
+                        .into(),
+                ]
+                .into(),
+                ..Default::default()
+            };
+
+            assert_eq!(expected, data);
+        }
+
+        #[rstest]
+        #[case::no_args_or_rename("my_fixture", "arguments or 'as new_name'")]
+        #[case::path_without_rename("crate::my_fixture(42)", "should be renamed")]
+        #[case::associated_without_rename("<Server>::started(42)", "should be renamed")]
+        fn reject_invalid_fixture(#[case] input: &str, #[case] message: &str) {
+            let error = syn::parse_str::<FixtureInfo>(input).unwrap_err();
+
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+
         #[rstest]
         #[case("first(42),", 1)]
         #[case("first(42), second=42,", 2)]
@@ -363,6 +582,8 @@ mod extend {
 
     mod should {
         use super::{assert_eq, *};
+        use mytest::rstest;
+        use rstest_test::assert_in;
 
         #[test]
         fn use_with_attributes() {
@@ -388,6 +609,142 @@ mod extend {
             assert_eq!(expected, info);
         }
 
+        #[test]
+        fn use_with_named_values() {
+            let to_parse = r#"
+                fn my_fix(#[with("Bob", age = 30)] user: User, #[with(age = 42)] admin: User) {}
+            "#;
+
+            let mut item_fn: ItemFn = to_parse.ast();
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            let expected = FixtureInfo {
+                data: vec![
+                    fixture("user", vec![r#""Bob""#])
+                        .with_named(vec![arg_value("age", "30")])
+                        .into(),
+                    fixture("admin", vec![])
+                        .with_named(vec![arg_value("age", "42")])
+                        .into(),
+                ]
+                .into(),
+                ..Default::default()
+            };
+
+            assert_eq!(expected, info);
+        }
+
+        #[test]
+        fn use_from_attributes() {
+            let to_parse = r#"
+                fn my_fix(#[from(other)] f1: &str, #[with(42)] #[from(crate::fix)] f2: u32) {}
+            "#;
+
+            let mut item_fn: ItemFn = to_parse.ast();
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            let expected = FixtureInfo {
+                data: vec![
+                    fixture("f1", vec![])
+                        .with_resolve(parse_quote! { other })
+                        .into(),
+                    fixture("f2", vec!["42"])
+                        .with_resolve(parse_quote! { crate::fix })
+                        .into(),
+                ]
+                .into(),
+                ..Default::default()
+            };
+
+            assert!(!format!("{:?}", item_fn).contains("from"));
+            assert_eq!(expected, info);
+        }
+
+        #[test]
+        fn use_from_attributes_with_associated_fixtures() {
+            let to_parse = r#"
+                fn my_fix(#[from(Self::stopped)] f1: S, #[from(<server::S>::started)] f2: S) {}
+            "#;
+
+            let mut item_fn: ItemFn = to_parse.ast();
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            let expected = FixtureInfo {
+                data: vec![
+                    fixture("f1", vec![])
+                        .with_resolve(parse_quote! { Self::stopped })
+                        .into(),
+                    fixture("f2", vec![])
+                        .with_resolve(parse_quote! { <server::S>::started })
+                        .into(),
+                ]
+                .into(),
+                ..Default::default()
+            };
+
+            assert_eq!(expected, info);
+        }
+
+        #[rstest]
+        #[case::trait_path("<S as Trait>::started")]
+        #[case::nested_path("<S>::inner::started")]
+        fn raise_error_on_invalid_associated_fixture_path(#[case] path: &str) {
+            let mut item_fn: ItemFn = format!("fn my_fix(#[from({})] f: u32) {{}}", path).ast();
+            let mut info = FixtureInfo::default();
+
+            let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+            assert_in!(
+                format!("{:?}", errors),
+                "An associated fixture should be referred by `<Type>::name`"
+            );
+        }
+
+        #[test]
+        fn use_shared_attributes() {
+            let to_parse = r#"
+                fn my_fix(#[shared] f1: &str, #[shared] #[from(crate::fix)] f2: u32, f3: u32) {}
+            "#;
+
+            let mut item_fn: ItemFn = to_parse.ast();
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            let expected = FixtureInfo {
+                data: vec![
+                    fixture("f1", vec![]).shared().into(),
+                    fixture("f2", vec![])
+                        .with_resolve(parse_quote! { crate::fix })
+                        .shared()
+                        .into(),
+                ]
+                .into(),
+                ..Default::default()
+            };
+
+            assert!(!format!("{:?}", item_fn).contains("shared"));
+            assert_eq!(expected, info);
+        }
+
+        #[rstest]
+        #[case::with_values("#[shared] #[with(42)] f: u32", "Cannot use #[shared] with #[with]")]
+        #[case::with_arguments("#[shared(42)] f: u32", "Invalid shared syntax")]
+        fn raise_error_on_invalid_shared_attribute(#[case] arg: &str, #[case] message: &str) {
+            let mut item_fn: ItemFn = format!("fn my_fix({}) {{}}", arg).ast();
+            let mut info = FixtureInfo::default();
+
+            let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+            assert_in!(format!("{:?}", errors), message);
+        }
+
         #[test]
         fn use_default_values_attributes() {
             let to_parse = r#"
@@ -469,10 +826,251 @@ mod extend {
             );
         }
 
+        #[test]
+        fn find_once_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[first(comp)]
+                #[second::default]
+                #[once]
+                #[last::more]
+                fn my_fix<I, J, K>(f1: I, f2: J, f3: K) -> impl Iterator<Item=(I, J, K)> {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.is_once());
+            assert_eq!(
+                attrs("#[simple]#[first(comp)]#[second::default]#[last::more]"),
+                item_fn.attrs
+            );
+        }
+
+        #[test]
+        fn no_once_attribute() {
+            let mut item_fn: ItemFn = r#"
+                fn my_fix<I, J, K>(f1: I, f2: J, f3: K) -> impl Iterator<Item=(I, J, K)> {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(!info.attributes.is_once());
+        }
+
+        #[test]
+        fn find_shared_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[shared]
+                #[last::more]
+                fn my_fix() -> Db {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.get_shared().is_some());
+            assert_eq!(attrs("#[simple]#[last::more]"), item_fn.attrs);
+        }
+
+        #[test]
+        fn find_teardown_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[teardown(|db| db.drop_schema())]
+                #[last::more]
+                fn my_fix() -> Db {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(
+                Some(&expr("|db| db.drop_schema()")),
+                info.attributes.get_teardown().map(|(_, e)| e)
+            );
+            assert_eq!(attrs("#[simple]#[last::more]"), item_fn.attrs);
+        }
+
+        #[test]
+        fn find_values_attribute_and_assign_them_to_the_first_argument() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[values(Backend::Sqlite, Backend::Postgres)]
+                fn backend(kind: Backend, other: u32) -> Backend {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(
+                Some(&values_list(
+                    "kind",
+                    &["Backend::Sqlite", "Backend::Postgres"]
+                )),
+                info.data.value_list()
+            );
+            assert_eq!(attrs("#[simple]"), item_fn.attrs);
+        }
+
+        #[test]
+        fn find_params_arguments() {
+            let mut item_fn: ItemFn = r#"
+                fn db(#[params] backend: Backend, url: &str, #[params] _engine: Engine) -> Db {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(
+                to_idents!(["backend", "_engine"]),
+                info.attributes.params().cloned().collect::<Vec<_>>()
+            );
+            assert!(crate::utils::fn_args(&item_fn).all(|arg| match arg {
+                FnArg::Typed(a) => a.attrs.is_empty(),
+                _ => true,
+            }));
+        }
+
         mod raise_error {
             use super::{assert_eq, *};
             use rstest_test::assert_in;
 
+            #[test]
+            fn if_from_is_defined_more_than_once() {
+                let mut item_fn: ItemFn = r#"
+                    fn my_fix(#[from(a)] #[from(b)] f: u32) {}
+                    "#
+                .ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_in!(
+                    format!("{:?}", error).to_lowercase(),
+                    "cannot use #[from] more than once"
+                );
+            }
+
+            #[test]
+            fn if_once_is_defined_more_than_once() {
+                let mut item_fn: ItemFn = r#"
+                    #[once]
+                    #[once]
+                    fn my_fix() -> u32 {}
+                    "#
+                .ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_in!(
+                    format!("{:?}", error).to_lowercase(),
+                    "cannot use #[once] more than once"
+                );
+            }
+
+            #[test]
+            fn if_once_has_some_arguments() {
+                let mut item_fn: ItemFn = r#"
+                    #[once(42)]
+                    fn my_fix() -> u32 {}
+                    "#
+                .ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_in!(format!("{:?}", error).to_lowercase(), "invalid once syntax");
+            }
+
+            #[test]
+            fn if_teardown_is_defined_more_than_once() {
+                let mut item_fn: ItemFn = r#"
+                    #[teardown(|_| ())]
+                    #[teardown(|_| ())]
+                    fn my_fix() -> u32 {}
+                    "#
+                .ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_in!(
+                    format!("{:?}", error).to_lowercase(),
+                    "cannot use #[teardown] more than once"
+                );
+            }
+
+            #[test]
+            fn for_invalid_teardown_expression() {
+                let mut item_fn: ItemFn = r#"
+                    #[teardown(|_| ,)]
+                    fn my_fix() -> u32 {}
+                    "#
+                .ast();
+
+                let errors = FixtureInfo::default()
+                    .extend_with_function_attrs(&mut item_fn)
+                    .unwrap_err();
+
+                assert_eq!(1, errors.len());
+            }
+
+            #[test]
+            fn if_values_are_defined_more_than_once() {
+                let mut item_fn: ItemFn = r#"
+                    #[values(1, 2)]
+                    #[values(3)]
+                    fn my_fix(v: u32) -> u32 {}
+                    "#
+                .ast();
+
+                let error = FixtureInfo::default()
+                    .extend_with_function_attrs(&mut item_fn)
+                    .unwrap_err();
+
+                assert_in!(
+                    format!("{:?}", error).to_lowercase(),
+                    "cannot use #[values] more than once"
+                );
+            }
+
+            #[mytest::rstest]
+            #[case::no_arguments("#[values(1, 2)] fn my_fix() -> u32 {}", "at least one argument")]
+            #[case::empty_values("#[values()] fn my_fix(v: u32) -> u32 {}", "should not be empty")]
+            #[case::params_with_args(
+                "fn my_fix(#[params(42)] v: u32) -> u32 {}",
+                "invalid params syntax"
+            )]
+            fn for_invalid_parametrized_fixture(#[case] code: &str, #[case] message: &str) {
+                let mut item_fn: ItemFn = code.ast();
+
+                let error = FixtureInfo::default()
+                    .extend_with_function_attrs(&mut item_fn)
+                    .unwrap_err();
+
+                assert_in!(format!("{:?}", error).to_lowercase(), message);
+            }
+
             #[test]
             fn for_invalid_expressions() {
                 let mut item_fn: ItemFn = r#"
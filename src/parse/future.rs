@@ -1,17 +1,70 @@
 use quote::{format_ident, ToTokens};
-use syn::{parse_quote, visit_mut::VisitMut, FnArg, ItemFn, Lifetime};
+use syn::{parse_quote, visit_mut::VisitMut, Attribute, FnArg, Ident, ItemFn, Lifetime};
 
+use super::Attribute as InfoAttribute;
 use crate::{error::ErrorsVec, refident::MaybeIdent, utils::attr_is};
 
+/// The `#[future]` arguments that should be awaited before call the function: they keep
+/// their own type in the function's signature.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub(crate) struct AwaitedFutures {
+    /// The awaited arguments in the function's order
+    pub(crate) args: Vec<Ident>,
+    /// Await all arguments concurrently
+    pub(crate) join: bool,
+}
+
+impl AwaitedFutures {
+    const AWT_ATTR: &'static str = "awt";
+    const JOIN_ATTR: &'static str = "awt_join";
+
+    /// Store the awaited arguments as `awt(args)` or `awt_join(args)` info attribute.
+    pub(crate) fn to_attribute(&self) -> Option<InfoAttribute> {
+        if self.args.is_empty() {
+            return None;
+        }
+        let name = if self.join {
+            Self::JOIN_ATTR
+        } else {
+            Self::AWT_ATTR
+        };
+        Some(InfoAttribute::Tagged(
+            format_ident!("{}", name),
+            self.args.clone(),
+        ))
+    }
+
+    pub(crate) fn from_attributes<'a>(attributes: impl Iterator<Item = &'a InfoAttribute>) -> Self {
+        attributes
+            .filter_map(|a| match a {
+                InfoAttribute::Tagged(name, args) if name == Self::AWT_ATTR => Some((args, false)),
+                InfoAttribute::Tagged(name, args) if name == Self::JOIN_ATTR => Some((args, true)),
+                _ => None,
+            })
+            .fold(Self::default(), |mut awaited, (args, join)| {
+                awaited.args.extend(args.iter().cloned());
+                awaited.join |= join;
+                awaited
+            })
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct ReplaceFutureAttribute {
     lifetimes: Vec<Lifetime>,
+    is_async: bool,
+    awt_all: bool,
+    awaited: AwaitedFutures,
     errors: Vec<syn::Error>,
 }
 
 impl ReplaceFutureAttribute {
-    pub(crate) fn replace(item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let mut visitor = Self::default();
+    pub(crate) fn replace(item_fn: &mut ItemFn) -> Result<AwaitedFutures, ErrorsVec> {
+        let mut visitor = Self {
+            is_async: item_fn.sig.asyncness.is_some(),
+            ..Default::default()
+        };
+        visitor.extract_awt(item_fn);
         visitor.visit_item_fn_mut(item_fn);
         if !visitor.lifetimes.is_empty() {
             let mut generics = visitor
@@ -32,11 +85,71 @@ impl ReplaceFutureAttribute {
             };
         }
         if visitor.errors.is_empty() {
-            Ok(())
+            Ok(visitor.awaited)
         } else {
             Err(visitor.errors.into())
         }
     }
+
+    /// Remove the function's `#[awt]` or `#[awt(join)]` attribute
+    fn extract_awt(&mut self, item_fn: &mut ItemFn) {
+        let attrs = std::mem::take(&mut item_fn.attrs);
+        let (awts, attrs): (Vec<_>, Vec<_>) = attrs.into_iter().partition(|a| attr_is(a, "awt"));
+        item_fn.attrs = attrs;
+        for (i, awt) in awts.iter().enumerate() {
+            if i > 0 {
+                self.errors.push(syn::Error::new_spanned(
+                    awt.into_token_stream(),
+                    "Cannot use #[awt] more than once.",
+                ));
+            } else if !self.is_async {
+                self.errors.push(sync_awt_error(awt));
+            } else {
+                match Self::parse_awt_options(awt) {
+                    Ok(join) => {
+                        self.awt_all = true;
+                        self.awaited.join = join;
+                    }
+                    Err(e) => self.errors.push(e),
+                }
+            }
+        }
+    }
+
+    /// `#[awt]` or `#[awt(join)]`: return if the futures should be joined
+    fn parse_awt_options(awt: &Attribute) -> syn::Result<bool> {
+        if awt.tokens.is_empty() {
+            return Ok(false);
+        }
+        match awt.parse_args::<Ident>() {
+            Ok(option) if option == "join" => Ok(true),
+            _ => Err(syn::Error::new_spanned(
+                awt.into_token_stream(),
+                "Invalid awt syntax: should be #[awt] or #[awt(join)]",
+            )),
+        }
+    }
+
+    /// `#[future]` or `#[future(awt)]`: return if the argument should be awaited
+    fn parse_future_options(future: &Attribute) -> syn::Result<bool> {
+        if future.tokens.is_empty() {
+            return Ok(false);
+        }
+        match future.parse_args::<Ident>() {
+            Ok(option) if option == "awt" => Ok(true),
+            _ => Err(syn::Error::new_spanned(
+                future.into_token_stream(),
+                "Invalid future syntax: should be #[future] or #[future(awt)]",
+            )),
+        }
+    }
+}
+
+fn sync_awt_error(attr: &Attribute) -> syn::Error {
+    syn::Error::new_spanned(
+        attr.into_token_stream(),
+        "Cannot await futures in a sync function: #[awt] and #[future(awt)] need an async function.",
+    )
 }
 
 impl VisitMut for ReplaceFutureAttribute {
@@ -59,6 +172,17 @@ impl VisitMut for ReplaceFutureAttribute {
                     }));
                     return;
                 }
+                let awt = match Self::parse_future_options(&futures[0]) {
+                    Ok(true) if !self.is_async => {
+                        self.errors.push(sync_awt_error(&futures[0]));
+                        return;
+                    }
+                    Ok(awt) => awt || self.awt_all,
+                    Err(e) => {
+                        self.errors.push(e);
+                        return;
+                    }
+                };
                 let ty = &mut t.ty;
                 use syn::Type::*;
                 match ty.as_ref() {
@@ -83,8 +207,12 @@ impl VisitMut for ReplaceFutureAttribute {
                     }
                 }
 
-                t.ty = parse_quote! {
-                    impl std::future::Future<Output = #ty>
+                if awt {
+                    self.awaited.args.push(ident);
+                } else {
+                    t.ty = parse_quote! {
+                        impl std::future::Future<Output = #ty>
+                    }
                 }
             }
             FnArg::Receiver(_) => {}
@@ -164,10 +292,68 @@ mod should {
         assert_eq!(expected, item_fn)
     }
 
+    #[rstest]
+    #[case::awt_arg(
+        "async fn f(#[future(awt)] a: u32, #[future] b: u32) {}",
+        "async fn f(a: u32, b: impl std::future::Future<Output = u32>) {}",
+        &["a"],
+        false
+    )]
+    #[case::awt_fn(
+        "#[awt] async fn f(#[future] a: u32, b: u32, #[future] c: &u32) {}",
+        "async fn f<'_c>(a: u32, b: u32, c: &'_c u32) {}",
+        &["a", "c"],
+        false
+    )]
+    #[case::awt_join(
+        "#[awt(join)] async fn f(#[future] a: u32, #[future(awt)] b: u32) {}",
+        "async fn f(a: u32, b: u32) {}",
+        &["a", "b"],
+        true
+    )]
+    fn keep_type_of_awaited_arguments(
+        #[case] item_fn: &str,
+        #[case] expected: &str,
+        #[case] awaited: &[&str],
+        #[case] join: bool,
+    ) {
+        let mut item_fn: ItemFn = item_fn.ast();
+        let expected: ItemFn = expected.ast();
+
+        let result = ReplaceFutureAttribute::replace(&mut item_fn).unwrap();
+
+        assert_eq!(expected, item_fn);
+        assert_eq!(
+            AwaitedFutures {
+                args: awaited.iter().map(|a| ident(a)).collect(),
+                join
+            },
+            result
+        );
+    }
+
+    #[test]
+    fn store_awaited_futures_as_attribute() {
+        let awaited = AwaitedFutures {
+            args: vec![ident("a"), ident("b")],
+            join: true,
+        };
+
+        let attribute = awaited.to_attribute();
+
+        assert_eq!(awaited, AwaitedFutures::from_attributes(attribute.iter()));
+        assert!(AwaitedFutures::default().to_attribute().is_none());
+    }
+
     #[rstest]
     #[case::no_more_than_one("fn f(#[future] #[future] a: u32) {}", "more than once")]
     #[case::no_impl("fn f(#[future] a: impl AsRef<str>) {}", "generete impl Future")]
     #[case::no_slice("fn f(#[future] a: [i32]) {}", "generete impl Future")]
+    #[case::awt_in_sync_arg("fn f(#[future(awt)] a: u32) {}", "need an async function")]
+    #[case::awt_in_sync_fn("#[awt] fn f(#[future] a: u32) {}", "need an async function")]
+    #[case::invalid_future("async fn f(#[future(other)] a: u32) {}", "Invalid future syntax")]
+    #[case::invalid_awt("#[awt(other)] async fn f(#[future] a: u32) {}", "Invalid awt syntax")]
+    #[case::awt_more_than_once("#[awt] #[awt] async fn f() {}", "#[awt] more than once")]
     fn raise_error(#[case] item_fn: &str, #[case] message: &str) {
         let mut item_fn: ItemFn = item_fn.ast();
 
@@ -5,33 +5,52 @@ use syn::{
     punctuated::Punctuated,
     token,
     visit_mut::VisitMut,
-    FnArg, Ident, ItemFn, Token,
+    ExprPath, FnArg, Ident, ItemFn, Token,
 };
 
 use crate::{
     error::ErrorsVec,
     refident::{MaybeIdent, RefIdent},
-    utils::{attr_is, attr_starts_with},
+    resolver,
+    utils::{attr_is, attr_starts_with, fn_args_idents},
 };
 use fixture::{
     ArgumentValue, DefaultsFunctionExtractor, FixtureModifiers, FixturesFunctionExtractor,
 };
 use quote::ToTokens;
-use testcase::TestCase;
+use testcase::{CaseArgs, CasesTable, TestCase};
 
-use self::{expressions::Expressions, vlist::ValueList};
+use self::{
+    expressions::Expressions,
+    vlist::{NamedValues, ValueList},
+};
 
 // To use the macros this should be the first one module
 #[macro_use]
 pub(crate) mod macros;
 
+pub(crate) mod cases_from;
+pub(crate) mod combinations;
 pub(crate) mod expressions;
+pub(crate) mod files;
 pub(crate) mod fixture;
+pub(crate) mod names;
+pub(crate) mod params;
 pub(crate) mod rstest;
+pub(crate) mod runtime;
+pub(crate) mod suite;
 pub(crate) mod testcase;
+pub(crate) mod types;
 pub(crate) mod vlist;
 pub(crate) mod future;
 
+/// The crate's root: data files paths are relative to it.
+pub(crate) fn manifest_dir() -> std::path::PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(Into::into)
+        .unwrap_or_default()
+}
+
 pub(crate) trait ExtendWithFunctionAttrs {
     fn extend_with_function_attrs(
         &mut self,
@@ -58,6 +77,7 @@ pub(crate) enum Attribute {
     Attr(Ident),
     Tagged(Ident, Vec<Ident>),
     Type(Ident, syn::Type),
+    Expr(Ident, syn::Expr),
 }
 
 impl Parse for Attribute {
@@ -130,25 +150,153 @@ impl Parse for Positional {
     }
 }
 
+/// The fixture's partial injection values: the positional ones followed by the named ones
+/// (`name = value`).
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct FixtureArgs {
+    pub(crate) positional: Positional,
+    pub(crate) named: Vec<ArgumentValue>,
+}
+
+impl Parse for FixtureArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut positional = vec![];
+        let mut named: Vec<ArgumentValue> = vec![];
+        while !input.is_empty() {
+            if ArgumentValue::peek(input) {
+                let value: ArgumentValue = input.parse()?;
+                if named.iter().any(|v| v.name == value.name) {
+                    return Err(syn::Error::new(
+                        value.name.span(),
+                        format!("Duplicate value for '{}' fixture argument.", value.name),
+                    ));
+                }
+                named.push(value);
+            } else if let Some(value) = named.last() {
+                return Err(input.error(format!(
+                    "Positional values should precede the named ones: found a positional \
+                    value after '{}'",
+                    value.name
+                )));
+            } else {
+                positional.push(input.parse()?);
+            }
+            if input.is_empty() {
+                break;
+            }
+            let _: Token![,] = input.parse()?;
+        }
+        Ok(Self {
+            positional: Positional(positional),
+            named,
+        })
+    }
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub(crate) struct Fixture {
     pub(crate) name: Ident,
+    /// The fixture's path if it's not the argument's name
+    pub(crate) resolve: Option<ExprPath>,
     pub(crate) positional: Positional,
+    /// The values of the fixture's arguments given by name
+    pub(crate) named: Vec<ArgumentValue>,
+    /// Resolve the argument by the instance shared in the test (`#[shared]`)
+    pub(crate) shared: bool,
 }
 
 impl Fixture {
     pub(crate) fn new(name: Ident, positional: Positional) -> Self {
-        Self { name, positional }
+        Self {
+            name,
+            resolve: None,
+            positional,
+            named: vec![],
+            shared: false,
+        }
+    }
+
+    pub(crate) fn shared(self) -> Self {
+        Self {
+            shared: true,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_named(self, named: Vec<ArgumentValue>) -> Self {
+        Self { named, ..self }
+    }
+
+    pub(crate) fn with_resolve(self, resolve: ExprPath) -> Self {
+        Self {
+            resolve: Some(resolve),
+            ..self
+        }
+    }
+
+    /// The path of the fixture that resolve this argument
+    pub(crate) fn fixture_path(&self) -> ExprPath {
+        self.resolve
+            .clone()
+            .unwrap_or_else(|| resolver::fixtures::name_path(self.name.clone()))
+    }
+}
+
+/// Parse the path of a fixture: `path::to::name` or `<Type>::name` for an associated fixture.
+pub(crate) fn parse_fixture_path(input: ParseStream) -> syn::Result<ExprPath> {
+    if !input.peek(Token![<]) {
+        return Ok(ExprPath {
+            attrs: vec![],
+            qself: None,
+            path: input.call(syn::Path::parse_mod_style)?,
+        });
+    }
+    let path: ExprPath = input.parse()?;
+    match &path.qself {
+        Some(qself)
+            if qself.as_token.is_none()
+                && path.path.segments.len() == 1
+                && path.path.segments[0].arguments.is_empty() =>
+        {
+            Ok(path)
+        }
+        _ => Err(syn::Error::new_spanned(
+            path,
+            "An associated fixture should be referred by `<Type>::name`",
+        )),
     }
 }
 
 impl Parse for Fixture {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let name = input.parse()?;
-        let content;
-        let _ = syn::parenthesized!(content in input);
-        let positional = content.parse()?;
-        Ok(Self::new(name, positional))
+        let resolve = input.call(parse_fixture_path)?;
+        if !input.peek(token::Paren) && !input.peek(Token![as]) {
+            return Err(input.error("Fixture need arguments or 'as new_name' format"));
+        }
+        let FixtureArgs { positional, named } = if input.peek(token::Paren) {
+            let content;
+            let _ = syn::parenthesized!(content in input);
+            content.parse()?
+        } else {
+            FixtureArgs {
+                positional: Positional(vec![]),
+                named: vec![],
+            }
+        };
+        if input.peek(Token![as]) {
+            let _: Token![as] = input.parse()?;
+            Ok(Self::new(input.parse()?, positional)
+                .with_named(named)
+                .with_resolve(resolve))
+        } else {
+            match resolve.path.get_ident().filter(|_| resolve.qself.is_none()) {
+                Some(name) => Ok(Self::new(name.clone(), positional).with_named(named)),
+                None => Err(syn::Error::new_spanned(
+                    resolve,
+                    "A fixture path should be renamed by 'as new_name'",
+                )),
+            }
+        }
     }
 }
 
@@ -194,6 +342,32 @@ pub(crate) fn extract_default_return_type(
     default_type_extractor.take()
 }
 
+pub(crate) fn extract_once(item_fn: &mut ItemFn) -> Result<Option<Ident>, ErrorsVec> {
+    let mut extractor = FlagAttributeFunctionExtractor::new(FixtureModifiers::ONCE_ATTR);
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+pub(crate) fn extract_shared(item_fn: &mut ItemFn) -> Result<Option<Ident>, ErrorsVec> {
+    let mut extractor = FlagAttributeFunctionExtractor::new(FixtureModifiers::SHARED_ATTR);
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+pub(crate) fn extract_teardown(
+    item_fn: &mut ItemFn,
+) -> Result<Option<(Ident, syn::Expr)>, ErrorsVec> {
+    let mut extractor = TearDownFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+pub(crate) fn extract_fixture_values(item_fn: &mut ItemFn) -> Result<Option<ValueList>, ErrorsVec> {
+    let mut extractor = FixtureValuesFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
 pub(crate) fn extract_partials_return_type(
     item_fn: &mut ItemFn,
 ) -> Result<Vec<(usize, syn::Type)>, ErrorsVec> {
@@ -332,6 +506,167 @@ impl VisitMut for PartialsTypeFunctionExtractor {
     }
 }
 
+/// Simple struct used to visit function attributes and extract a flag attribute (like
+/// `once`) if any
+struct FlagAttributeFunctionExtractor(&'static str, Result<Option<Ident>, ErrorsVec>);
+
+impl FlagAttributeFunctionExtractor {
+    fn new(name: &'static str) -> Self {
+        Self(name, Ok(None))
+    }
+
+    fn take(self) -> Result<Option<Ident>, ErrorsVec> {
+        self.1
+    }
+}
+
+impl VisitMut for FlagAttributeFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let name = self.0;
+        let attrs = std::mem::take(&mut node.attrs);
+        let (flags, remain): (Vec<_>, Vec<_>) =
+            attrs.into_iter().partition(|attr| attr_is(&attr, name));
+
+        node.attrs = remain;
+        let mut flags = flags.into_iter();
+        let mut data = None;
+        let mut errors = ErrorsVec::default();
+        match flags.nth(0) {
+            Some(attr) if attr.tokens.is_empty() => data = attr.path.get_ident().cloned(),
+            Some(attr) => errors.push(syn::Error::new_spanned(
+                attr,
+                format!("Invalid {} syntax: should be just #[{}]", name, name),
+            )),
+            None => {}
+        };
+        errors.extend(flags.map(|a| {
+            syn::Error::new_spanned(a, format!("You cannot use #[{}] more than once", name))
+        }));
+        self.1 = if errors.len() > 0 {
+            Err(errors)
+        } else {
+            Ok(data)
+        };
+
+        syn::visit_mut::visit_item_fn_mut(self, node);
+    }
+}
+
+/// Simple struct used to visit function attributes and extract the `teardown` attribute
+/// if any
+struct TearDownFunctionExtractor(Result<Option<(Ident, syn::Expr)>, ErrorsVec>);
+
+impl TearDownFunctionExtractor {
+    fn take(self) -> Result<Option<(Ident, syn::Expr)>, ErrorsVec> {
+        self.0
+    }
+}
+
+impl Default for TearDownFunctionExtractor {
+    fn default() -> Self {
+        Self(Ok(None))
+    }
+}
+
+impl VisitMut for TearDownFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (teardowns, remain): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| attr_is(&attr, FixtureModifiers::TEARDOWN_ATTR));
+
+        node.attrs = remain;
+        let mut teardowns = teardowns.into_iter();
+        let mut data = None;
+        let mut errors = ErrorsVec::default();
+        match teardowns.nth(0).map(|attr| {
+            (
+                attr.path.get_ident().cloned(),
+                attr.parse_args::<syn::Expr>(),
+            )
+        }) {
+            Some((Some(name), Ok(e))) => data = Some((name, e)),
+            Some((_, Err(e))) => errors.push(e),
+            _ => {}
+        };
+        errors.extend(
+            teardowns
+                .map(|a| syn::Error::new_spanned(a, "You cannot use #[teardown] more than once")),
+        );
+        self.0 = if errors.len() > 0 {
+            Err(errors)
+        } else {
+            Ok(data)
+        };
+
+        syn::visit_mut::visit_item_fn_mut(self, node);
+    }
+}
+
+/// Simple struct used to visit function attributes and extract the fixture's `values`
+/// attribute if any: the values are assigned to the first fixture's argument
+struct FixtureValuesFunctionExtractor(Result<Option<ValueList>, ErrorsVec>);
+
+impl FixtureValuesFunctionExtractor {
+    fn take(self) -> Result<Option<ValueList>, ErrorsVec> {
+        self.0
+    }
+}
+
+impl Default for FixtureValuesFunctionExtractor {
+    fn default() -> Self {
+        Self(Ok(None))
+    }
+}
+
+impl VisitMut for FixtureValuesFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (values, remain): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| attr_is(&attr, FixtureModifiers::VALUES_ATTR));
+
+        node.attrs = remain;
+        let mut values = values.into_iter();
+        let mut data = None;
+        let mut errors = ErrorsVec::default();
+        match values.nth(0) {
+            Some(attr) => match (
+                fn_args_idents(node).next(),
+                attr.parse_args::<Expressions>(),
+            ) {
+                (_, Err(e)) => errors.push(e),
+                (None, _) => errors.push(syn::Error::new_spanned(
+                    attr,
+                    "Fixture with #[values] should have at least one argument to assign the values to",
+                )),
+                (Some(arg), Ok(v)) => {
+                    let values = v.take();
+                    if values.is_empty() {
+                        errors.push(syn::Error::new_spanned(
+                            attr,
+                            "Values list should not be empty",
+                        ))
+                    } else {
+                        data = Some(ValueList::new(arg.clone(), values))
+                    }
+                }
+            },
+            None => {}
+        };
+        errors.extend(
+            values.map(|a| syn::Error::new_spanned(a, "You cannot use #[values] more than once")),
+        );
+        self.0 = if errors.len() > 0 {
+            Err(errors)
+        } else {
+            Ok(data)
+        };
+
+        syn::visit_mut::visit_item_fn_mut(self, node);
+    }
+}
+
 /// Simple struct used to visit function attributes and extract case arguments and
 /// eventualy parsing errors
 #[derive(Default)]
@@ -373,13 +708,19 @@ impl VisitMut for CasesFunctionExtractor {
         let case: syn::PathSegment = parse_quote! { case };
         for attr in attrs.into_iter() {
             if attr_starts_with(&attr, &case) {
-                match attr.parse_args::<Expressions>() {
-                    Ok(expressions) => {
+                match attr.parse_args::<CaseArgs>() {
+                    Ok(CaseArgs {
+                        args,
+                        named,
+                        expected,
+                    }) => {
                         let description = attr.path.segments.into_iter().nth(1).map(|p| p.ident);
                         self.0.push(TestCase {
-                            args: expressions.into(),
+                            args,
+                            named,
                             attrs: std::mem::take(&mut attrs_buffer),
                             description,
+                            expected,
                         });
                     }
                     Err(err) => self.1.push(err),
@@ -404,6 +745,37 @@ pub(crate) fn extract_cases(item_fn: &mut ItemFn) -> Result<Vec<TestCase>, Error
     }
 }
 
+/// Simple struct used to visit function attributes and extract the cases' tables and
+/// eventualy parsing errors
+#[derive(Default)]
+struct CasesTablesFunctionExtractor(Vec<CasesTable>, Vec<syn::Error>);
+
+impl VisitMut for CasesTablesFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (tables, remain): (Vec<_>, Vec<_>) =
+            attrs.into_iter().partition(|attr| attr_is(attr, "cases"));
+        node.attrs = remain;
+        for attr in tables {
+            match attr.parse_args::<CasesTable>() {
+                Ok(table) => self.0.push(table),
+                Err(err) => self.1.push(err),
+            }
+        }
+    }
+}
+
+pub(crate) fn extract_cases_tables(item_fn: &mut ItemFn) -> Result<Vec<CasesTable>, ErrorsVec> {
+    let mut tables_extractor = CasesTablesFunctionExtractor::default();
+    tables_extractor.visit_item_fn_mut(item_fn);
+
+    if tables_extractor.1.len() > 0 {
+        Err(tables_extractor.1.into())
+    } else {
+        Ok(tables_extractor.0)
+    }
+}
+
 /// Simple struct used to visit function attributes and extract value list and
 /// eventualy parsing errors
 #[derive(Default)]
@@ -415,10 +787,8 @@ impl VisitMut for ValueListFunctionExtractor {
             node,
             |a| attr_is(a, "values"),
             |a, name| {
-                a.parse_args::<Expressions>().map(|v| ValueList {
-                    arg: name.clone(),
-                    values: v.take(),
-                })
+                a.parse_args::<NamedValues>()
+                    .and_then(|v| ValueList::named(name.clone(), v))
             },
         ) {
             match r {
@@ -426,6 +796,11 @@ impl VisitMut for ValueListFunctionExtractor {
                 Err(err) => self.1.push(err),
             }
         }
+        match files::extract_files(node) {
+            Ok(Some(vlist)) => self.0.push(vlist),
+            Ok(None) => {}
+            Err(mut errors) => self.1.append(&mut errors),
+        }
 
         syn::visit_mut::visit_fn_arg_mut(self, node);
     }
@@ -442,6 +817,90 @@ pub(crate) fn extract_value_list(item_fn: &mut ItemFn) -> Result<Vec<ValueList>,
     }
 }
 
+/// Simple struct used to visit function attributes and extract the arguments marked
+/// by `#[params]` and eventualy parsing errors
+#[derive(Default)]
+struct ParamsFunctionExtractor(Vec<Ident>, Vec<syn::Error>);
+
+impl VisitMut for ParamsFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        for r in extract_argument_attrs(
+            node,
+            |a| attr_is(a, "params"),
+            |a, name| {
+                if a.tokens.is_empty() {
+                    Ok(name.clone())
+                } else {
+                    Err(syn::Error::new_spanned(
+                        a,
+                        "Invalid params syntax: should be just #[params]",
+                    ))
+                }
+            },
+        ) {
+            match r {
+                Ok(value) => self.0.push(value),
+                Err(err) => self.1.push(err),
+            }
+        }
+
+        syn::visit_mut::visit_fn_arg_mut(self, node);
+    }
+}
+
+pub(crate) fn extract_params(item_fn: &mut ItemFn) -> Result<Vec<Ident>, ErrorsVec> {
+    let mut params_extractor = ParamsFunctionExtractor::default();
+    params_extractor.visit_item_fn_mut(item_fn);
+
+    if params_extractor.1.len() > 0 {
+        Err(params_extractor.1.into())
+    } else {
+        Ok(params_extractor.0)
+    }
+}
+
+/// Simple struct used to visit function attributes and extract the arguments marked
+/// by `#[zip]` and eventualy parsing errors
+#[derive(Default)]
+struct ZipFunctionExtractor(Vec<Ident>, Vec<syn::Error>);
+
+impl VisitMut for ZipFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        for r in extract_argument_attrs(
+            node,
+            |a| attr_is(a, "zip"),
+            |a, name| {
+                if a.tokens.is_empty() {
+                    Ok(name.clone())
+                } else {
+                    Err(syn::Error::new_spanned(
+                        a,
+                        "Invalid zip syntax: should be just #[zip]",
+                    ))
+                }
+            },
+        ) {
+            match r {
+                Ok(value) => self.0.push(value),
+                Err(err) => self.1.push(err),
+            }
+        }
+
+        syn::visit_mut::visit_fn_arg_mut(self, node);
+    }
+}
+
+pub(crate) fn extract_zipped(item_fn: &mut ItemFn) -> Result<Vec<Ident>, ErrorsVec> {
+    let mut zip_extractor = ZipFunctionExtractor::default();
+    zip_extractor.visit_item_fn_mut(item_fn);
+
+    if zip_extractor.1.len() > 0 {
+        Err(zip_extractor.1.into())
+    } else {
+        Ok(zip_extractor.0)
+    }
+}
+
 /// Simple struct used to visit function args attributes to extract the
 /// excluded ones and eventualy parsing errors
 struct ExcludedTraceAttributesFunctionExtractor(Result<Vec<Ident>, ErrorsVec>);
@@ -563,4 +1022,56 @@ mod should {
             assert_eq!(expected, attributes);
         }
     }
+
+    mod parse_fixture {
+        use super::assert_eq;
+        use super::*;
+        use mytest::rstest;
+        use rstest_test::assert_in;
+
+        #[test]
+        fn with_positional_and_named_values() {
+            let parsed: Fixture = r#"user("Bob", age = 30, admin = true)"#.ast();
+
+            assert_eq!(
+                fixture("user", vec![r#""Bob""#])
+                    .with_named(vec![arg_value("age", "30"), arg_value("admin", "true")]),
+                parsed
+            );
+        }
+
+        #[test]
+        fn renamed_with_named_values() {
+            let parsed: Fixture = r#"users::user(age = 30) as admin"#.ast();
+
+            assert_eq!(
+                fixture("admin", vec![])
+                    .with_named(vec![arg_value("age", "30")])
+                    .with_resolve(parse_quote! { users::user }),
+                parsed
+            );
+        }
+
+        #[test]
+        fn not_take_comparisons_as_named_values() {
+            let parsed: Fixture = r#"user(age == 30)"#.ast();
+
+            assert_eq!(fixture("user", vec!["age == 30"]), parsed);
+        }
+
+        #[rstest]
+        #[case::positional_after_named(
+            "user(age = 30, 42)",
+            "Positional values should precede the named ones: found a positional value after 'age'"
+        )]
+        #[case::duplicate(
+            "user(age = 30, age = 42)",
+            "Duplicate value for 'age' fixture argument."
+        )]
+        fn raise_error(#[case] code: &str, #[case] message: &str) {
+            let error = syn::parse_str::<Fixture>(code).unwrap_err();
+
+            assert_in!(error.to_string(), message);
+        }
+    }
 }
@@ -0,0 +1,201 @@
+/// How the generated tests are named: `names = values` appends a rendering of the simple
+/// values to the names and `name = "{arg}_..."` is a template for the cases' names.
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    Ident, LitStr, Token,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum NamesDefinition {
+    /// `names = values`
+    Values(Ident),
+    /// `name = "template"`
+    Template(LitStr),
+}
+
+impl NamesDefinition {
+    pub(crate) const NAMES_KEYWORD: &'static str = "names";
+    pub(crate) const NAME_KEYWORD: &'static str = "name";
+    const VALUES: &'static str = "values";
+
+    pub(crate) fn is_names_definition(input: ParseStream) -> bool {
+        input.peek2(Token![=])
+            && input
+                .fork()
+                .parse::<Ident>()
+                .map(|ident| ident == Self::NAMES_KEYWORD || ident == Self::NAME_KEYWORD)
+                .unwrap_or_default()
+    }
+
+    pub(crate) fn keyword(&self) -> &'static str {
+        match self {
+            NamesDefinition::Values(_) => Self::NAMES_KEYWORD,
+            NamesDefinition::Template(_) => Self::NAME_KEYWORD,
+        }
+    }
+}
+
+impl Parse for NamesDefinition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let keyword: Ident = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        if keyword == Self::NAMES_KEYWORD {
+            let mode: Ident = input.parse()?;
+            if mode != Self::VALUES {
+                return Err(syn::Error::new(
+                    mode.span(),
+                    format!("Unknown names mode '{}': should be values", mode),
+                ));
+            }
+            Ok(NamesDefinition::Values(mode))
+        } else if keyword == Self::NAME_KEYWORD {
+            let template: LitStr = input.parse()?;
+            NameTemplate::parse_template(&template)?;
+            Ok(NamesDefinition::Template(template))
+        } else {
+            Err(syn::Error::new(keyword.span(), "Expected names or name"))
+        }
+    }
+}
+
+impl ToTokens for NamesDefinition {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let keyword = format_ident!("{}", self.keyword());
+        tokens.extend(match self {
+            NamesDefinition::Values(mode) => quote! { #keyword = #mode },
+            NamesDefinition::Template(template) => quote! { #keyword = #template },
+        })
+    }
+}
+
+/// A piece of a name template: some text or an argument's placeholder.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum TemplatePart {
+    Text(String),
+    Arg(String),
+}
+
+/// The parsed `name = "..."` template: `{arg}` is replaced by the argument's value
+/// and `{{`, `}}` are the escaped braces.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct NameTemplate {
+    pub(crate) template: LitStr,
+    pub(crate) parts: Vec<TemplatePart>,
+}
+
+impl NameTemplate {
+    pub(crate) fn parse_template(template: &LitStr) -> Result<Self> {
+        let value = template.value();
+        let mut parts = vec![];
+        let mut text = String::new();
+        let mut chars = value.chars().peekable();
+        let error = |msg: &str| syn::Error::new(template.span(), msg);
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{')
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}')
+                }
+                '{' => {
+                    let arg: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    let arg = arg.trim();
+                    if syn::parse_str::<Ident>(arg).is_err() {
+                        return Err(error(&format!(
+                            "Invalid placeholder '{{{}}}': should be an argument's name",
+                            arg
+                        )));
+                    }
+                    if !text.is_empty() {
+                        parts.push(TemplatePart::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(TemplatePart::Arg(arg.to_owned()))
+                }
+                '}' => return Err(error("Unmatched '}' in name template: use '}}'")),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(TemplatePart::Text(text));
+        }
+        Ok(Self {
+            template: template.clone(),
+            parts,
+        })
+    }
+
+    pub(crate) fn args(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|p| match p {
+            TemplatePart::Arg(arg) => Some(arg.as_str()),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use mytest::rstest;
+    use rstest_test::assert_in;
+
+    fn template(s: &str) -> NameTemplate {
+        NameTemplate::parse_template(&LitStr::new(s, proc_macro2::Span::call_site())).unwrap()
+    }
+
+    #[test]
+    fn parse_names_values() {
+        let names: NamesDefinition = "names = values".ast();
+
+        assert!(matches!(names, NamesDefinition::Values(_)));
+    }
+
+    #[test]
+    fn parse_name_template() {
+        let names: NamesDefinition = r#"name = "{input}_yields_{expected}""#.ast();
+
+        assert_eq!(
+            NamesDefinition::Template(LitStr::new(
+                "{input}_yields_{expected}",
+                proc_macro2::Span::call_site()
+            )),
+            names
+        );
+    }
+
+    #[test]
+    fn split_template_in_parts() {
+        let template = template("{ input }_yields_{expected}{{x}}");
+
+        assert_eq!(
+            vec![
+                TemplatePart::Arg("input".to_owned()),
+                TemplatePart::Text("_yields_".to_owned()),
+                TemplatePart::Arg("expected".to_owned()),
+                TemplatePart::Text("{x}".to_owned()),
+            ],
+            template.parts
+        );
+        assert_eq!(
+            vec!["input", "expected"],
+            template.args().collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::unknown_mode("names = labels", "Unknown names mode 'labels': should be values")]
+    #[case::no_literal("name = input", "expected string literal")]
+    #[case::invalid_placeholder(r#"name = "{1a}""#, "Invalid placeholder '{1a}'")]
+    #[case::empty_placeholder(r#"name = "a_{}""#, "Invalid placeholder '{}'")]
+    #[case::unmatched_brace(r#"name = "a}""#, "Unmatched '}' in name template")]
+    fn raise_error(#[case] input: &str, #[case] expected: &str) {
+        let error = syn::parse_str::<NamesDefinition>(input).unwrap_err();
+
+        assert_in!(error.to_string(), expected);
+    }
+}
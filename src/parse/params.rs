@@ -0,0 +1,210 @@
+/// Parametrized fixtures' handshake data: every parametrized fixture defines a
+/// `macro_rules!` that append its dimensions to the collected ones and pass them to the
+/// next fixture's macro or, at the end, to the `__rstest_params!` procedural macro.
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    Ident, Token,
+};
+
+/// A parametrized fixture dimension: the name of the fixture and the labels of its
+/// values (`_` if the value has no label).
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct ParamsDim {
+    pub(crate) name: Ident,
+    pub(crate) labels: Vec<Option<Ident>>,
+}
+
+impl ParamsDim {
+    pub(crate) fn new(name: Ident, labels: Vec<Option<Ident>>) -> Self {
+        Self { name, labels }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.labels.len()
+    }
+}
+
+impl Parse for ParamsDim {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let _ = syn::parenthesized!(content in input);
+        let name = content.parse()?;
+        let labels_content;
+        let _ = syn::bracketed!(labels_content in content);
+        let mut labels = vec![];
+        while !labels_content.is_empty() {
+            if labels_content.peek(Token![_]) {
+                let _: Token![_] = labels_content.parse()?;
+                labels.push(None);
+            } else {
+                labels.push(Some(labels_content.parse()?));
+            }
+        }
+        Ok(Self::new(name, labels))
+    }
+}
+
+impl ToTokens for ParamsDim {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.name;
+        let labels = self.labels.iter().map(|l| match l {
+            Some(label) => quote! { #label },
+            None => quote! { _ },
+        });
+        tokens.extend(quote! { (#name [#(#labels)*]) })
+    }
+}
+
+/// All the dimensions collected from a fixture's macro.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub(crate) struct ParamsGroup(pub(crate) Vec<ParamsDim>);
+
+impl Parse for ParamsGroup {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        let _ = syn::parenthesized!(content in input);
+        let mut dims = vec![];
+        while !content.is_empty() {
+            dims.push(content.parse()?);
+        }
+        Ok(Self(dims))
+    }
+}
+
+impl ToTokens for ParamsGroup {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let dims = &self.0;
+        tokens.extend(quote! { (#(#dims)*) })
+    }
+}
+
+/// What should be done when all parametrized fixtures' dimensions are collected.
+#[derive(Debug)]
+pub(crate) enum ParamsTarget {
+    /// Render the test: `test { <rstest attribute args> } <test function>`
+    Test {
+        args: TokenStream,
+        item: TokenStream,
+    },
+    /// Define the fixture's macro: `fixture <visibility> <name> <own dims>`
+    Fixture {
+        vis: syn::Visibility,
+        name: Ident,
+        dims: Vec<ParamsDim>,
+    },
+}
+
+/// The `__rstest_params!` input: `[<groups>] { <target> }`
+#[derive(Debug)]
+pub(crate) struct ParamsCallback {
+    pub(crate) groups: Vec<ParamsGroup>,
+    pub(crate) target: ParamsTarget,
+}
+
+impl Parse for ParamsCallback {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let groups_content;
+        let _ = syn::bracketed!(groups_content in input);
+        let mut groups = vec![];
+        while !groups_content.is_empty() {
+            groups.push(groups_content.parse()?);
+        }
+        let content;
+        let _ = syn::braced!(content in input);
+        let kind: Ident = content.parse()?;
+        let target = if kind == "test" {
+            let args;
+            let _ = syn::braced!(args in content);
+            ParamsTarget::Test {
+                args: args.parse()?,
+                item: content.parse()?,
+            }
+        } else if kind == "fixture" {
+            let vis = content.parse()?;
+            let name = content.parse()?;
+            let mut dims = vec![];
+            while !content.is_empty() {
+                dims.push(content.parse()?);
+            }
+            ParamsTarget::Fixture { vis, name, dims }
+        } else {
+            return Err(syn::Error::new(kind.span(), "Expected test or fixture"));
+        };
+        Ok(Self { groups, target })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    #[test]
+    fn parse_dimension() {
+        let dim: ParamsDim = "(backend [Sqlite _ Postgres])".ast();
+
+        assert_eq!(
+            ParamsDim::new(
+                ident("backend"),
+                vec![Some(ident("Sqlite")), None, Some(ident("Postgres"))]
+            ),
+            dim
+        );
+    }
+
+    #[test]
+    fn render_and_parse_the_same_group() {
+        let group = ParamsGroup(vec![
+            ParamsDim::new(ident("backend"), vec![Some(ident("Sqlite")), None]),
+            ParamsDim::new(ident("engine"), vec![None]),
+        ]);
+
+        let parsed: ParamsGroup = group.to_token_stream().ast();
+
+        assert_eq!(group, parsed);
+    }
+
+    #[test]
+    fn parse_test_callback() {
+        let callback: ParamsCallback = r#"
+            [((backend [Sqlite Postgres])) ((engine [_ _]))]
+            { test { case(42) } fn test(#[params] backend: Backend) {} }
+        "#
+        .ast();
+
+        assert_eq!(2, callback.groups.len());
+        match callback.target {
+            ParamsTarget::Test { args, item } => {
+                assert_eq!("case (42)", args.to_string());
+                assert!(item.to_string().starts_with("fn test"));
+            }
+            _ => panic!("Should be a test"),
+        }
+    }
+
+    #[test]
+    fn parse_fixture_callback() {
+        let callback: ParamsCallback = r#"
+            [((backend [Sqlite Postgres]))]
+            { fixture pub(crate) db (db [A B]) }
+        "#
+        .ast();
+
+        match callback.target {
+            ParamsTarget::Fixture { vis, name, dims } => {
+                assert_eq!(vis, syn::parse_quote! { pub(crate) });
+                assert_eq!(ident("db"), name);
+                assert_eq!(
+                    vec![ParamsDim::new(
+                        ident("db"),
+                        vec![Some(ident("A")), Some(ident("B"))]
+                    )],
+                    dims
+                );
+            }
+            _ => panic!("Should be a fixture"),
+        }
+    }
+}
@@ -1,21 +1,33 @@
 use syn::{
     parse::{Parse, ParseStream},
-    Ident, ItemFn, Token,
+    parse_quote, Ident, ItemFn, Token,
 };
 
-use super::testcase::TestCase;
+use super::testcase::{CasesTable, TestCase};
 use super::{
-    extract_case_args, extract_cases, extract_fixtures, extract_excluded_trace, extract_value_list,
-    parse_vector_trailing_till_double_comma, Attribute, Attributes, ExtendWithFunctionAttrs,
-    Fixture,
+    cases_from::{extract_cases_from, CasesFrom},
+    combinations::{
+        extract_combinations_filters, CombinationsDefinition, CombinationsFilter, CombinationsMode,
+    },
+    extract_case_args, extract_cases, extract_cases_tables, extract_defaults,
+    extract_excluded_trace, extract_fixtures, extract_params, extract_value_list, extract_zipped,
+    manifest_dir, parse_vector_trailing_till_double_comma, Attribute, Attributes,
+    ExtendWithFunctionAttrs, Fixture,
+};
+use crate::parse::{
+    fixture::ArgumentValue,
+    future::AwaitedFutures,
+    names::{NameTemplate, NamesDefinition},
+    runtime::{Runtime, RuntimeDefinition},
+    types::{extract_types, TypeList},
+    vlist::ValueList,
 };
-use crate::parse::vlist::ValueList;
 use crate::{
     error::ErrorsVec,
     refident::{MaybeIdent, RefIdent},
 };
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, ToTokens};
+use quote::{format_ident, quote, ToTokens};
 
 #[derive(PartialEq, Debug, Default)]
 pub(crate) struct RsTestInfo {
@@ -28,25 +40,94 @@ impl Parse for RsTestInfo {
         Ok(if input.is_empty() {
             Default::default()
         } else {
-            Self {
-                data: input.parse()?,
-                attributes: input
-                    .parse::<Token![::]>()
-                    .or_else(|_| Ok(Default::default()))
-                    .and_then(|_| input.parse())?,
+            let mut data: RsTestData = input.parse()?;
+            let mut attributes: RsTestAttributes = input
+                .parse::<Token![::]>()
+                .or_else(|_| Ok(Default::default()))
+                .and_then(|_| input.parse())?;
+            let mut runtimes = data.take_runtimes().into_iter();
+            if let Some(runtime) = runtimes.next() {
+                attributes.set_runtime(runtime);
+            }
+            if let Some(RuntimeDefinition(name)) = runtimes.next() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "You cannot define the runtime more than once",
+                ));
+            }
+            let mut combinations = data.take_combinations().into_iter();
+            if let Some(combinations) = combinations.next() {
+                attributes.set_combinations(combinations);
+            }
+            if let Some(combinations) = combinations.next() {
+                return Err(syn::Error::new_spanned(
+                    combinations,
+                    "You cannot define the combinations more than once",
+                ));
             }
+            for names in data.take_names() {
+                if attributes.has_names(names.keyword()) {
+                    return Err(syn::Error::new_spanned(
+                        names,
+                        "You cannot define the names more than once",
+                    ));
+                }
+                attributes.set_names(names);
+            }
+            Self { data, attributes }
         })
     }
 }
 
 impl ExtendWithFunctionAttrs for RsTestInfo {
     fn extend_with_function_attrs(&mut self, item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let (_, excluded) = merge_errors!(
+        // `#[cases_from]` and `#[cases]` should be removed before extract the cases
+        let composed_tuple!(cases_from, tables, _inner, excluded, params, zipped) = merge_errors!(
+            extract_cases_from(item_fn),
+            extract_cases_tables(item_fn),
             self.data.extend_with_function_attrs(item_fn),
-            extract_excluded_trace(item_fn)
+            extract_excluded_trace(item_fn),
+            extract_params(item_fn),
+            extract_zipped(item_fn)
         )?;
         self.attributes.add_notraces(excluded);
-        Ok(())
+        self.attributes.add_params(params);
+        self.attributes.add_zipped(zipped);
+        self.add_cases(tables, cases_from)
+    }
+}
+
+impl RsTestInfo {
+    /// Add the cases defined by tables and data files: they need the case arguments.
+    fn add_cases(
+        &mut self,
+        tables: Vec<CasesTable>,
+        cases_from: Vec<CasesFrom>,
+    ) -> Result<(), ErrorsVec> {
+        let case_args = self.data.case_args().cloned().collect::<Vec<_>>();
+        let base_dir = manifest_dir();
+        let mut errors = vec![];
+        for table in tables {
+            match table.test_cases(&case_args) {
+                Ok(cases) => self.data.items.extend(cases.into_iter().map(Into::into)),
+                Err(err) => errors.push(err),
+            }
+        }
+        for source in cases_from {
+            match source.load(&case_args, &base_dir) {
+                Ok(cases) => {
+                    self.data.items.extend(cases.into_iter().map(Into::into));
+                    self.attributes
+                        .add_tracked_file(&base_dir.join(source.path.value()), &source.path);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.into())
+        }
     }
 }
 
@@ -91,6 +172,14 @@ impl RsTestData {
         self.fixtures().next().is_some()
     }
 
+    /// The `#[default(expr)]` values of the test's arguments.
+    pub(crate) fn values(&self) -> impl Iterator<Item = &ArgumentValue> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::ArgumentValue(ref value) => Some(value),
+            _ => None,
+        })
+    }
+
     pub(crate) fn list_values(&self) -> impl Iterator<Item = &ValueList> {
         self.items.iter().filter_map(|mv| match mv {
             RsTestItem::ValueList(ref value_list) => Some(value_list),
@@ -101,6 +190,72 @@ impl RsTestData {
     pub(crate) fn has_list_values(&self) -> bool {
         self.list_values().next().is_some()
     }
+
+    /// The `#[types(T = [...])]` lists of the test's type parameters.
+    pub(crate) fn type_lists(&self) -> impl Iterator<Item = &TypeList> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::TypeList(ref types) => Some(types),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn has_type_lists(&self) -> bool {
+        self.type_lists().next().is_some()
+    }
+
+    /// The `#[exclude]` and `#[only]` filters of the values lists' combinations.
+    pub(crate) fn filters(&self) -> impl Iterator<Item = &CombinationsFilter> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::Filter(ref filter) => Some(filter),
+            _ => None,
+        })
+    }
+
+    /// Remove the `runtime = <name>` definitions: they are test's attributes.
+    fn take_runtimes(&mut self) -> Vec<RuntimeDefinition> {
+        let (runtimes, items) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition::<Vec<_>, _>(|it| matches!(it, RsTestItem::Runtime(_)));
+        self.items = items;
+        runtimes
+            .into_iter()
+            .filter_map(|it| match it {
+                RsTestItem::Runtime(runtime) => Some(runtime),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Remove the `combinations = <mode>` definitions: they are test's attributes.
+    fn take_combinations(&mut self) -> Vec<CombinationsDefinition> {
+        let (combinations, items) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition::<Vec<_>, _>(|it| matches!(it, RsTestItem::Combinations(_)));
+        self.items = items;
+        combinations
+            .into_iter()
+            .filter_map(|it| match it {
+                RsTestItem::Combinations(combinations) => Some(combinations),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Remove the `names = values` and `name = "..."` definitions: they are test's
+    /// attributes.
+    fn take_names(&mut self) -> Vec<NamesDefinition> {
+        let (names, items) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition::<Vec<_>, _>(|it| matches!(it, RsTestItem::Names(_)));
+        self.items = items;
+        names
+            .into_iter()
+            .filter_map(|it| match it {
+                RsTestItem::Names(names) => Some(names),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl Parse for RsTestData {
@@ -117,17 +272,27 @@ impl Parse for RsTestData {
 
 impl ExtendWithFunctionAttrs for RsTestData {
     fn extend_with_function_attrs(&mut self, item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let composed_tuple!(fixtures, case_args, cases, value_list) = merge_errors!(
+        // `#[types]` should be removed before extract the cases: otherwise it would be taken
+        // as an attribute of the following case
+        let composed_tuple!(types, fixtures, defaults, case_args, cases, value_list, filters) = merge_errors!(
+            extract_types(item_fn),
             extract_fixtures(item_fn),
+            extract_defaults(item_fn),
             extract_case_args(item_fn),
             extract_cases(item_fn),
-            extract_value_list(item_fn)
+            extract_value_list(item_fn),
+            extract_combinations_filters(item_fn)
         )?;
 
         self.items.extend(fixtures.into_iter().map(|f| f.into()));
+        self.items.extend(defaults.into_iter().map(|f| f.into()));
         self.items.extend(case_args.into_iter().map(|f| f.into()));
         self.items.extend(cases.into_iter().map(|f| f.into()));
         self.items.extend(value_list.into_iter().map(|f| f.into()));
+        self.items
+            .extend(filters.into_iter().map(RsTestItem::Filter));
+        self.items
+            .extend(types.into_iter().map(RsTestItem::TypeList));
         Ok(())
     }
 }
@@ -135,9 +300,15 @@ impl ExtendWithFunctionAttrs for RsTestData {
 #[derive(PartialEq, Debug)]
 pub(crate) enum RsTestItem {
     Fixture(Fixture),
+    ArgumentValue(ArgumentValue),
     CaseArgName(Ident),
     TestCase(TestCase),
     ValueList(ValueList),
+    Runtime(RuntimeDefinition),
+    Names(NamesDefinition),
+    Filter(CombinationsFilter),
+    Combinations(CombinationsDefinition),
+    TypeList(TypeList),
 }
 
 impl From<Fixture> for RsTestItem {
@@ -146,6 +317,12 @@ impl From<Fixture> for RsTestItem {
     }
 }
 
+impl From<ArgumentValue> for RsTestItem {
+    fn from(value: ArgumentValue) -> Self {
+        RsTestItem::ArgumentValue(value)
+    }
+}
+
 impl From<Ident> for RsTestItem {
     fn from(ident: Ident) -> Self {
         RsTestItem::CaseArgName(ident)
@@ -166,7 +343,15 @@ impl From<ValueList> for RsTestItem {
 
 impl Parse for RsTestItem {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if input.fork().parse::<TestCase>().is_ok() {
+        if Runtime::is_runtime_definition(input) {
+            input.parse::<RuntimeDefinition>().map(RsTestItem::Runtime)
+        } else if CombinationsMode::is_combinations_definition(input) {
+            input
+                .parse::<CombinationsDefinition>()
+                .map(RsTestItem::Combinations)
+        } else if NamesDefinition::is_names_definition(input) {
+            input.parse::<NamesDefinition>().map(RsTestItem::Names)
+        } else if input.fork().parse::<TestCase>().is_ok() {
             input.parse::<TestCase>().map(RsTestItem::TestCase)
         } else if input.peek2(Token![=>]) {
             input.parse::<ValueList>().map(RsTestItem::ValueList)
@@ -185,9 +370,10 @@ impl MaybeIdent for RsTestItem {
         use RsTestItem::*;
         match self {
             Fixture(ref fixture) => Some(fixture.ident()),
+            ArgumentValue(ref value) => Some(&value.name),
             CaseArgName(ref case_arg) => Some(case_arg),
             ValueList(ref value_list) => Some(value_list.ident()),
-            TestCase(_) => None,
+            TestCase(_) | Runtime(_) | Names(_) | Filter(_) | Combinations(_) | TypeList(_) => None,
         }
     }
 }
@@ -197,9 +383,18 @@ impl ToTokens for RsTestItem {
         use RsTestItem::*;
         match self {
             Fixture(ref fixture) => fixture.to_tokens(tokens),
+            ArgumentValue(ref value) => value.name.to_tokens(tokens),
             CaseArgName(ref case_arg) => case_arg.to_tokens(tokens),
             TestCase(ref case) => case.to_tokens(tokens),
             ValueList(ref list) => list.to_tokens(tokens),
+            Runtime(RuntimeDefinition(name)) => {
+                let keyword = format_ident!("{}", super::runtime::Runtime::KEYWORD);
+                tokens.extend(quote! { #keyword = #name })
+            }
+            Names(ref names) => names.to_tokens(tokens),
+            Filter(ref filter) => filter.to_tokens(tokens),
+            Combinations(ref combinations) => combinations.to_tokens(tokens),
+            TypeList(ref types) => types.to_tokens(tokens),
         }
     }
 }
@@ -209,6 +404,9 @@ wrap_attributes!(RsTestAttributes);
 impl RsTestAttributes {
     const TRACE_VARIABLE_ATTR: &'static str = "trace";
     const NOTRACE_VARIABLE_ATTR: &'static str = "notrace";
+    const PARAMS_ATTR: &'static str = "params";
+    const ZIP_ATTR: &'static str = "zip";
+    const TRACKED_FILE_ATTR: &'static str = "tracked_file";
 
     pub(crate) fn trace_me(&self, ident: &Ident) -> bool {
         if self.should_trace() {
@@ -248,12 +446,161 @@ impl RsTestAttributes {
         ));
     }
 
+    pub(crate) fn add_params(&mut self, params: Vec<Ident>) {
+        if params.is_empty() {
+            return;
+        }
+        self.inner.attributes.push(Attribute::Tagged(
+            format_ident!("{}", Self::PARAMS_ATTR),
+            params,
+        ));
+    }
+
+    /// The arguments marked by `#[params]`: they are resolved by parametrized fixtures.
+    pub(crate) fn params(&self) -> impl Iterator<Item = &Ident> {
+        self.iter()
+            .filter_map(|m| match m {
+                Attribute::Tagged(name, args) if name == Self::PARAMS_ATTR => Some(args),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    pub(crate) fn has_params(&self) -> bool {
+        self.params().next().is_some()
+    }
+
+    pub(crate) fn add_zipped(&mut self, zipped: Vec<Ident>) {
+        if zipped.is_empty() {
+            return;
+        }
+        self.inner.attributes.push(Attribute::Tagged(
+            format_ident!("{}", Self::ZIP_ATTR),
+            zipped,
+        ));
+    }
+
+    /// The arguments marked by `#[zip]`: their values lists are zipped.
+    pub(crate) fn zipped(&self) -> impl Iterator<Item = &Ident> {
+        self.iter()
+            .filter_map(|m| match m {
+                Attribute::Tagged(name, args) if name == Self::ZIP_ATTR => Some(args),
+                _ => None,
+            })
+            .flatten()
+    }
+
     fn is_trace(m: &Attribute) -> bool {
         match m {
             Attribute::Attr(i) if i == Self::TRACE_VARIABLE_ATTR => true,
             _ => false,
         }
     }
+
+    /// Record the `#[future]` arguments that should be awaited before call the function.
+    pub(crate) fn set_awaited(&mut self, awaited: AwaitedFutures) {
+        self.inner.attributes.extend(awaited.to_attribute())
+    }
+
+    /// Record a data file used to generate the tests: they should be compiled again
+    /// when it changes.
+    pub(crate) fn add_tracked_file(&mut self, path: &std::path::Path, lit: &syn::LitStr) {
+        let path = syn::LitStr::new(&path.to_string_lossy(), lit.span());
+        self.inner.attributes.push(Attribute::Expr(
+            format_ident!("{}", Self::TRACKED_FILE_ATTR),
+            parse_quote! { #path },
+        ))
+    }
+
+    pub(crate) fn tracked_files(&self) -> impl Iterator<Item = &syn::Expr> {
+        self.iter().filter_map(|m| match m {
+            Attribute::Expr(name, path) if name == Self::TRACKED_FILE_ATTR => Some(path),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn awaited(&self) -> AwaitedFutures {
+        AwaitedFutures::from_attributes(self.iter())
+    }
+
+    pub(crate) fn set_runtime(&mut self, runtime: RuntimeDefinition) {
+        self.inner.attributes.push(Attribute::Tagged(
+            format_ident!("{}", Runtime::KEYWORD),
+            vec![runtime.0],
+        ))
+    }
+
+    pub(crate) fn set_combinations(&mut self, combinations: CombinationsDefinition) {
+        self.inner.attributes.push(Attribute::Expr(
+            format_ident!("{}", CombinationsMode::KEYWORD),
+            combinations.0,
+        ))
+    }
+
+    /// How the values lists are combined: the cartesian product if not defined.
+    pub(crate) fn combinations(&self) -> CombinationsMode {
+        self.iter()
+            .filter_map(|m| match m {
+                Attribute::Expr(name, mode) if name == CombinationsMode::KEYWORD => {
+                    CombinationsMode::from_expr(mode).ok()
+                }
+                _ => None,
+            })
+            .next()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn set_names(&mut self, names: NamesDefinition) {
+        let keyword = format_ident!("{}", names.keyword());
+        self.inner.attributes.push(match names {
+            NamesDefinition::Values(mode) => Attribute::Tagged(keyword, vec![mode]),
+            NamesDefinition::Template(template) => {
+                Attribute::Expr(keyword, parse_quote! { #template })
+            }
+        })
+    }
+
+    fn has_names(&self, keyword: &str) -> bool {
+        self.iter().any(|m| match m {
+            Attribute::Tagged(name, _) | Attribute::Expr(name, _) => name == keyword,
+            _ => false,
+        })
+    }
+
+    /// `names = values`: the tests' names contain the values.
+    pub(crate) fn names_from_values(&self) -> bool {
+        self.has_names(NamesDefinition::NAMES_KEYWORD)
+    }
+
+    /// The cases' names template given by `name = "..."`, if any.
+    pub(crate) fn name_template(&self) -> Option<NameTemplate> {
+        self.iter()
+            .filter_map(|m| match m {
+                Attribute::Expr(
+                    name,
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(template),
+                        ..
+                    }),
+                ) if name == NamesDefinition::NAME_KEYWORD => {
+                    NameTemplate::parse_template(template).ok()
+                }
+                _ => None,
+            })
+            .next()
+    }
+
+    /// The runtime given by `runtime = <name>`, if any.
+    pub(crate) fn runtime(&self) -> Option<Runtime> {
+        self.iter()
+            .filter_map(|m| match m {
+                Attribute::Tagged(name, args) if name == Runtime::KEYWORD => {
+                    args.first().and_then(Runtime::from_ident)
+                }
+                _ => None,
+            })
+            .next()
+    }
 }
 
 impl Parse for RsTestAttributes {
@@ -402,6 +749,87 @@ mod test {
                 .unwrap();
             assert_eq!(attrs("#[something_else]"), b_args);
         }
+
+        #[test]
+        fn extract_params_args_attribute() {
+            let mut item_fn = r#"
+            fn test_fn(#[params] backend: Backend, b: &str, #[params] _engine: Engine) {
+            }
+            "#
+            .ast();
+
+            let mut info = RsTestInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.has_params());
+            assert_eq!(
+                to_idents!(["backend", "_engine"]),
+                info.attributes.params().cloned().collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn extract_zip_args_attribute() {
+            let mut item_fn = r#"
+            fn test_fn(#[zip] #[values(1, 2)] a: u32, b: &str, #[zip] #[values(3, 4)] c: u32) {
+            }
+            "#
+            .ast();
+
+            let mut info = RsTestInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(
+                to_idents!(["a", "c"]),
+                info.attributes.zipped().cloned().collect::<Vec<_>>()
+            );
+            assert_eq!(2, info.data.list_values().count());
+        }
+
+        #[test]
+        fn extract_default_values() {
+            let mut item_fn = r#"
+            fn test_fn(#[case] a: u32, #[case] #[default(42)] b: u32, #[default("x")] c: &str) {
+            }
+            "#
+            .ast();
+            let expected: ItemFn = r#"
+            fn test_fn(a: u32, b: u32, c: &str) {
+            }
+            "#
+            .ast();
+
+            let mut info = RsTestInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(expected, item_fn);
+            assert_eq!(
+                vec![&arg_value("b", "42"), &arg_value("c", r#""x""#)],
+                info.data.values().collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn extract_types_lists_before_the_cases() {
+            let mut item_fn = r#"
+            #[types(T = [u8, u16])]
+            #[case(1)]
+            fn test_fn<T>(#[case] a: T) {
+            }
+            "#
+            .ast();
+
+            let mut info = RsTestInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.data.has_type_lists());
+            assert!(info.data.cases().all(|case| case.attrs.is_empty()));
+            assert!(item_fn.attrs.is_empty());
+        }
     }
 
     mod parametrize_cases {
@@ -742,6 +1170,7 @@ mod test {
 
         mod defined_via_with_attributes {
             use super::{assert_eq, *};
+            use rstest_test::assert_in;
 
             #[test]
             fn one_arg() {
@@ -764,6 +1193,109 @@ mod test {
                     list_values[1].args()
                 );
             }
+
+            #[test]
+            fn named_values() {
+                let mut item_fn = r#"
+                fn test_fn(#[values(empty => "", "b", emoji => "c")] input: &str) {
+                }
+                "#
+                .ast();
+
+                let mut info = RsTestInfo::default();
+
+                info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+                let list_values = info.data.list_values().cloned().collect::<Vec<_>>();
+
+                assert_eq!(
+                    to_args!([r#""""#, r#""b""#, r#""c""#]),
+                    list_values[0].args()
+                );
+                assert_eq!(
+                    vec!["empty", "input_2", "emoji"],
+                    (0..3).map(|i| list_values[0].name(i)).collect::<Vec<_>>()
+                );
+            }
+
+            #[test]
+            fn raise_error_on_duplicate_names() {
+                let mut item_fn = r#"
+                fn test_fn(#[values(a => 1, a => 2)] x: u32, #[values(y_2 => 1, 2)] y: u32) {
+                }
+                "#
+                .ast();
+
+                let errors = RsTestInfo::default()
+                    .extend_with_function_attrs(&mut item_fn)
+                    .unwrap_err();
+
+                assert_eq!(2, errors.len());
+                assert_in!(errors[0].to_string(), "Duplicate name 'a'");
+                assert_in!(errors[1].to_string(), "Duplicate name 'y_2'");
+            }
+        }
+    }
+
+    mod combinations {
+        use super::{assert_eq, *};
+        use rstest_test::assert_in;
+
+        #[test]
+        fn move_combinations_definition_in_attributes() {
+            let info = parse_rstest("a => [1, 2], combinations = nwise(3), b => [3, 4]");
+
+            assert_eq!(CombinationsMode::NWise(3), info.attributes.combinations());
+            assert_eq!(2, info.data.list_values().count());
+        }
+
+        #[test]
+        fn use_all_combinations_if_not_defined() {
+            let info = parse_rstest("a => [1, 2]");
+
+            assert_eq!(CombinationsMode::All, info.attributes.combinations());
+        }
+
+        #[test]
+        fn raise_error_if_defined_more_than_once() {
+            let error = syn::parse_str::<RsTestInfo>("combinations = pairwise, combinations = all")
+                .unwrap_err();
+
+            assert_in!(error.to_string(), "combinations more than once");
+        }
+    }
+
+    mod runtime {
+        use super::{assert_eq, *};
+        use rstest_test::assert_in;
+
+        #[test]
+        fn move_runtime_definition_in_attributes() {
+            let info = parse_rstest("a, runtime = tokio, case(42)");
+
+            assert_eq!(Some(Runtime::Tokio), info.attributes.runtime());
+            assert_eq!(1, info.data.case_args().count());
+            assert_eq!(1, info.data.cases().count());
+            assert!(!info
+                .data
+                .items
+                .iter()
+                .any(|it| matches!(it, RsTestItem::Runtime(_))));
+        }
+
+        #[test]
+        fn no_runtime_if_not_defined() {
+            let info = parse_rstest("a, case(42)");
+
+            assert_eq!(None, info.attributes.runtime());
+        }
+
+        #[test]
+        fn raise_error_if_defined_more_than_once() {
+            let error =
+                syn::parse_str::<RsTestInfo>("runtime = tokio, runtime = smol").unwrap_err();
+
+            assert_in!(error.to_string(), "runtime more than once");
         }
     }
 
@@ -0,0 +1,173 @@
+/// The async runtime used to run `async` tests: `runtime = <name>` in `rstest` attribute
+/// or, if not given, the one selected by crate's features.
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    Ident, Token,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum Runtime {
+    AsyncStd,
+    Tokio,
+    TokioMultiThread,
+    Smol,
+}
+
+impl Runtime {
+    pub(crate) const KEYWORD: &'static str = "runtime";
+
+    const NAMES: [(&'static str, Runtime); 4] = [
+        ("async_std", Runtime::AsyncStd),
+        ("tokio", Runtime::Tokio),
+        ("tokio_multi_thread", Runtime::TokioMultiThread),
+        ("smol", Runtime::Smol),
+    ];
+
+    pub(crate) fn from_ident(ident: &Ident) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(name, _)| ident == name)
+            .map(|&(_, runtime)| runtime)
+    }
+
+    const FEATURES: [(&'static str, bool, Runtime); 3] = [
+        ("async-std", cfg!(feature = "async-std"), Runtime::AsyncStd),
+        ("tokio", cfg!(feature = "tokio"), Runtime::Tokio),
+        ("smol", cfg!(feature = "smol"), Runtime::Smol),
+    ];
+
+    /// The runtime selected by the enabled crate's features. Features are additive (another
+    /// crate can enable one more runtime) so if more than one is enabled there is no default
+    /// runtime: the enabled features' names are returned as error.
+    pub(crate) fn from_features() -> std::result::Result<Option<Self>, Vec<&'static str>> {
+        Self::select(
+            Self::FEATURES
+                .iter()
+                .filter(|(_, enabled, _)| *enabled)
+                .map(|&(name, _, runtime)| (name, runtime)),
+        )
+    }
+
+    fn select(
+        enabled: impl Iterator<Item = (&'static str, Runtime)>,
+    ) -> std::result::Result<Option<Self>, Vec<&'static str>> {
+        let enabled = enabled.collect::<Vec<_>>();
+        match enabled.as_slice() {
+            [] => Ok(None),
+            [(_, runtime)] => Ok(Some(*runtime)),
+            _ => Err(enabled.into_iter().map(|(name, _)| name).collect()),
+        }
+    }
+
+    /// The runtime that executes the tests marked by a test attribute like
+    /// `#[tokio::test]`: `None` if it's not one of the known runtimes' attributes.
+    pub(crate) fn from_test_attribute(attr: &syn::Attribute) -> Option<Self> {
+        let segments = attr
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>();
+        match segments.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            ["async_std", "test"] => Some(Runtime::AsyncStd),
+            ["tokio", "test"] => Some(Runtime::Tokio),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_runtime_definition(input: ParseStream) -> bool {
+        input.peek2(Token![=])
+            && input
+                .fork()
+                .parse::<Ident>()
+                .map(|ident| ident == Self::KEYWORD)
+                .unwrap_or_default()
+    }
+}
+
+/// `runtime = <name>`: just the name is stored.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct RuntimeDefinition(pub(crate) Ident);
+
+impl Parse for RuntimeDefinition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let keyword: Ident = input.parse()?;
+        if keyword != Runtime::KEYWORD {
+            return Err(syn::Error::new(keyword.span(), "Expected runtime"));
+        }
+        let _: Token![=] = input.parse()?;
+        let name: Ident = input.parse()?;
+        match Runtime::from_ident(&name) {
+            Some(_) => Ok(Self(name)),
+            None => Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "Unknown runtime '{}': should be one of {}",
+                    name,
+                    Runtime::NAMES
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use mytest::rstest;
+    use rstest_test::assert_in;
+
+    #[rstest]
+    #[case("runtime = async_std", Runtime::AsyncStd)]
+    #[case("runtime = tokio", Runtime::Tokio)]
+    #[case("runtime = tokio_multi_thread", Runtime::TokioMultiThread)]
+    #[case("runtime = smol", Runtime::Smol)]
+    fn parse_runtime(#[case] input: &str, #[case] expected: Runtime) {
+        let definition: RuntimeDefinition = input.ast();
+
+        assert_eq!(Some(expected), Runtime::from_ident(&definition.0));
+    }
+
+    #[rstest]
+    #[case::async_std("#[async_std::test]", Some(Runtime::AsyncStd))]
+    #[case::tokio("#[tokio::test(flavor = \"multi_thread\")]", Some(Runtime::Tokio))]
+    #[case::other("#[actix_rt::test]", None)]
+    #[case::sync("#[test]", None)]
+    fn recognize_the_runtime_of_test_attributes(
+        #[case] attr: &str,
+        #[case] expected: Option<Runtime>,
+    ) {
+        let attr = attrs(attr).pop().unwrap();
+
+        assert_eq!(expected, Runtime::from_test_attribute(&attr));
+    }
+
+    #[rstest]
+    #[case::none(vec![], Ok(None))]
+    #[case::one(vec![("tokio", Runtime::Tokio)], Ok(Some(Runtime::Tokio)))]
+    #[case::more(
+        vec![("async-std", Runtime::AsyncStd), ("tokio", Runtime::Tokio)],
+        Err(vec!["async-std", "tokio"])
+    )]
+    fn select_the_runtime_just_if_one_feature_is_enabled(
+        #[case] enabled: Vec<(&'static str, Runtime)>,
+        #[case] expected: std::result::Result<Option<Runtime>, Vec<&'static str>>,
+    ) {
+        assert_eq!(expected, Runtime::select(enabled.into_iter()));
+    }
+
+    #[test]
+    fn reject_unknown_runtime() {
+        let error = syn::parse_str::<RuntimeDefinition>("runtime = actix").unwrap_err();
+
+        assert_in!(
+            error.to_string(),
+            "Unknown runtime 'actix': should be one of async_std, tokio, tokio_multi_thread, smol"
+        );
+    }
+}
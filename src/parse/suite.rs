@@ -0,0 +1,103 @@
+/// `#[rstest_suite]` instances: the suite's macro calls `__rstest_suite!` with the suite's
+/// module followed by the macro's arguments.
+use syn::{
+    parse::{Parse, ParseStream},
+    Ident, ItemMod, Path, Result, Token, Type,
+};
+
+/// The `__rstest_suite!` input: `<suite module> <type>[ as <name>][, fixture = <path>]`
+#[derive(Debug)]
+pub(crate) struct SuiteInstance {
+    pub(crate) suite: ItemMod,
+    /// The implementation used for the tests' type parameter.
+    pub(crate) ty: Type,
+    /// The instance module's name if it should not be derived from the type.
+    pub(crate) name: Option<Ident>,
+    /// The fixture that resolves the arguments of the implementation type.
+    pub(crate) fixture: Option<Path>,
+}
+
+impl Parse for SuiteInstance {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let suite = input.parse()?;
+        let ty = input.parse()?;
+        let name = match input.parse::<Option<Token![as]>>()? {
+            Some(_) => Some(input.parse()?),
+            None => None,
+        };
+        let mut fixture = None;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            let _eq: Token![=] = input.parse()?;
+            if key != "fixture" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("Unknown suite argument '{}': should be 'fixture'", key),
+                ));
+            }
+            if fixture.is_some() {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "You cannot define the suite's fixture more than once",
+                ));
+            }
+            fixture = Some(input.call(Path::parse_mod_style)?);
+        }
+        if !input.is_empty() {
+            return Err(input.error("Expected ','"));
+        }
+        Ok(Self {
+            suite,
+            ty,
+            name,
+            fixture,
+        })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use mytest::rstest;
+    use rstest_test::assert_in;
+
+    const SUITE: &str = "mod contract { #[rstest] fn test<S: Storage>(store: S) {} }";
+
+    #[test]
+    fn parse_the_type_and_the_fixture() {
+        let instance: SuiteInstance =
+            format!("{} InMemory<u32>, fixture = stores::in_memory,", SUITE).ast();
+
+        assert_eq!("contract", instance.suite.ident.to_string());
+        assert_eq!(instance.ty, "InMemory<u32>".ast());
+        assert_eq!(instance.fixture, Some("stores::in_memory".ast()));
+    }
+
+    #[test]
+    fn parse_just_the_type() {
+        let instance: SuiteInstance = format!("{} InMemory", SUITE).ast();
+
+        assert_eq!(instance.ty, "InMemory".ast());
+        assert!(instance.name.is_none());
+        assert!(instance.fixture.is_none());
+    }
+
+    #[test]
+    fn parse_the_module_name() {
+        let instance: SuiteInstance = format!("{} InMemory as memory, fixture = f", SUITE).ast();
+
+        assert_eq!(instance.name, Some(ident("memory")));
+        assert_eq!(instance.fixture, Some("f".ast()));
+    }
+
+    #[rstest]
+    #[case::unknown("InMemory, other = f", "Unknown suite argument 'other'")]
+    #[case::twice("InMemory, fixture = f, fixture = g", "more than once")]
+    #[case::no_comma("InMemory fixture = f", "Expected ','")]
+    fn raise_error(#[case] args: &str, #[case] message: &str) {
+        let error = syn::parse_str::<SuiteInstance>(&format!("{} {}", SUITE, args)).unwrap_err();
+
+        assert_in!(error.to_string(), message);
+    }
+}
@@ -1,19 +1,27 @@
+use std::ops::Range;
+
 use syn::{
     parse::{Error, Parse, ParseStream, Result},
-    punctuated::Punctuated,
-    Attribute, Expr, Ident, Token,
+    token, Attribute, Expr, Ident, LitStr, Pat, Token,
 };
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
 
+use super::fixture::ArgumentValue;
+use crate::utils::{respan, sanitize};
+
 #[derive(PartialEq, Debug, Clone)]
 /// A test case instance data. Contains a list of arguments. It is parsed by parametrize
 /// attributes.
 pub(crate) struct TestCase {
     pub(crate) args: Vec<Expr>,
+    /// The values given by name: `arg = value`
+    pub(crate) named: Vec<ArgumentValue>,
     pub(crate) attrs: Vec<Attribute>,
     pub(crate) description: Option<Ident>,
+    /// The test's expected result given by `=> expected`
+    pub(crate) expected: Option<Expected>,
 }
 
 impl Parse for TestCase {
@@ -28,13 +36,27 @@ impl Parse for TestCase {
             }
             let content;
             let _ = syn::parenthesized!(content in input);
-            let args = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?
-                .into_iter()
-                .collect();
+            let CaseArgs {
+                args,
+                named,
+                mut expected,
+            } = content.parse()?;
+            if input.peek(Token![=>]) {
+                let arrow = input.parse::<Token![=>]>()?;
+                if expected.is_some() {
+                    return Err(Error::new(
+                        arrow.spans[0],
+                        "You cannot define the expected result more than once",
+                    ));
+                }
+                expected = Some(input.parse()?);
+            }
             Ok(TestCase {
                 args,
+                named,
                 attrs,
                 description,
+                expected,
             })
         } else {
             Err(Error::new(case.span(), "expected a test case"))
@@ -42,9 +64,378 @@ impl Parse for TestCase {
     }
 }
 
+/// The `_` case's argument: the argument takes its fixture or default value.
+pub(crate) fn is_placeholder(arg: &Expr) -> bool {
+    match arg {
+        Expr::Verbatim(tokens) => tokens.to_string() == "_",
+        _ => false,
+    }
+}
+
+/// What the test function should produce:
+///
+/// * `=> value` the returned value is compared with `value` by `assert_eq!`
+/// * `=> panics ["message"]` the test should panic (like `#[should_panic]`)
+/// * `=> matches Pattern [if guard]` the returned value should match the pattern
+/// * `=> err[(Pattern)]` the test returns a `Result` that should be an error that
+/// matches the pattern (if any)
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) enum Expected {
+    Value(Expr),
+    Panics(Option<LitStr>),
+    Matches(Box<Pat>, Option<Expr>),
+    Err(Option<Box<Pat>>),
+}
+
+impl Expected {
+    const PANICS: &'static str = "panics";
+    const MATCHES: &'static str = "matches";
+    const ERR: &'static str = "err";
+}
+
+impl Parse for Expected {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fork = input.fork();
+        let keyword = fork.parse::<Ident>().ok();
+        let at_end = fork.is_empty() || fork.peek(Token![,]);
+        match keyword {
+            Some(k) if k == Self::PANICS && (at_end || fork.peek(LitStr)) => {
+                let _: Ident = input.parse()?;
+                let message = if input.peek(LitStr) {
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
+                Ok(Expected::Panics(message))
+            }
+            Some(k) if k == Self::MATCHES && !at_end => {
+                let _: Ident = input.parse()?;
+                let pattern = input.parse()?;
+                let guard = if input.peek(Token![if]) {
+                    let _: Token![if] = input.parse()?;
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
+                Ok(Expected::Matches(Box::new(pattern), guard))
+            }
+            Some(k) if k == Self::ERR && (at_end || fork.peek(token::Paren)) => {
+                let _: Ident = input.parse()?;
+                let pattern = if input.peek(token::Paren) {
+                    let content;
+                    let _ = syn::parenthesized!(content in input);
+                    Some(Box::new(content.parse()?))
+                } else {
+                    None
+                };
+                Ok(Expected::Err(pattern))
+            }
+            _ => Ok(Expected::Value(input.parse()?)),
+        }
+    }
+}
+
+impl TestCase {
+    /// The case's values of the given `#[case]` arguments: the positional values are taken
+    /// in order and the other ones by name. The omitted arguments are skipped.
+    pub(crate) fn values<'a>(&'a self, case_args: &[String]) -> Vec<(String, &'a Expr)> {
+        case_args
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, arg)| {
+                self.args
+                    .get(pos)
+                    .or_else(|| self.named.iter().find(|v| v.name == arg).map(|v| &v.expr))
+                    .map(|value| (arg.clone(), value))
+            })
+            .collect()
+    }
+}
+
+/// The case's arguments optionally followed by the expected result: `arg1, ..., argn => expected`.
+/// The positional arguments can be followed by the named ones: `name = value`.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct CaseArgs {
+    pub(crate) args: Vec<Expr>,
+    pub(crate) named: Vec<ArgumentValue>,
+    pub(crate) expected: Option<Expected>,
+}
+
+impl Parse for CaseArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = vec![];
+        let mut named: Vec<ArgumentValue> = vec![];
+        while !input.is_empty() && !input.peek(Token![=>]) {
+            if ArgumentValue::peek(input) {
+                named.push(input.parse()?);
+            } else if let Some(value) = named.last() {
+                return Err(input.error(format!(
+                    "Positional values should precede the named ones: found a positional \
+                    value after '{}'",
+                    value.name
+                )));
+            } else {
+                args.push(input.parse()?);
+            }
+            if input.is_empty() || input.peek(Token![=>]) {
+                break;
+            }
+            let _: Token![,] = input.parse()?;
+        }
+        let expected = if input.peek(Token![=>]) {
+            let _: Token![=>] = input.parse()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        if !input.is_empty() {
+            return Err(input.error("Unexpected tokens after the case's expected result"));
+        }
+        Ok(Self {
+            args,
+            named,
+            expected,
+        })
+    }
+}
+
 impl ToTokens for TestCase {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        self.args.iter().for_each(|c| c.to_tokens(tokens))
+        self.args.iter().for_each(|c| c.to_tokens(tokens));
+        self.named.iter().for_each(|v| {
+            v.name.to_tokens(tokens);
+            v.expr.to_tokens(tokens);
+        })
+    }
+}
+
+/// The table of `#[cases(table = r"...")]`: the first row contains the `#[case]` arguments'
+/// names and every other row is a case. Cells are separated by `|` (use `\|` for a `|` in
+/// an expression) and the `name` column, if it's not a case argument, is used as
+/// case's description.
+#[derive(PartialEq, Debug, Clone)]
+pub(crate) struct CasesTable {
+    table: LitStr,
+}
+
+/// A table's cell: its text and its position in the table's literal.
+#[derive(PartialEq, Debug, Clone)]
+struct Cell {
+    text: String,
+    range: Range<usize>,
+}
+
+impl CasesTable {
+    const NAME_COLUMN: &'static str = "name";
+
+    /// Build the test cases where the arguments are in the `case_args` order.
+    pub(crate) fn test_cases(&self, case_args: &[Ident]) -> Result<Vec<TestCase>> {
+        let value = self.table.value();
+        let mut rows = self.rows(&value).into_iter();
+        let header = match rows.next() {
+            Some(header) => header,
+            None => return Err(Error::new(self.table.span(), "Empty cases table")),
+        };
+        let columns = self.columns(&header, case_args)?;
+        let mut errors = None;
+        let mut cases = vec![];
+        for (range, cells) in rows {
+            match self.test_case(range, cells, &columns) {
+                Ok(case) => cases.push(case),
+                Err(e) => combine(&mut errors, e),
+            }
+        }
+        match errors {
+            Some(errors) => Err(errors),
+            None => Ok(cases),
+        }
+    }
+
+    /// Map every header's column to the index of its case argument: `None` is the
+    /// `name` column.
+    fn columns(
+        &self,
+        (range, header): &(Range<usize>, Vec<Cell>),
+        case_args: &[Ident],
+    ) -> Result<Vec<Option<usize>>> {
+        let name_is_arg = case_args.iter().any(|a| a == Self::NAME_COLUMN);
+        let mut errors = None;
+        let mut columns = vec![];
+        for cell in header {
+            match case_args.iter().position(|a| a == &cell.text) {
+                Some(index) if columns.contains(&Some(index)) => combine(
+                    &mut errors,
+                    self.error(&cell.range, format!("Duplicate column '{}'", cell.text)),
+                ),
+                Some(index) => columns.push(Some(index)),
+                None if cell.text == Self::NAME_COLUMN && !name_is_arg => columns.push(None),
+                None => combine(
+                    &mut errors,
+                    self.error(
+                        &cell.range,
+                        format!("Column '{}' is not a #[case] argument", cell.text),
+                    ),
+                ),
+            }
+        }
+        for (index, arg) in case_args.iter().enumerate() {
+            if !columns.contains(&Some(index)) {
+                combine(
+                    &mut errors,
+                    self.error(
+                        range,
+                        format!("Missed column for #[case] argument '{}'", arg),
+                    ),
+                )
+            }
+        }
+        match errors {
+            Some(errors) => Err(errors),
+            None => Ok(columns),
+        }
+    }
+
+    fn test_case(
+        &self,
+        range: Range<usize>,
+        cells: Vec<Cell>,
+        columns: &[Option<usize>],
+    ) -> Result<TestCase> {
+        if cells.len() != columns.len() {
+            return Err(self.error(
+                &range,
+                format!(
+                    "Wrong number of cells: expected {} like the header's columns but found {}",
+                    columns.len(),
+                    cells.len()
+                ),
+            ));
+        }
+        let mut args = vec![None; columns.iter().flatten().count()];
+        let mut description = None;
+        for (cell, column) in cells.into_iter().zip(columns) {
+            match column {
+                Some(index) => args[*index] = Some(self.expression(&cell)?),
+                None => {
+                    description = Some(sanitize(&cell.text))
+                        .filter(|d| !d.is_empty())
+                        .map(|d| Ident::new(&d, self.span(&cell.range)))
+                }
+            }
+        }
+        Ok(TestCase {
+            args: args.into_iter().flatten().collect(),
+            named: vec![],
+            attrs: vec![],
+            description,
+            expected: None,
+        })
+    }
+
+    fn expression(&self, cell: &Cell) -> Result<Expr> {
+        let span = self.span(&cell.range);
+        let invalid = |e: &dyn std::fmt::Display| {
+            Error::new(span, format!("Invalid expression '{}': {}", cell.text, e))
+        };
+        let tokens: TokenStream = cell.text.parse().map_err(|e| invalid(&e))?;
+        syn::parse2(respan(tokens, span)).map_err(|e| invalid(&e))
+    }
+
+    /// Split the table in rows of cells and skip the empty lines and the markdown
+    /// separators like `|---|:--:|`.
+    fn rows(&self, value: &str) -> Vec<(Range<usize>, Vec<Cell>)> {
+        let mut offset = 0;
+        let mut rows = vec![];
+        for line in value.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let line = line.trim_end();
+            if line.trim().is_empty() {
+                continue;
+            }
+            let cells = split_cells(line, start);
+            let is_separator = cells.iter().all(|c| {
+                let text = c.text.trim_matches(':');
+                !text.is_empty() && text.chars().all(|c| c == '-')
+            });
+            if !is_separator {
+                rows.push((start..start + line.len(), cells))
+            }
+        }
+        rows
+    }
+
+    fn error(&self, range: &Range<usize>, msg: impl std::fmt::Display) -> Error {
+        Error::new(self.span(range), msg)
+    }
+
+    /// The span of the literal's part in `range`: if the compiler cannot resolve it
+    /// (stable compiler or not raw string) the span of the whole literal.
+    fn span(&self, range: &Range<usize>) -> Span {
+        let token = self.table.token();
+        let repr = token.to_string();
+        repr.strip_prefix('r')
+            .and_then(|raw| raw.find('"').map(|quote| quote + 2))
+            .and_then(|prefix| token.subspan(range.start + prefix..range.end + prefix))
+            .unwrap_or_else(|| self.table.span())
+    }
+}
+
+fn combine(errors: &mut Option<Error>, error: Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
+    }
+}
+
+/// Split a table's row by the not escaped `|`: the first and the last empty cells are
+/// the borders.
+fn split_cells(line: &str, offset: usize) -> Vec<Cell> {
+    let mut cells = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '|' if !escaped => {
+                cells.push(cell(line, start..i, offset));
+                start = i + 1;
+            }
+            _ => {}
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    cells.push(cell(line, start..line.len(), offset));
+    if cells.len() > 1 && cells[0].text.is_empty() {
+        cells.remove(0);
+    }
+    if cells.len() > 1 && cells[cells.len() - 1].text.is_empty() {
+        cells.pop();
+    }
+    cells
+}
+
+fn cell(line: &str, range: Range<usize>, offset: usize) -> Cell {
+    let raw = &line[range.clone()];
+    let start = range.start + (raw.len() - raw.trim_start().len());
+    let end = range.end - (raw.len() - raw.trim_end().len());
+    Cell {
+        text: line[start..end.max(start)].replace("\\|", "|"),
+        range: offset + start..offset + end.max(start),
+    }
+}
+
+impl Parse for CasesTable {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Ident) {
+            let keyword: Ident = input.parse()?;
+            if keyword != "table" {
+                return Err(Error::new(keyword.span(), "Expected table"));
+            }
+            let _: Token![=] = input.parse()?;
+        }
+        Ok(Self {
+            table: input.parse()?,
+        })
     }
 }
 
@@ -159,4 +550,232 @@ mod should {
         assert!(content.contains("should_panic"));
         assert!(content.contains("other_attr"));
     }
+
+    mod expected {
+        use super::{assert_eq, *};
+        use mytest::rstest;
+        use rstest_test::assert_in;
+
+        #[rstest]
+        #[case::value("case(2, 3) => 5", Expected::Value(expr("5")))]
+        #[case::value_in_args("case(2, 3 => a + b)", Expected::Value(expr("a + b")))]
+        #[case::panics("case(0 => panics)", Expected::Panics(None))]
+        #[case::panics_message(
+            r#"case(0) => panics "divide by zero""#,
+            Expected::Panics(Some(syn::parse_str(r#""divide by zero""#).unwrap()))
+        )]
+        #[case::matches(
+            "case(2 => matches Some(_))",
+            Expected::Matches(Box::new(syn::parse_str("Some(_)").unwrap()), None)
+        )]
+        #[case::matches_guard(
+            "case(2 => matches Some(x) if x > 3)",
+            Expected::Matches(Box::new(syn::parse_str("Some(x)").unwrap()), Some(expr("x > 3")))
+        )]
+        #[case::err("case(-1 => err)", Expected::Err(None))]
+        #[case::err_pattern(
+            "case(-1 => err(Error::Negative))",
+            Expected::Err(Some(Box::new(syn::parse_str("Error::Negative").unwrap())))
+        )]
+        #[case::panics_as_value("case(0 => panics.len())", Expected::Value(expr("panics.len()")))]
+        #[case::err_as_value("case(0 => err + 1)", Expected::Value(expr("err + 1")))]
+        fn parse_expected_result(#[case] code: &str, #[case] expected: Expected) {
+            let test_case = parse_test_case(code);
+
+            assert_eq!(Some(expected), test_case.expected);
+        }
+
+        #[test]
+        fn keep_the_arguments() {
+            let test_case = parse_test_case("case(2, 3, => 5)");
+
+            assert_eq!(to_args!(["2", "3"]), test_case.args());
+            assert!(test_case.expected.is_some());
+        }
+
+        #[test]
+        fn no_expected_result() {
+            assert_eq!(None, parse_test_case("case(2, 3)").expected);
+        }
+
+        #[rstest]
+        #[case::twice(
+            "case(2 => 3) => 4",
+            "You cannot define the expected result more than once"
+        )]
+        #[case::trailing("case(2 => 3 4)", "Unexpected tokens after the case's expected result")]
+        fn raise_error(#[case] code: &str, #[case] message: &str) {
+            let error = syn::parse_str::<TestCase>(code).unwrap_err();
+
+            assert_in!(error.to_string(), message);
+        }
+    }
+
+    mod named_values {
+        use super::{assert_eq, *};
+        use rstest_test::assert_in;
+
+        #[test]
+        fn parse_the_named_values_after_the_positional_ones() {
+            let test_case = parse_test_case("case(1, c = 3, b = a == 2 => 5)");
+
+            assert_eq!(to_args!(["1"]), test_case.args());
+            assert_eq!(
+                vec![arg_value("c", "3"), arg_value("b", "a == 2")],
+                test_case.named
+            );
+            assert_eq!(Some(Expected::Value(expr("5"))), test_case.expected);
+        }
+
+        #[test]
+        fn not_confuse_comparison_or_expected_result_with_named_values() {
+            let test_case = parse_test_case("case(a == 2, b => c)");
+
+            assert_eq!(to_args!(["a == 2", "b"]), test_case.args());
+            assert!(test_case.named.is_empty());
+        }
+
+        #[test]
+        fn resolve_the_values_by_position_and_by_name() {
+            let test_case = parse_test_case("case(1, d = 4, b = 2)");
+            let case_args = ["a", "b", "c", "d"]
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+
+            let values = test_case
+                .values(&case_args)
+                .into_iter()
+                .map(|(name, value)| (name, value.clone()))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                vec![
+                    ("a".to_owned(), expr("1")),
+                    ("b".to_owned(), expr("2")),
+                    ("d".to_owned(), expr("4"))
+                ],
+                values
+            );
+        }
+
+        #[test]
+        fn raise_error_on_positional_value_after_named_ones() {
+            let error = syn::parse_str::<TestCase>("case(a = 1, 2)").unwrap_err();
+
+            assert_in!(
+                error.to_string(),
+                "Positional values should precede the named ones"
+            );
+        }
+    }
+
+    mod cases_table {
+        use super::{assert_eq, *};
+        use mytest::rstest;
+        use rstest_test::assert_in;
+
+        fn test_cases(table: &str, args: &[&str]) -> Result<Vec<TestCase>> {
+            let table: CasesTable = table.ast();
+            table.test_cases(&args.iter().map(ident).collect::<Vec<_>>())
+        }
+
+        fn descriptions(cases: &[TestCase]) -> Vec<Option<String>> {
+            cases
+                .iter()
+                .map(|c| c.description.as_ref().map(ToString::to_string))
+                .collect()
+        }
+
+        #[test]
+        fn map_columns_by_name() {
+            let cases = test_cases(
+                r##"table = r#"
+                | expected | input        |
+                |----------|:------------:|
+                | 42       | "42"         |
+
+                | -1       | vec![1, 2]   |
+                "#"##,
+                &["input", "expected"],
+            )
+            .unwrap();
+
+            assert_eq!(
+                vec![to_args!([r#""42""#, "42"]), to_args!(["vec![1, 2]", "-1"])],
+                cases.iter().map(|c| c.args.clone()).collect::<Vec<_>>()
+            );
+            assert_eq!(vec![None, None], descriptions(&cases));
+        }
+
+        #[test]
+        fn accept_table_without_keyword_and_borders() {
+            let cases = test_cases(r#""a | b\n1 | 2""#, &["a", "b"]).unwrap();
+
+            assert_eq!(to_args!(["1", "2"]), cases[0].args);
+        }
+
+        #[test]
+        fn use_name_column_as_description() {
+            let cases = test_cases(
+                r#"r"| name | a |
+                    | first case | 1 |
+                    |  | 2 |""#,
+                &["a"],
+            )
+            .unwrap();
+
+            assert_eq!(
+                vec![Some("first_case".to_owned()), None],
+                descriptions(&cases)
+            );
+        }
+
+        #[test]
+        fn unescape_pipes() {
+            let cases = test_cases(
+                r#"r"| a      | b            |
+                    | 1 \| 2 | \|x\| x + 1 |""#,
+                &["a", "b"],
+            )
+            .unwrap();
+
+            assert_eq!(to_args!(["1 | 2", "|x| x + 1"]), cases[0].args);
+        }
+
+        #[test]
+        fn use_name_as_argument_if_it_is_a_case_argument() {
+            let cases = test_cases(r#""| name |\n| \"foo\" |""#, &["name"]).unwrap();
+
+            assert_eq!(to_args!([r#""foo""#]), cases[0].args);
+            assert_eq!(vec![None], descriptions(&cases));
+        }
+
+        #[rstest]
+        #[case::empty(r#""  ""#, &["a"], "Empty cases table")]
+        #[case::missed_column(r#""| a |\n| 1 |""#, &["a", "b"], "Missed column for #[case] argument 'b'")]
+        #[case::unknown_column(r#""| a | c |\n| 1 | 2 |""#, &["a"], "Column 'c' is not a #[case] argument")]
+        #[case::duplicate_column(r#""| a | a |\n| 1 | 2 |""#, &["a"], "Duplicate column 'a'")]
+        #[case::wrong_cells(r#""| a | b |\n| 1 | 2 | 3 |""#, &["a", "b"], "Wrong number of cells: expected 2 like the header's columns but found 3")]
+        #[case::invalid_expression(r#""| a |\n| 1 + |""#, &["a"], "Invalid expression '1 +'")]
+        #[case::invalid_tokens(r#""| a |\n| ( |""#, &["a"], "Invalid expression '('")]
+        fn raise_error(#[case] table: &str, #[case] args: &[&str], #[case] expected: &str) {
+            let error = test_cases(table, args).unwrap_err();
+
+            assert_in!(error.to_string(), expected);
+        }
+
+        #[test]
+        fn report_all_the_wrong_rows() {
+            let error = test_cases(r#""| a |\n| 1 + |\n| 2 |\n| 3 | 4 |""#, &["a"]).unwrap_err();
+
+            assert_eq!(2, error.into_iter().count());
+        }
+
+        #[test]
+        #[should_panic(expected = "Expected table")]
+        fn reject_unknown_keyword() {
+            let _: CasesTable = r#"tab = "| a |""#.ast();
+        }
+    }
 }
@@ -0,0 +1,160 @@
+/// `#[types(T = [u8, u16])]` function's attribute: the generic test is instantiated for
+/// each type of the list (for each combination of the types if more lists are given).
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    visit_mut::VisitMut,
+    Ident, ItemFn, Token, Type,
+};
+
+use crate::{
+    error::ErrorsVec,
+    utils::{attr_is, sanitize},
+};
+
+/// The types that replace the `ident` test's type parameter.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct TypeList {
+    pub(crate) ident: Ident,
+    pub(crate) types: Vec<Type>,
+}
+
+impl TypeList {
+    /// The name of the module that contains the tests for the type at `index`: the type
+    /// parameter's name followed by the type (`T_u8`) or by the index if the type cannot
+    /// be used in an identifier.
+    pub(crate) fn name(&self, index: usize) -> String {
+        let rendered = self.types[index]
+            .to_token_stream()
+            .to_string()
+            .replace(' ', "");
+        let name = sanitize(&rendered).trim_matches('_').to_owned();
+        if name.is_empty() {
+            format!("{}_{}", self.ident, index + 1)
+        } else {
+            format!("{}_{}", self.ident, name)
+        }
+    }
+}
+
+impl Parse for TypeList {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        let _eq: Token![=] = input.parse()?;
+        let content;
+        let bracket = syn::bracketed!(content in input);
+        let types = Punctuated::<Type, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        if types.is_empty() {
+            return Err(syn::Error::new(
+                bracket.span,
+                format!(
+                    "Empty types list: '{}' should have at least one type",
+                    ident
+                ),
+            ));
+        }
+        Ok(Self { ident, types })
+    }
+}
+
+impl ToTokens for TypeList {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.ident.to_tokens(tokens)
+    }
+}
+
+/// Simple struct used to visit function attributes and extract the types lists and
+/// eventualy parsing errors
+#[derive(Default)]
+struct TypesFunctionExtractor(Vec<TypeList>, Vec<syn::Error>);
+
+impl VisitMut for TypesFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (types, remain): (Vec<_>, Vec<_>) =
+            attrs.into_iter().partition(|attr| attr_is(attr, "types"));
+        node.attrs = remain;
+        for attr in types {
+            match attr.parse_args_with(Punctuated::<TypeList, Token![,]>::parse_terminated) {
+                Ok(lists) => self.0.extend(lists),
+                Err(err) => self.1.push(err),
+            }
+        }
+    }
+}
+
+pub(crate) fn extract_types(item_fn: &mut ItemFn) -> Result<Vec<TypeList>, ErrorsVec> {
+    let mut types_extractor = TypesFunctionExtractor::default();
+    types_extractor.visit_item_fn_mut(item_fn);
+
+    if types_extractor.1.len() > 0 {
+        Err(types_extractor.1.into())
+    } else {
+        Ok(types_extractor.0)
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use mytest::rstest;
+    use rstest_test::assert_in;
+
+    fn t(code: &str) -> Type {
+        code.ast()
+    }
+
+    #[test]
+    fn extract_the_types_lists() {
+        let mut item_fn: ItemFn = r#"
+        #[types(T = [u8, Vec<u16>], U = [&str])]
+        #[other]
+        #[types(V = [i64])]
+        fn test<T, U, V>(a: T, b: U, c: V) {}
+        "#
+        .ast();
+
+        let lists = extract_types(&mut item_fn).unwrap();
+
+        assert_eq!(
+            vec!["T", "T", "U", "V"],
+            lists
+                .iter()
+                .flat_map(|l| l.types.iter().map(move |_| l.ident.to_string()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![t("u8"), t("Vec<u16>")], lists[0].types);
+        assert_eq!(attrs("#[other]"), item_fn.attrs);
+    }
+
+    #[rstest]
+    #[case::simple("u8", "T_u8")]
+    #[case::generic("Vec<u16>", "T_Vec_u16")]
+    #[case::reference("&str", "T_str")]
+    #[case::path("std::string::String", "T_std__string__String")]
+    #[case::tuple("()", "T_1")]
+    fn name_the_modules_by_type(#[case] ty: &str, #[case] expected: &str) {
+        let list = TypeList {
+            ident: ident("T"),
+            types: vec![t(ty)],
+        };
+
+        assert_eq!(expected, list.name(0));
+    }
+
+    #[rstest]
+    #[case::empty("#[types(T = [])]", "Empty types list")]
+    #[case::no_brackets("#[types(T = u8)]", "expected square brackets")]
+    fn raise_error(#[case] attr: &str, #[case] message: &str) {
+        let mut item_fn: ItemFn = format!("{} fn test<T>(a: T) {{}}", attr).ast();
+
+        let errors = extract_types(&mut item_fn).unwrap_err();
+
+        assert_in!(format!("{:?}", errors), message);
+    }
+}
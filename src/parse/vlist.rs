@@ -1,18 +1,164 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream, Result},
-    Expr, Ident, Token,
+    Attribute, Expr, Ident, Token,
 };
 
 use crate::refident::RefIdent;
 
-use super::expressions::Expressions;
-
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct ValueList {
     pub(crate) arg: Ident,
     pub(crate) values: Vec<Expr>,
+    /// Optional labels appended to the case names: can be empty if no value has a label
+    pub(crate) labels: Vec<Option<String>>,
+    /// Optional names given by `name => value`: used instead of the argument's name and
+    /// the value's index
+    pub(crate) names: Vec<Option<Ident>>,
+    /// The attributes of the tests that use the value: can be empty if no value has
+    /// attributes
+    pub(crate) attrs: Vec<Vec<Attribute>>,
+}
+
+impl ValueList {
+    pub(crate) fn new(arg: Ident, values: Vec<Expr>) -> Self {
+        Self {
+            arg,
+            values,
+            labels: vec![],
+            names: vec![],
+            attrs: vec![],
+        }
+    }
+
+    /// Build the values list from the `[#[attr]...] [name =>] value` entries and check that
+    /// the names are unique.
+    pub(crate) fn named(arg: Ident, values: NamedValues) -> Result<Self> {
+        let (mut attrs, mut names, mut exprs) = (vec![], vec![], vec![]);
+        for NamedValue {
+            attrs: a,
+            name,
+            value,
+        } in values.0
+        {
+            attrs.push(a);
+            names.push(name);
+            exprs.push(value);
+        }
+        if attrs.iter().all(Vec::is_empty) {
+            attrs.clear();
+        }
+        let vlist = Self {
+            names,
+            attrs,
+            ..Self::new(arg, exprs)
+        };
+        let mut errors = vlist
+            .names
+            .iter()
+            .enumerate()
+            .filter_map(|(index, name)| name.as_ref().map(|n| (index, n)))
+            .filter(|&(index, name)| {
+                (0..vlist.values.len())
+                    .filter(|&other| other != index)
+                    .any(|other| name == vlist.name(other).as_str())
+            })
+            .map(|(_, name)| {
+                syn::Error::new(
+                    name.span(),
+                    format!("Duplicate name '{}' in values list", name),
+                )
+            });
+        match errors.next() {
+            Some(mut error) => {
+                errors.for_each(|e| error.combine(e));
+                Err(error)
+            }
+            None => Ok(vlist),
+        }
+    }
+
+    pub(crate) fn label(&self, index: usize) -> Option<&str> {
+        self.labels.get(index).and_then(|l| l.as_deref())
+    }
+
+    /// The name of the `index`-th value's test: the given name or the argument's name
+    /// followed by the value's index.
+    pub(crate) fn name(&self, index: usize) -> String {
+        match self.names.get(index).and_then(Option::as_ref) {
+            Some(name) => name.to_string(),
+            None => format!(
+                "{}_{:0len$}",
+                self.arg,
+                index + 1,
+                len = self.values.len().to_string().len()
+            ),
+        }
+    }
+
+    pub(crate) fn is_named(&self, index: usize) -> bool {
+        matches!(self.names.get(index), Some(Some(_)))
+    }
+
+    /// The `index`-th value's attributes: `#[should_panic]` or `#[ignore]` for instance.
+    pub(crate) fn attrs(&self, index: usize) -> &[Attribute] {
+        self.attrs.get(index).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct NamedValue {
+    attrs: Vec<Attribute>,
+    name: Option<Ident>,
+    value: Expr,
+}
+
+/// A comma separated list of values where every value can have some attributes and a
+/// name: `[#[attr]...] [name =>] value`.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub(crate) struct NamedValues(Vec<NamedValue>);
+
+impl NamedValues {
+    fn parse_name(input: ParseStream) -> Result<Option<Ident>> {
+        let fork = input.fork();
+        if fork.call(Ident::parse_any).is_err() || !fork.peek(Token![=>]) {
+            return Ok(None);
+        }
+        let name = input.call(Ident::parse_any)?;
+        let _: Token![=>] = input.parse()?;
+        if syn::parse_str::<Ident>(&name.to_string()).is_err() {
+            return Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "Invalid name '{}': should be a valid identifier and not a keyword",
+                    name
+                ),
+            ));
+        }
+        Ok(Some(name))
+    }
+}
+
+impl Parse for NamedValues {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut values = vec![];
+        while !input.is_empty() {
+            let attrs = input.call(Attribute::parse_outer)?;
+            let name = Self::parse_name(input)?;
+            values.push(NamedValue {
+                attrs,
+                name,
+                value: input.parse()?,
+            });
+            if input.is_empty() {
+                break;
+            }
+            let _: Token![,] = input.parse()?;
+        }
+        Ok(Self(values))
+    }
 }
 
 impl Parse for ValueList {
@@ -21,12 +167,9 @@ impl Parse for ValueList {
         let _to: Token![=>] = input.parse()?;
         let content;
         let paren = syn::bracketed!(content in input);
-        let values: Expressions = content.parse()?;
+        let values: NamedValues = content.parse()?;
 
-        let ret = Self {
-            arg,
-            values: values.take(),
-        };
+        let ret = Self::named(arg, values)?;
         if ret.values.len() == 0 {
             Err(syn::Error::new(
                 paren.span,
@@ -59,6 +202,8 @@ mod should {
     mod parse_values_list {
         use super::assert_eq;
         use super::*;
+        use mytest::rstest;
+        use rstest_test::assert_in;
 
         fn parse_values_list<S: AsRef<str>>(values_list: S) -> ValueList {
             parse_meta(values_list)
@@ -96,6 +241,47 @@ mod should {
             parse_values_list(r#"other => [some:<>(1,2,3)]"#);
         }
 
+        #[test]
+        fn named_values() {
+            let values_list = parse_values_list(r#"input => [empty => "", "b", r#c => 42]"#);
+
+            assert_eq!(values_list.args(), to_args!([r#""""#, r#""b""#, "42"]));
+            assert_eq!(
+                vec![Some(ident("empty")), None, Some(ident("r#c"))],
+                values_list.names
+            );
+        }
+
+        #[test]
+        fn values_attributes() {
+            let values_list =
+                parse_values_list(r#"input => [1, #[should_panic] 0, #[ignore] big => 1_000]"#);
+
+            assert_eq!(values_list.args(), to_args!(["1", "0", "1_000"]));
+            assert!(values_list.attrs(0).is_empty());
+            assert_eq!(attrs("#[should_panic]"), values_list.attrs(1));
+            assert_eq!(attrs("#[ignore]"), values_list.attrs(2));
+            assert_eq!(ident("big"), values_list.name(2));
+        }
+
+        #[test]
+        fn no_attributes() {
+            let values_list = parse_values_list(r#"input => [1, 2]"#);
+
+            assert!(values_list.attrs.is_empty());
+        }
+
+        #[rstest]
+        #[case::duplicate("a => [x => 1, x => 2]", "Duplicate name 'x' in values list")]
+        #[case::default_name("a => [1, a_1 => 2]", "Duplicate name 'a_1' in values list")]
+        #[case::keyword("a => [type => 1]", "Invalid name 'type'")]
+        #[case::empty("a => []", "Values list should not be empty")]
+        fn raise_error(#[case] input: &str, #[case] expected: &str) {
+            let error = syn::parse_str::<ValueList>(input).unwrap_err();
+
+            assert_in!(error.to_string(), expected);
+        }
+
         #[test]
         #[should_panic(expected = r#"expected square brackets"#)]
         fn forget_brackets() {
@@ -0,0 +1,264 @@
+/// Covering arrays for `combinations = pairwise` and `combinations = nwise(n)`: a subset of
+/// the values lists' combinations where every `n` values of `n` different lists are
+/// used together at least once. The algorithms are greedy and deterministic, so the same
+/// values lists always generate the same tests.
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+
+/// How many rows are tried for every row of the covering array.
+const MAX_SEEDS: usize = 8;
+
+/// `strength` couples of (list, value) indexes sorted by list.
+type Tuple = Vec<(usize, usize)>;
+
+/// All the `size` sized subsets of `0..n` in lexicographic order.
+fn subsets(n: usize, size: usize) -> Vec<Vec<usize>> {
+    fn subsets_from(start: usize, n: usize, size: usize) -> Vec<Vec<usize>> {
+        if size == 0 {
+            return vec![vec![]];
+        }
+        (start..n)
+            .flat_map(|first| {
+                subsets_from(first + 1, n, size - 1)
+                    .into_iter()
+                    .map(move |rest| std::iter::once(first).chain(rest).collect())
+            })
+            .collect()
+    }
+    subsets_from(0, n, size)
+}
+
+fn tuples_of<'a>(row: &'a [usize], strength: usize) -> impl Iterator<Item = Tuple> + 'a {
+    subsets(row.len(), strength)
+        .into_iter()
+        .map(move |lists| lists.into_iter().map(|l| (l, row[l])).collect())
+}
+
+fn all_tuples(sizes: &[usize], strength: usize) -> BTreeSet<Tuple> {
+    subsets(sizes.len(), strength)
+        .into_iter()
+        .flat_map(|lists| {
+            lists.iter().fold(vec![vec![]], |acc: Vec<Tuple>, &l| {
+                acc.into_iter()
+                    .flat_map(|prefix| {
+                        (0..sizes[l]).map(move |v| {
+                            let mut tuple = prefix.clone();
+                            tuple.push((l, v));
+                            tuple
+                        })
+                    })
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+/// The uncovered tuples that would be covered by assigning `value` to the `list` in the
+/// partial `row`.
+fn gain(
+    row: &[Option<usize>],
+    list: usize,
+    value: usize,
+    strength: usize,
+    uncovered: &BTreeSet<Tuple>,
+) -> usize {
+    let assigned = row
+        .iter()
+        .enumerate()
+        .filter_map(|(l, v)| v.map(|v| (l, v)))
+        .filter(|&(l, _)| l != list)
+        .collect::<Vec<_>>();
+    subsets(assigned.len(), strength - 1)
+        .into_iter()
+        .filter(|others| {
+            let mut tuple = others.iter().map(|&i| assigned[i]).collect::<Tuple>();
+            tuple.push((list, value));
+            tuple.sort_unstable();
+            uncovered.contains(&tuple)
+        })
+        .count()
+}
+
+/// Complete the row started by the `seed` tuple: every other list takes the value that
+/// covers more new tuples.
+fn build_row(
+    sizes: &[usize],
+    seed: &Tuple,
+    strength: usize,
+    uncovered: &BTreeSet<Tuple>,
+    pending: &HashMap<(usize, usize), usize>,
+) -> Vec<usize> {
+    let mut row = vec![None; sizes.len()];
+    seed.iter().for_each(|&(l, v)| row[l] = Some(v));
+    for list in 0..sizes.len() {
+        if row[list].is_some() {
+            continue;
+        }
+        let value = (0..sizes[list])
+            .max_by_key(|&v| {
+                (
+                    gain(&row, list, v, strength, uncovered),
+                    pending.get(&(list, v)).copied().unwrap_or_default(),
+                    Reverse(v),
+                )
+            })
+            .unwrap_or_default();
+        row[list] = Some(value);
+    }
+    row.into_iter().map(Option::unwrap).collect()
+}
+
+/// Build a covering array of the given `strength` for lists of the given `sizes`: every
+/// row is built from some seeds (the first uncovered tuples of different lists' subsets)
+/// and the one that covers more new tuples is taken.
+pub(crate) fn covering_array(sizes: &[usize], strength: usize) -> Vec<Vec<usize>> {
+    let strength = strength.min(sizes.len());
+    let mut uncovered = all_tuples(sizes, strength);
+    // How many uncovered tuples contain each value
+    let mut pending = HashMap::new();
+    uncovered
+        .iter()
+        .flatten()
+        .for_each(|&value| *pending.entry(value).or_insert(0) += 1);
+    let mut rows = vec![];
+    while !uncovered.is_empty() {
+        let mut seeds = uncovered.iter().cloned().collect::<Vec<_>>();
+        seeds.dedup_by_key(|t| t.iter().map(|&(l, _)| l).collect::<Vec<_>>());
+        let row = seeds
+            .iter()
+            .take(MAX_SEEDS)
+            .map(|seed| build_row(sizes, seed, strength, &uncovered, &pending))
+            .max_by_key(|row| {
+                (
+                    tuples_of(row, strength)
+                        .filter(|t| uncovered.contains(t))
+                        .count(),
+                    Reverse(row.clone()),
+                )
+            })
+            .unwrap();
+        tuples_of(&row, strength).for_each(|t| {
+            if uncovered.remove(&t) {
+                t.iter().for_each(|value| {
+                    pending.entry(*value).and_modify(|count| *count -= 1);
+                });
+            }
+        });
+        rows.push(row);
+    }
+    rows
+}
+
+/// Select from the allowed `candidates` (the combinations left by `#[exclude]` and `#[only]`
+/// filters) the ones that cover all the tuples of the given `strength` they contain: at
+/// every step take the first candidate that covers more new tuples.
+pub(crate) fn covering_subset(candidates: &[Vec<usize>], strength: usize) -> Vec<Vec<usize>> {
+    let strength = candidates
+        .first()
+        .map(|c| strength.min(c.len()))
+        .unwrap_or_default();
+    let mut uncovered = candidates
+        .iter()
+        .flat_map(|c| tuples_of(c, strength))
+        .collect::<BTreeSet<_>>();
+    let mut rows = vec![];
+    while !uncovered.is_empty() {
+        let best = candidates
+            .iter()
+            .max_by_key(|c| {
+                (
+                    tuples_of(c, strength)
+                        .filter(|t| uncovered.contains(t))
+                        .count(),
+                    Reverse(*c),
+                )
+            })
+            .unwrap();
+        tuples_of(best, strength).for_each(|t| {
+            uncovered.remove(&t);
+        });
+        rows.push(best.clone());
+    }
+    rows
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::assert_eq;
+    use mytest::rstest;
+
+    fn product(sizes: &[usize]) -> Vec<Vec<usize>> {
+        sizes.iter().fold(vec![vec![]], |acc, &size| {
+            acc.into_iter()
+                .flat_map(|prefix| {
+                    (0..size).map(move |v| {
+                        let mut row = prefix.clone();
+                        row.push(v);
+                        row
+                    })
+                })
+                .collect()
+        })
+    }
+
+    fn assert_cover(rows: &[Vec<usize>], expected: &BTreeSet<Tuple>, strength: usize) {
+        let covered = rows
+            .iter()
+            .flat_map(|r| tuples_of(r, strength))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(expected, &covered);
+    }
+
+    #[test]
+    fn enumerate_subsets_in_order() {
+        assert_eq!(vec![vec![0, 1], vec![0, 2], vec![1, 2]], subsets(3, 2));
+    }
+
+    #[rstest]
+    #[case::small(&[2, 2, 2], 2, 4)]
+    #[case::different_sizes(&[3, 2, 2], 2, 6)]
+    #[case::five_lists_of_six(&[6, 6, 6, 6, 6], 2, 48)]
+    #[case::ten_lists_of_ten(&[10; 10], 2, 170)]
+    #[case::three_wise(&[3, 3, 3, 3], 3, 35)]
+    #[case::strength_as_lists(&[2, 3], 2, 6)]
+    #[case::strength_more_than_lists(&[2, 3], 4, 6)]
+    fn cover_all_tuples(#[case] sizes: &[usize], #[case] strength: usize, #[case] max_rows: usize) {
+        let rows = covering_array(sizes, strength);
+
+        let strength = strength.min(sizes.len());
+        assert_cover(&rows, &all_tuples(sizes, strength), strength);
+        assert!(
+            rows.len() <= max_rows,
+            "Too many rows: {} > {}",
+            rows.len(),
+            max_rows
+        );
+    }
+
+    #[test]
+    fn be_stable() {
+        assert_eq!(
+            vec![vec![0, 0, 0], vec![0, 1, 1], vec![1, 0, 1], vec![1, 1, 0]],
+            covering_array(&[2, 2, 2], 2)
+        );
+    }
+
+    #[test]
+    fn select_candidates_that_cover_allowed_tuples() {
+        let candidates = product(&[3, 3, 3])
+            .into_iter()
+            .filter(|r| !(r[0] == 1 && r[1] == 2))
+            .collect::<Vec<_>>();
+
+        let rows = covering_subset(&candidates, 2);
+
+        let expected = candidates
+            .iter()
+            .flat_map(|r| tuples_of(r, 2))
+            .collect::<BTreeSet<_>>();
+        assert_cover(&rows, &expected, 2);
+        assert!(rows.iter().all(|r| candidates.contains(r)));
+        assert!(rows.len() < candidates.len());
+    }
+}
@@ -0,0 +1,60 @@
+/// Render the checks of the cases' expected results (`#[case(args => expected)]`): the test
+/// function's result is checked by the generated test that returns `()`.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Attribute};
+
+use crate::parse::testcase::Expected;
+
+/// `=> panics` is just a `#[should_panic]` test.
+pub(crate) fn should_panic_attr(expected: Option<&Expected>) -> Option<Attribute> {
+    match expected? {
+        Expected::Panics(Some(message)) => {
+            Some(parse_quote! { #[should_panic(expected = #message)] })
+        }
+        Expected::Panics(None) => Some(parse_quote! { #[should_panic] }),
+        _ => None,
+    }
+}
+
+/// Check the result of the test function's call `execute`.
+pub(crate) fn render_check(execute: TokenStream, expected: &Expected) -> TokenStream {
+    match expected {
+        Expected::Value(value) => quote! {
+            let __result = #execute;
+            assert_eq!(#value, __result);
+        },
+        Expected::Panics(_) => quote! {
+            let _ = #execute;
+        },
+        Expected::Matches(pattern, guard) => {
+            let guard = guard.as_ref().map(|guard| quote! { if #guard });
+            quote! {
+                match #execute {
+                    #pattern #guard => {}
+                    __result => panic!(
+                        "Expected a result that matches `{}` but got {:?}",
+                        stringify!(#pattern #guard),
+                        __result
+                    ),
+                }
+            }
+        }
+        Expected::Err(pattern) => {
+            let pattern = match pattern {
+                Some(pattern) => quote! { #pattern },
+                None => quote! { _ },
+            };
+            quote! {
+                match #execute {
+                    std::result::Result::Err(#pattern) => {}
+                    __result => panic!(
+                        "Expected an error that matches `{}` but got {:?}",
+                        stringify!(#pattern),
+                        __result
+                    ),
+                }
+            }
+        }
+    }
+}
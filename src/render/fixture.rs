@@ -1,111 +1,1293 @@
-use proc_macro2::{Span, TokenStream};
-use syn::{parse_quote, Ident, ItemFn};
+use std::collections::HashMap;
 
-use quote::quote;
+use proc_macro2::TokenStream;
+use syn::{
+    ext::IdentExt, parse_quote, spanned::Spanned, Expr, FnArg, Ident, ItemFn, Path, ReturnType,
+    Type, Visibility,
+};
 
-use super::{generics_clean_up, inject, render_exec_call};
-use crate::parse::fixture::FixtureInfo;
+use quote::{format_ident, quote, quote_spanned};
+
+use super::{generics_clean_up, inject, params, render_await_futures, render_exec_call};
+use crate::parse::{
+    fixture::FixtureInfo, future::AwaitedFutures, params::ParamsDim, vlist::ValueList,
+};
+use crate::refident::MaybeIdent;
 use crate::resolver::{self, Resolver};
-use crate::utils::{fn_args, fn_args_idents};
+use crate::utils::{contains_impl_trait, fn_args, fn_args_idents};
+
+fn wrap_return_type_as_static_ref(rt: ReturnType) -> ReturnType {
+    match rt {
+        syn::ReturnType::Type(_, t) => parse_quote! {
+            -> &'static #t
+        },
+        o => o,
+    }
+}
+
+fn wrap_call_impl_with_call_once_impl(call_impl: TokenStream, rt: &ReturnType) -> TokenStream {
+    match rt {
+        syn::ReturnType::Type(_, t) => {
+            // Spanned on the return type to point the user there if it isn't `Sync`
+            let value = quote_spanned! {t.span() =>
+                static VALUE: __OnceCell<#t> = __OnceCell(std::cell::UnsafeCell::new(None));
+            };
+            quote! {
+                struct __OnceCell<T>(std::cell::UnsafeCell<Option<T>>);
+                unsafe impl<T: Sync> Sync for __OnceCell<T> {}
+
+                static ONCE: std::sync::Once = std::sync::Once::new();
+                #value
+
+                ONCE.call_once(|| unsafe { *VALUE.0.get() = Some(#call_impl) });
+                unsafe { (*VALUE.0.get()).as_ref().unwrap() }
+            }
+        }
+        _ => quote! {
+            static ONCE: std::sync::Once = std::sync::Once::new();
+
+            ONCE.call_once(|| #call_impl);
+        },
+    }
+}
+
+/// If the return type is a `rstest_fixtures::Fixture<T, G>` (a fixture with teardown) return
+/// `T`. The type is recognized just by this path: a `Fixture` type imported by `use` is a plain
+/// value.
+pub(crate) fn fixture_value_type(rt: &ReturnType) -> Option<Type> {
+    let t = match rt {
+        ReturnType::Type(_, t) => t,
+        _ => return None,
+    };
+    let segments = match t.as_ref() {
+        Type::Path(tp) if tp.qself.is_none() => &tp.path.segments,
+        _ => return None,
+    };
+    let segment = match (segments.first(), segments.last()) {
+        (Some(krate), Some(segment))
+            if segments.len() == 2
+                && krate.ident == "rstest_fixtures"
+                && krate.arguments.is_empty()
+                && segment.ident == "Fixture" =>
+        {
+            segment
+        }
+        _ => return None,
+    };
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 2 => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(t)) => Some(t.clone()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn return_type_or_unit(rt: &ReturnType) -> Type {
+    match rt {
+        ReturnType::Type(_, t) => t.as_ref().clone(),
+        ReturnType::Default => parse_quote! { () },
+    }
+}
+
+/// The `#[teardown]` fixtures keep their value till the teardown (`impl FnOnce(T)` takes it):
+/// they lend it or give a clone of it.
+fn teardown_fixture_type(value_type: Type) -> Type {
+    parse_quote! {
+        ::rstest_fixtures::Fixture<
+            #value_type,
+            impl ::rstest_fixtures::TearDown,
+            impl FnOnce(#value_type)
+        >
+    }
+}
+
+/// The type returned by `default()`, `partial_n()` and `build()` fixture methods.
+fn resolve_output(rt: ReturnType, info: &FixtureInfo) -> ReturnType {
+    if info.attributes.is_once() {
+        wrap_return_type_as_static_ref(rt)
+    } else {
+        rt
+    }
+}
+
+/// The type injected by the `__guarded_*()` fixture methods: the fixture itself and the
+/// guards of the fixtures that it uses. The `#[teardown]` fixtures are a `Fixture` that keeps
+/// the value, the ones that return a `Fixture` are the returned one and the other ones just the
+/// value in an `Option`. They don't await: the value of an async fixture is its future.
+fn resolve_guarded_output(rt: ReturnType, fixture: &ItemFn, info: &FixtureInfo) -> ReturnType {
+    let own: Type = if info.attributes.get_teardown().is_some() {
+        teardown_fixture_type(return_type_or_unit(&rt))
+    } else if fixture_value_type(&rt).is_some() {
+        return_type_or_unit(&rt)
+    } else {
+        let mut value_type = return_type_or_unit(&resolve_output(rt, info));
+        if fixture.sig.asyncness.is_some() {
+            value_type = parse_quote! { impl std::future::Future<Output = #value_type> };
+        }
+        parse_quote! { Option<#value_type> }
+    };
+    parse_quote! { -> (#own, impl Sized) }
+}
+
+fn has_teardown(fixture: &ItemFn, info: &FixtureInfo) -> bool {
+    info.attributes.get_teardown().is_some() || fixture_value_type(&fixture.sig.output).is_some()
+}
+
+/// The fixtures injected by their `__guarded_*()` functions: the ones with a teardown, the
+/// shared ones and the ones that use other fixtures, because they keep their guards (the
+/// macro cannot know if the used fixtures have a teardown). The other ones are injected by
+/// their public functions (see `inject::render_fallback()`).
+fn is_guarded(fixture: &ItemFn, info: &FixtureInfo, resolver: &impl Resolver) -> bool {
+    has_teardown(fixture, info)
+        || info.attributes.get_shared().is_some()
+        || info.data.fixtures().any(|f| f.shared)
+        || info.is_parametrized()
+        || !inject::fixtures_paths(fixture.sig.inputs.iter(), resolver).is_empty()
+}
+
+/// The functions that inject a guarded fixture: `__guarded_*()` or, for the parametrized
+/// fixtures, `__params_*()` that take also the indexes of the values to use (`__params`, see
+/// `resolve_own_values()` and `resolve_params()`). The public functions use the first values.
+struct Injecting(bool);
+
+impl Injecting {
+    fn new(info: &FixtureInfo) -> Self {
+        Self(info.is_parametrized())
+    }
+
+    /// The name of the function that injects by the fixture's `member` (`default`, `build` or
+    /// `partial_n`).
+    fn name(&self, member: &str) -> String {
+        match self.0 {
+            true => format!("__params_{}", member),
+            false => format!("__guarded_{}", member),
+        }
+    }
+
+    /// The indexes' parameter.
+    fn param(&self) -> TokenStream {
+        match self.0 {
+            true => quote! { __params: &[usize], },
+            false => Default::default(),
+        }
+    }
+
+    /// Give the indexes to another injecting function.
+    fn forward(&self) -> TokenStream {
+        match self.0 {
+            true => quote! { __params, },
+            false => Default::default(),
+        }
+    }
+
+    /// The indexes of the first values of the fixture `name`.
+    fn first(&self, name: &Ident) -> TokenStream {
+        match self.0 {
+            true => quote! { &[0usize; #name::__PARAMS], },
+            false => Default::default(),
+        }
+    }
+}
+
+/// Pair the value returned by `get()` (the fixtures without teardown keep it in an `Option`)
+/// with the guards of the injected fixtures in reverse order: tuples drop their fields in
+/// order, so the teardown of the fixture itself comes first and then the injected ones in
+/// reverse dependency order.
+fn compose_guards(
+    call_get: TokenStream,
+    guards: &[Ident],
+    fixture: &ItemFn,
+    info: &FixtureInfo,
+) -> TokenStream {
+    let guards = guards.iter().rev();
+    let call_get = match info.attributes.get_teardown() {
+        Some((_, teardown)) => quote! { ::rstest_fixtures::with_teardown(#call_get, #teardown) },
+        None if fixture_value_type(&fixture.sig.output).is_some() => call_get,
+        None => quote! { Some(#call_get) },
+    };
+    quote! {
+        (#call_get, (#(#guards,)*))
+    }
+}
+
+/// Get the value by calling a `__guarded_*()` method: the public functions return just the
+/// value, so the guards of the fixtures that it uses are leaked to keep it valid and the
+/// `#[teardown]` fixtures give it without calling the teardown.
+fn render_unguarded_call(
+    guarded_call: TokenStream,
+    fixture: &ItemFn,
+    info: &FixtureInfo,
+) -> TokenStream {
+    let value = if info.attributes.get_teardown().is_some() {
+        quote! { fixture.release() }
+    } else if fixture_value_type(&fixture.sig.output).is_some() {
+        quote! { fixture }
+    } else if fixture.sig.asyncness.is_some() {
+        quote! { fixture.unwrap().await }
+    } else {
+        quote! { fixture.unwrap() }
+    };
+    quote! {
+        let (fixture, guards) = #guarded_call;
+        std::mem::forget(guards);
+        #value
+    }
+}
+
+/// Give the injected value from the fixture returned by a `__guarded_*()` method (the
+/// fixtures without teardown use the ones in `inject::render_fallback()`): the `#[teardown]`
+/// fixtures keep it, so they lend it to the references (`__lend()`) and give a clone of it to
+/// the other arguments (`__take()`), the other ones give it and keep just the guard.
+fn render_take_impl(fixture: &ItemFn, info: &FixtureInfo, members: &Members) -> TokenStream {
+    let vis = &members.vis;
+    let (take, lend) = (members.ident("__take"), members.ident("__lend"));
+    if info.attributes.get_teardown().is_some() {
+        quote! {
+            #[doc(hidden)]
+            #vis fn #take<__T: Clone, __G: ::rstest_fixtures::TearDown, __F: FnOnce(__T), __D>(
+                fixture: &mut (::rstest_fixtures::Fixture<__T, __G, __F>, __D),
+            ) -> __T {
+                let value = fixture.0.value_mut();
+                let teardown_value = value.clone();
+                std::mem::replace(value, teardown_value)
+            }
+
+            #[doc(hidden)]
+            #vis fn #lend<__T, __G: ::rstest_fixtures::TearDown, __F: FnOnce(__T), __D>(
+                fixture: &mut (::rstest_fixtures::Fixture<__T, __G, __F>, __D),
+            ) -> &mut __T {
+                fixture.0.value_mut()
+            }
+        }
+    } else if has_teardown(fixture, info) {
+        quote! {
+            #[doc(hidden)]
+            #vis fn #take<__T, __G: ::rstest_fixtures::TearDown, __D>(
+                fixture: &mut (::rstest_fixtures::Fixture<__T, __G>, __D),
+            ) -> __T {
+                fixture.0.take()
+            }
+
+            #[doc(hidden)]
+            #vis fn #lend<__T, __G: ::rstest_fixtures::TearDown, __D>(
+                fixture: &mut (::rstest_fixtures::Fixture<__T, __G>, __D),
+            ) -> __T {
+                fixture.0.take()
+            }
+        }
+    } else {
+        Default::default()
+    }
+}
+
+/// The type of the fallback given to a `__guarded_*()` function (see
+/// `inject::render_fallback()`): it's never called but it types the caller's closure. It's
+/// the public function's pointer if its output can be written here, or a type parameter
+/// otherwise (the `inputs` cannot be `impl Trait`). Return the type parameters, the type and
+/// the fallback given by the public function: its path or, if it's not a pointer, a closure
+/// that doesn't refer the function's output type (it would be a cycle).
+fn fallback_type(
+    inputs: &[Type],
+    output: &ReturnType,
+    fixture: &ItemFn,
+    public: Path,
+) -> (Vec<syn::GenericParam>, Type, TokenStream) {
+    let output_type = return_type_or_unit(output);
+    if fixture.sig.asyncness.is_some() || contains_impl_trait(&output_type) {
+        let ignored = inputs.iter().map(|_| quote! { _ });
+        (
+            vec![
+                parse_quote! { __F: FnOnce(#(#inputs),*) -> __R },
+                parse_quote! { __R },
+            ],
+            parse_quote! { __F },
+            quote! { |#(#ignored),*| () },
+        )
+    } else {
+        (
+            vec![],
+            parse_quote! { fn(#(#inputs),*) -> #output_type },
+            quote! { #public },
+        )
+    }
+}
+
+/// The fixture's functions take the `#[future(awt)]` arguments as futures and await them
+/// before call the fixture.
+fn sign_args(fixture: &ItemFn, awaited: &AwaitedFutures) -> Vec<FnArg> {
+    fn_args(fixture)
+        .cloned()
+        .map(|mut arg| {
+            let is_awaited = arg
+                .maybe_ident()
+                .map(|ident| awaited.args.contains(ident))
+                .unwrap_or_default();
+            match &mut arg {
+                FnArg::Typed(t) if is_awaited => {
+                    let ty = &t.ty;
+                    t.ty = parse_quote! { impl std::future::Future<Output = #ty> };
+                }
+                _ => {}
+            }
+            arg
+        })
+        .collect()
+}
+
+/// Resolve the argument that take the fixture's values by the index given to the fixture: the
+/// first one of `__params`.
+fn resolve_own_values(value_list: Option<&ValueList>) -> HashMap<String, Expr> {
+    value_list
+        .map(|vlist| {
+            let indexes = 0..vlist.values.len();
+            let values = &vlist.values;
+            (
+                vlist.arg.to_string(),
+                parse_quote! {
+                    match __params[0] {
+                        #(#indexes => #values,)*
+                        _ => unreachable!("Invalid fixture parameter's index")
+                    }
+                },
+            )
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Resolve the `#[params]` arguments by the `__params_*()` functions of their fixtures: each
+/// one takes its `__PARAMS` indexes of `__params`, that follow the own values' one.
+fn resolve_params(info: &FixtureInfo) -> HashMap<String, Expr> {
+    let own = info.data.value_list().is_some() as usize;
+    let mut offset = quote! { #own };
+    info.attributes
+        .params()
+        .map(|arg| {
+            let path = resolver::fixtures::name_path(params::fixture_name(arg));
+            let count = resolver::fixtures::member(&path, "__PARAMS");
+            let indexes = parse_quote! { &__params[#offset..][..#count] };
+            offset = quote! { #offset + #count };
+            let call = match info.data.fixtures().find(|f| &f.name == arg) {
+                Some(f) => resolver::fixtures::render_params_call(
+                    &path,
+                    &indexes,
+                    &f.positional.0,
+                    &f.named,
+                ),
+                None => resolver::fixtures::render_params_call(&path, &indexes, &[], &[]),
+            };
+            (arg.to_string(), call)
+        })
+        .collect()
+}
+
+/// `__PARAMS`: how many indexes the parametrized fixture takes, one for its own values (if
+/// any) and the ones taken by the parametrized fixtures that it uses.
+fn render_params_count(value_list: Option<&ValueList>, params: &[Ident]) -> TokenStream {
+    let fixtures = params.iter().map(params::fixture_name).collect::<Vec<_>>();
+    let own = value_list.is_some() as usize;
+    quote! {
+        #[doc(hidden)]
+        pub const __PARAMS: usize = #own #(+ #fixtures::__PARAMS)*;
+    }
+}
+
+/// The parametrized fixtures are injected by their `__params_*()` functions: the
+/// `__guarded_*()` ones, called for the arguments without `#[params]` (see
+/// `resolver::fixtures::render_call()`), are defined just to reject the given fallback by a
+/// trait that names the error.
+fn render_params_stubs(fixture: &ItemFn, sign_args: &[FnArg]) -> TokenStream {
+    let name = &fixture.sig.ident;
+    let vis = &fixture.vis;
+    let check = format_ident!(
+        "{}_is_a_parametrized_fixture_mark_the_argument_with_params",
+        name.unraw()
+    );
+    let cache_type = resolver::fixtures::shared_cache_type();
+    let partials = (1..=sign_args.len()).map(|n| {
+        let partial = format_ident!("__guarded_partial_{}", n);
+        let types = (0..n)
+            .map(|i| format_ident!("__A{}", i))
+            .collect::<Vec<_>>();
+        quote! {
+            #[doc(hidden)]
+            pub fn #partial<#(#types,)* __V, __F: #check>(
+                _: #cache_type,
+                #(_: #types,)*
+                _: __F
+            ) -> (Option<__V>, ()) {
+                unreachable!()
+            }
+        }
+    });
+    let builder = builder_args(fixture, sign_args).map(|args| {
+        let builder = format_ident!("__{}_builder", name);
+        let slots = (0..args.len())
+            .map(|i| format_ident!("__A{}", i))
+            .collect::<Vec<_>>();
+        quote! {
+            impl<#(#slots),*> #builder<#(#slots),*> {
+                #[doc(hidden)]
+                pub fn __guarded_build<__V, __F: #check>(
+                    self,
+                    _: #cache_type,
+                    _: __F
+                ) -> (Option<__V>, ()) {
+                    unreachable!()
+                }
+            }
+        }
+    });
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #vis trait #check {}
+
+        impl #name {
+            #[doc(hidden)]
+            pub fn __guarded_default<__V, __F: #check>(_: #cache_type, _: __F) -> (Option<__V>, ()) {
+                unreachable!()
+            }
+
+            #(#partials)*
+        }
+
+        #builder
+    }
+}
+
+fn render_params_handshake(fixture: &ItemFn, info: &FixtureInfo) -> TokenStream {
+    let name = &fixture.sig.ident;
+    let params = info.attributes.params().cloned().collect::<Vec<_>>();
+    let value_list = info.data.value_list();
+    if value_list.is_none() && params.is_empty() {
+        return Default::default();
+    }
+    let dims = value_list
+        .map(|vlist| {
+            ParamsDim::new(
+                name.clone(),
+                vlist.values.iter().map(params::value_label).collect(),
+            )
+        })
+        .into_iter()
+        .collect();
+    let params_count = render_params_count(value_list, &params);
+    let stubs = render_params_stubs(fixture, &sign_args(fixture, &info.attributes.awaited()));
+    let handshake = params::render_fixture_handshake(&fixture.vis, name, dims, &params);
+    quote! {
+        impl #name {
+            #params_count
+        }
+
+        #stubs
+
+        #handshake
+    }
+}
+
+/// The names of the fixture's functions: the ones of a fixture defined at module level are in
+/// the `impl` of the fixture's struct (`name::get()`) and the ones of an associated fixture
+/// are in the `impl` of its type (`Type::__name_get()`) and have the fixture's visibility.
+struct Members<'a> {
+    name: &'a Ident,
+    associated: bool,
+    vis: Visibility,
+}
+
+impl Members<'_> {
+    fn ident(&self, member: &str) -> Ident {
+        if self.associated {
+            resolver::fixtures::associated_member(self.name, member)
+        } else {
+            format_ident!("{}", member)
+        }
+    }
+
+    fn call(&self, member: &str) -> Path {
+        let ident = self.ident(member);
+        parse_quote! { Self::#ident }
+    }
+
+    fn fixture(&self) -> Path {
+        let name = self.name;
+        if self.associated {
+            parse_quote! { Self::#name }
+        } else {
+            parse_quote! { #name }
+        }
+    }
+}
 
 pub(crate) fn render<'a>(fixture: ItemFn, info: FixtureInfo) -> TokenStream {
     let name = &fixture.sig.ident;
+    let visibility = &fixture.vis;
+    let members = Members {
+        name,
+        associated: false,
+        vis: parse_quote! { pub },
+    };
+    let functions = render_functions(&fixture, &info, &members);
+    let sign_args = sign_args(&fixture, &info.attributes.awaited());
+    let resolver = fixture_resolver(&info);
+    let (with, builder) = render_builder(&fixture, &sign_args, &resolver, &info);
+    let params_handshake = render_params_handshake(&fixture, &info);
+
+    quote! {
+        #[allow(non_camel_case_types)]
+        #visibility struct #name {}
+
+        impl #name {
+            #functions
+
+            #with
+        }
+
+        #[allow(dead_code)]
+        #fixture
+
+        #builder
+
+        #params_handshake
+    }
+}
+
+/// Render the fixture defined as associated function of the `impl` that contains it: the
+/// fixture's functions are defined in the same `impl` and prefixed by the fixture's name.
+pub(crate) fn render_associated(fixture: ItemFn, info: FixtureInfo) -> TokenStream {
+    let members = Members {
+        name: &fixture.sig.ident,
+        associated: true,
+        vis: fixture.vis.clone(),
+    };
+    let functions = render_functions(&fixture, &info, &members);
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = &fixture;
+
+    quote! {
+        #(#attrs)*
+        #[allow(dead_code)]
+        #vis #sig #block
+
+        #functions
+    }
+}
+
+fn fixture_resolver<'a>(info: &'a FixtureInfo) -> impl Resolver + 'a {
+    (
+        resolve_own_values(info.data.value_list()),
+        (
+            resolve_params(info),
+            (
+                resolver::fixtures::get(info.data.fixtures()),
+                resolver::values::get(info.data.values()),
+            ),
+        ),
+    )
+}
+
+/// The `get()`, `default()` and `partial_n()` fixture's functions. The guarded fixtures (see
+/// `is_guarded()`) have also their `__guarded_*()` versions, used to inject the fixture, and
+/// the `__take()` and `__lend()` functions if they have a teardown.
+fn render_functions(fixture: &ItemFn, info: &FixtureInfo, members: &Members) -> TokenStream {
     let asyncness = &fixture.sig.asyncness.clone();
     let vargs = fn_args_idents(&fixture).cloned().collect::<Vec<_>>();
     let args = &vargs;
-    let orig_args = &fixture.sig.inputs;
+    let awaited = info.attributes.awaited();
+    let sign_args = sign_args(&fixture, &awaited);
     let orig_attrs = &fixture.attrs;
     let generics = &fixture.sig.generics;
-    let default_output = info
+    let default_type = info
         .attributes
         .extract_default_type()
         .unwrap_or(fixture.sig.output.clone());
+    let default_output = resolve_output(default_type.clone(), info);
     let default_generics =
         generics_clean_up(&fixture.sig.generics, std::iter::empty(), &default_output);
     let default_where_clause = &default_generics.where_clause;
     let where_clause = &fixture.sig.generics.where_clause;
-    let output = &fixture.sig.output;
-    let visibility = &fixture.vis;
-    let resolver = (
-        resolver::fixtures::get(info.data.fixtures()),
-        resolver::values::get(info.data.values()),
-    );
+    let mut output = fixture.sig.output.clone();
+    let resolver = fixture_resolver(info);
+    let guarded = is_guarded(fixture, info, &resolver);
     let generics_idents = generics
         .type_params()
-        .map(|tp| &tp.ident)
-        .cloned()
+        .map(|tp| &tp.ident)
+        .cloned()
+        .collect::<Vec<_>>();
+    let partials = (1..=sign_args.len())
+        .map(|n| render_partial_impl(fixture, &sign_args, n, &resolver, info, members, guarded));
+
+    let await_futures = render_await_futures(&awaited);
+    let mut call_impl = render_exec_call(members.fixture(), args, asyncness.is_some());
+    let injecting = Injecting::new(info);
+    let (get, default, guarded_default) = (
+        members.ident("get"),
+        members.ident("default"),
+        members.ident(&injecting.name("default")),
+    );
+    let vis = &members.vis;
+
+    if info.attributes.is_once() {
+        call_impl = wrap_call_impl_with_call_once_impl(call_impl, &output);
+        output = wrap_return_type_as_static_ref(output);
+    }
+
+    let get_impl = quote! {
+        #(#orig_attrs)*
+        #[allow(unused_mut)]
+        #vis #asyncness fn #get #generics (#(#sign_args),*) #output #where_clause {
+            #await_futures
+            #call_impl
+        }
+    };
+    if !guarded {
+        let (inject, _) = inject::resolve_aruments_and_guards(
+            fixture.sig.inputs.iter(),
+            &resolver,
+            &generics_idents,
+        );
+        let call_get = render_exec_call(members.call("get"), args, asyncness.is_some());
+        return quote! {
+            #get_impl
+
+            #vis #asyncness fn #default #default_generics () #default_output #default_where_clause {
+                #inject
+                #call_get
+            }
+
+            #(#partials)*
+        };
+    }
+
+    let guarded_output = resolve_guarded_output(default_type, fixture, info);
+    let mut guarded_generics =
+        generics_clean_up(&fixture.sig.generics, std::iter::empty(), &guarded_output);
+    let (fallback_params, fallback, default_fallback) =
+        fallback_type(&[], &default_output, fixture, members.call("default"));
+    guarded_generics.params.extend(fallback_params);
+    let guarded_where_clause = &guarded_generics.where_clause;
+    let (inject, call_get) = render_inject_and_call_get(
+        fixture,
+        fixture.sig.inputs.iter(),
+        &resolver,
+        &generics_idents,
+        info,
+        members,
+    );
+    let mut guarded_body = quote! {
+        #inject
+        #call_get
+    };
+    if info.attributes.get_shared().is_some() {
+        let name = members.name;
+        let shared = resolver::fixtures::render_shared(
+            &parse_quote! { Self::#name },
+            parse_quote! {
+                {
+                    #inject
+                    #call_get.0.unwrap()
+                }
+            },
+        );
+        guarded_body = quote! { (Some(#shared), ()) };
+    }
+    let (params, first_params) = (injecting.param(), injecting.first(members.name));
+    let default_call = render_unguarded_call(
+        quote! { Self::#guarded_default(#first_params Default::default(), #default_fallback) },
+        fixture,
+        info,
+    );
+    let take = render_take_impl(fixture, info, members);
+    let (cache, cache_type) = (
+        resolver::fixtures::shared_cache(),
+        resolver::fixtures::shared_cache_type(),
+    );
+
+    quote! {
+        #get_impl
+
+        #vis #asyncness fn #default #default_generics () #default_output #default_where_clause {
+            #default_call
+        }
+
+        #[doc(hidden)]
+        #vis fn #guarded_default #guarded_generics (#params #cache: #cache_type, _: #fallback) #guarded_output #guarded_where_clause {
+            #guarded_body
+        }
+
+        #(#partials)*
+
+        #take
+    }
+}
+
+fn render_inject_and_call_get<'a>(
+    fixture: &ItemFn,
+    args: impl Iterator<Item = &'a syn::FnArg>,
+    resolver: &impl Resolver,
+    generics_idents: &[Ident],
+    info: &FixtureInfo,
+    members: &Members,
+) -> (TokenStream, TokenStream) {
+    let fixture_args = fn_args_idents(fixture).cloned().collect::<Vec<_>>();
+    // Don't await: the guarded value of an async fixture is its future
+    let call_get = render_exec_call(members.call("get"), &fixture_args, false);
+    let (inject, guards) = inject::resolve_aruments_and_guards(args, resolver, generics_idents);
+    (inject, compose_guards(call_get, &guards, fixture, info))
+}
+
+fn render_partial_impl(
+    fixture: &ItemFn,
+    sign_args: &[FnArg],
+    n: usize,
+    resolver: &impl Resolver,
+    info: &FixtureInfo,
+    members: &Members,
+    guarded: bool,
+) -> TokenStream {
+    let partial_type = info.attributes.extract_partial_type(n);
+    let by_builder = !members.associated
+        && partial_type.is_none()
+        && builder_args(fixture, sign_args).map_or(false, |args| {
+            let given = (0..args.len()).map(|i| i < n).collect::<Vec<_>>();
+            can_build(fixture, &args, &given)
+        });
+    let partial_type = partial_type.unwrap_or(fixture.sig.output.clone());
+    let output = resolve_output(partial_type.clone(), info);
+
+    let generics = generics_clean_up(&fixture.sig.generics, sign_args.iter().take(n), &output);
+    let where_clause = &generics.where_clause;
+    let genercs_idents = generics
+        .type_params()
+        .map(|tp| &tp.ident)
+        .cloned()
+        .collect::<Vec<_>>();
+    let asyncness = &fixture.sig.asyncness;
+    let values = sign_args
+        .iter()
+        .take(n)
+        .filter_map(MaybeIdent::maybe_ident)
+        .collect::<Vec<_>>();
+    let name = members.ident(&format!("partial_{}", n));
+    let vis = &members.vis;
+    let sign_args = sign_args.iter().take(n).collect::<Vec<_>>();
+
+    if !guarded {
+        let (inject, _) = inject::resolve_aruments_and_guards(
+            fixture.sig.inputs.iter().skip(n),
+            resolver,
+            &genercs_idents,
+        );
+        let fixture_args = fn_args_idents(fixture).cloned().collect::<Vec<_>>();
+        let call_get = render_exec_call(members.call("get"), &fixture_args, asyncness.is_some());
+        return quote! {
+            #[allow(unused_mut)]
+            #vis #asyncness fn #name #generics (#(#sign_args),*) #output #where_clause {
+                #inject
+                #call_get
+            }
+        };
+    }
+
+    let guarded_output = resolve_guarded_output(partial_type, fixture, info);
+    let mut guarded_generics = generics_clean_up(
+        &fixture.sig.generics,
+        sign_args.iter().cloned(),
+        &guarded_output,
+    );
+    // The fallback's type refers the arguments' types: the `impl Trait` ones become type
+    // parameters
+    let mut inputs = vec![];
+    let guarded_args = sign_args
+        .iter()
+        .enumerate()
+        .map(|(i, &arg)| match arg {
+            FnArg::Typed(t) => {
+                let (ty, params) = name_impl_traits(&t.ty, i);
+                guarded_generics
+                    .params
+                    .extend(params.into_iter().map(syn::GenericParam::Type));
+                inputs.push(ty.clone());
+                let mut t = t.clone();
+                t.ty = Box::new(ty);
+                FnArg::Typed(t)
+            }
+            arg => arg.clone(),
+        })
+        .collect::<Vec<_>>();
+    let (fallback_params, fallback, partial_fallback) = fallback_type(
+        &inputs,
+        &output,
+        fixture,
+        members.call(&format!("partial_{}", n)),
+    );
+    guarded_generics.params.extend(fallback_params);
+    let guarded_where_clause = &guarded_generics.where_clause;
+    let (cache, cache_type) = (
+        resolver::fixtures::shared_cache(),
+        resolver::fixtures::shared_cache_type(),
+    );
+
+    let setters = (0..n)
+        .map(|i| format_ident!("__arg_{}", i))
+        .collect::<Vec<_>>();
+    let injecting = Injecting::new(info);
+    let (params, forward_params, first_params) = (
+        injecting.param(),
+        injecting.forward(),
+        injecting.first(members.name),
+    );
+    let body = if by_builder {
+        // The fixture's builder resolves the other arguments
+        let build = format_ident!("{}", injecting.name("build"));
+        quote! {
+            Self::builder()#(.#setters(#values))*.#build(#forward_params #cache, |builder| builder.build())
+        }
+    } else {
+        let (inject, call_get) = render_inject_and_call_get(
+            fixture,
+            fixture.sig.inputs.iter().skip(n),
+            resolver,
+            &genercs_idents,
+            info,
+            members,
+        );
+        quote! {
+            #inject
+            #call_get
+        }
+    };
+
+    let guarded = members.ident(&injecting.name(&format!("partial_{}", n)));
+    let call = if by_builder {
+        let awaited = asyncness.map(|_| quote! { .await });
+        quote! { Self::builder()#(.#setters(#values))*.build()#awaited }
+    } else {
+        render_unguarded_call(
+            quote! { Self::#guarded(#first_params Default::default(), #(#values,)* #partial_fallback) },
+            fixture,
+            info,
+        )
+    };
+
+    quote! {
+        #[allow(unused_mut)]
+        #vis #asyncness fn #name #generics (#(#sign_args),*) #output #where_clause {
+            #call
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_mut)]
+        #vis fn #guarded #guarded_generics (#params #cache: #cache_type, #(#guarded_args,)* _: #fallback) #guarded_output #guarded_where_clause {
+            #body
+        }
+    }
+}
+
+/// `ty` refers some of the given type parameters.
+fn uses_type_params(ty: &Type, type_params: &[&Ident]) -> bool {
+    use syn::visit::Visit;
+    struct Finder<'a>(&'a [&'a Ident], bool);
+    impl<'ast> Visit<'ast> for Finder<'_> {
+        fn visit_type_path(&mut self, i: &'ast syn::TypePath) {
+            if i.qself.is_none() {
+                if let Some(ident) = i.path.get_ident() {
+                    self.1 |= self.0.contains(&ident);
+                }
+            }
+            syn::visit::visit_type_path(self, i)
+        }
+    }
+    let mut finder = Finder(type_params, false);
+    finder.visit_type(ty);
+    finder.1
+}
+
+/// Replace every `impl Trait` in `ty` by a new type parameter (`__I{slot}_{n}`): return the
+/// new type and the type parameters.
+fn name_impl_traits(ty: &Type, slot: usize) -> (Type, Vec<syn::TypeParam>) {
+    use syn::visit_mut::VisitMut;
+    struct Namer(usize, Vec<syn::TypeParam>);
+    impl VisitMut for Namer {
+        fn visit_type_mut(&mut self, i: &mut Type) {
+            syn::visit_mut::visit_type_mut(self, i);
+            if let Type::ImplTrait(impl_trait) = i {
+                let name = format_ident!("__I{}_{}", self.0, self.1.len());
+                let bounds = &impl_trait.bounds;
+                self.1.push(parse_quote! { #name: #bounds });
+                *i = parse_quote! { #name };
+            }
+        }
+    }
+    let mut ty = ty.clone();
+    let mut namer = Namer(slot, vec![]);
+    namer.visit_type_mut(&mut ty);
+    (ty, namer.1)
+}
+
+/// An argument of the fixture's builder: the fixed type ones are kept in an `Option` and the
+/// other ones (`impl Trait` or that use the fixture's type parameters) are typed by the given
+/// value: `(value,)` or `()` if not given.
+struct BuilderArg<'a> {
+    ident: &'a Ident,
+    arg: &'a FnArg,
+    ty: &'a Type,
+    fixed: bool,
+}
+
+/// The arguments of the fixture's builder or `None` if some argument has no name.
+fn builder_args<'a>(fixture: &ItemFn, sign_args: &'a [FnArg]) -> Option<Vec<BuilderArg<'a>>> {
+    let type_params = fixture
+        .sig
+        .generics
+        .type_params()
+        .map(|tp| &tp.ident)
+        .collect::<Vec<_>>();
+    sign_args
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Typed(t) => Some(BuilderArg {
+                ident: arg.maybe_ident()?,
+                arg,
+                ty: &t.ty,
+                fixed: !contains_impl_trait(&t.ty) && !uses_type_params(&t.ty, &type_params),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `build()` resolves the arguments that are not `given` like `default()` does: it cannot
+/// if one of them uses a type parameter fixed by the given ones or by the fixture's output.
+fn can_build(fixture: &ItemFn, args: &[BuilderArg], given: &[bool]) -> bool {
+    let used = |tp: &Ident, ty: &Type| uses_type_params(ty, &[tp]);
+    let fixed_params = fixture
+        .sig
+        .generics
+        .type_params()
+        .map(|tp| &tp.ident)
+        .filter(|tp| {
+            matches!(&fixture.sig.output, ReturnType::Type(_, t) if used(tp, t))
+                || args.iter().zip(given).any(|(a, &g)| g && used(tp, a.ty))
+        })
+        .collect::<Vec<_>>();
+    args.iter()
+        .zip(given)
+        .all(|(a, &g)| g || !uses_type_params(a.ty, &fixed_params))
+}
+
+/// The `build()` method of the builder with the `given` not fixed arguments: the fixed ones
+/// are resolved if not set. The guarded fixtures have also `__guarded_build()`.
+fn render_build_impl(
+    fixture: &ItemFn,
+    args: &[BuilderArg],
+    given: &[bool],
+    resolver: &impl Resolver,
+    info: &FixtureInfo,
+    guarded: bool,
+) -> TokenStream {
+    let name = &fixture.sig.ident;
+    let builder = format_ident!("__{}_builder", name);
+    let given_args = args
+        .iter()
+        .zip(given)
+        .filter(|(_, &g)| g)
+        .map(|(a, _)| a.arg)
+        .collect::<Vec<_>>();
+    let mut impl_generics = generics_clean_up(
+        &fixture.sig.generics,
+        given_args.iter().cloned(),
+        &ReturnType::Default,
+    );
+    impl_generics.where_clause = None;
+    let generics = generics_clean_up(
+        &fixture.sig.generics,
+        given_args.iter().cloned(),
+        &fixture.sig.output,
+    );
+    // The type parameters used just by the output
+    let build_generics = generics
+        .type_params()
+        .filter(|tp| impl_generics.type_params().all(|it| it.ident != tp.ident))
+        .collect::<Vec<_>>();
+    let where_clause = &generics.where_clause;
+    let output = resolve_output(fixture.sig.output.clone(), info);
+    let asyncness = &fixture.sig.asyncness;
+    let types = args
+        .iter()
+        .zip(given)
+        .enumerate()
+        .map(|(i, (a, &g))| match (a.fixed, g) {
+            (true, _) => a.ty.clone(),
+            (false, true) => {
+                let (ty, params) = name_impl_traits(a.ty, i);
+                impl_generics
+                    .params
+                    .extend(params.into_iter().map(syn::GenericParam::Type));
+                parse_quote! { (#ty,) }
+            }
+            (false, false) => parse_quote! { () },
+        })
+        .collect::<Vec<Type>>();
+
+    let generics_idents = fixture
+        .sig
+        .generics
+        .type_params()
+        .map(|tp| tp.ident.clone())
         .collect::<Vec<_>>();
-    let inject = inject::resolve_aruments(fixture.sig.inputs.iter(), &resolver, &generics_idents);
-    let partials =
-        (1..=orig_args.len()).map(|n| render_partial_impl(&fixture, n, &resolver, &info));
+    let mut guards = vec![];
+    let resolve = args
+        .iter()
+        .zip(given)
+        .enumerate()
+        .map(|(i, (a, &g))| {
+            let (ident, index) = (a.ident, syn::Index::from(i));
+            if g {
+                return quote! { let #ident = self.#index.0; };
+            }
+            let (define, lend) =
+                inject::resolve_argument_and_lend(a.arg, resolver, &generics_idents);
+            if !a.fixed {
+                return match lend {
+                    Some((guard, lend)) => {
+                        guards.push(guard.clone());
+                        quote! {
+                            #define
+                            let mut #guard = #ident;
+                            let #ident = #lend(&mut #guard);
+                        }
+                    }
+                    None => quote! { #define },
+                };
+            }
+            match lend {
+                // The guard keeps the fixture just if the value is not set
+                Some((guard, lend)) => {
+                    guards.push(guard.clone());
+                    quote! {
+                        let mut #guard = None;
+                        let #ident = match self.#index {
+                            Some(#ident) => #ident,
+                            None => {
+                                #define
+                                #lend(#guard.get_or_insert(#ident))
+                            }
+                        };
+                    }
+                }
+                None => quote! {
+                    let #ident = match self.#index {
+                        Some(#ident) => #ident,
+                        None => {
+                            #define
+                            #ident
+                        }
+                    };
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+    let idents = args.iter().map(|a| a.ident).cloned().collect::<Vec<_>>();
+    if !guarded {
+        let call_get = render_exec_call(parse_quote! { #name::get }, &idents, asyncness.is_some());
+        return quote! {
+            impl #impl_generics #builder<#(#types),*> {
+                pub #asyncness fn build<#(#build_generics),*>(self) #output #where_clause {
+                    #(#resolve)*
+                    #call_get
+                }
+            }
+        };
+    }
 
-    let call_get = render_exec_call(parse_quote! { Self::get }, args, asyncness.is_some());
-    let call_impl = render_exec_call(parse_quote! { #name }, args, asyncness.is_some());
+    let plain_fixtures = inject::render_fallback(
+        args.iter()
+            .zip(given)
+            .filter(|(_, &g)| !g)
+            .map(|(a, _)| a.arg),
+        resolver,
+    );
+    let call_get = compose_guards(
+        render_exec_call(parse_quote! { #name::get }, &idents, false),
+        &guards,
+        fixture,
+        info,
+    );
+    let guarded_output = resolve_guarded_output(fixture.sig.output.clone(), fixture, info);
+    let (fallback_params, fallback, build_fallback) = fallback_type(
+        &[parse_quote! { Self }],
+        &output,
+        fixture,
+        parse_quote! { Self::build },
+    );
+    let injecting = Injecting::new(info);
+    let build = format_ident!("{}", injecting.name("build"));
+    let (params, first_params) = (injecting.param(), injecting.first(name));
+    let call = render_unguarded_call(
+        quote! { self.#build(#first_params Default::default(), #build_fallback) },
+        fixture,
+        info,
+    );
+    let (cache, cache_type) = (
+        resolver::fixtures::shared_cache(),
+        resolver::fixtures::shared_cache_type(),
+    );
 
     quote! {
-        #[allow(non_camel_case_types)]
-        #visibility struct #name {}
-
-        impl #name {
-            #(#orig_attrs)*
-            #[allow(unused_mut)]
-            pub #asyncness fn get #generics (#orig_args) #output #where_clause {
-                #call_impl
+        impl #impl_generics #builder<#(#types),*> {
+            pub #asyncness fn build<#(#build_generics),*>(self) #output #where_clause {
+                #call
             }
 
-            pub #asyncness fn default #default_generics () #default_output #default_where_clause {
-                #inject
+            #[doc(hidden)]
+            #[allow(unused_mut)]
+            pub fn #build<#(#build_generics,)* #(#fallback_params),*>(
+                self,
+                #params
+                #cache: #cache_type,
+                _: #fallback,
+            ) #guarded_output #where_clause {
+                #plain_fixtures
+                #(#resolve)*
                 #call_get
             }
-
-            #(#partials)*
         }
-
-        #[allow(dead_code)]
-        #fixture
     }
 }
 
-fn render_partial_impl(
+/// The fixture's builder: `builder()` creates it, every argument has a `with_arg()` setter
+/// (and a `__arg_n()` one for the positional values) and `build()` resolves the arguments
+/// that are not given like `default()` does. Return the `builder()` method and the builder's
+/// definition: the arguments that are not fixed (see `BuilderArg`) change the builder's type,
+/// so `build()` is implemented once for each of them: when the ones before it are given and
+/// the ones after it not (if it can resolve them).
+fn render_builder(
     fixture: &ItemFn,
-    n: usize,
+    sign_args: &[FnArg],
     resolver: &impl Resolver,
     info: &FixtureInfo,
-) -> TokenStream {
-    let output = info
-        .attributes
-        .extract_partial_type(n)
-        .unwrap_or(fixture.sig.output.clone());
-
-    let generics = generics_clean_up(&fixture.sig.generics, fn_args(fixture).take(n), &output);
-    let where_clause = &generics.where_clause;
-    let asyncness = &fixture.sig.asyncness;
-
-    let genercs_idents = generics
-        .type_params()
-        .map(|tp| &tp.ident)
-        .cloned()
+) -> (TokenStream, TokenStream) {
+    let args = match builder_args(fixture, sign_args) {
+        Some(args) => args,
+        None => return Default::default(),
+    };
+    let guarded = is_guarded(fixture, info, resolver);
+    let vis = &fixture.vis;
+    let builder = format_ident!("__{}_builder", fixture.sig.ident);
+    let slots = (0..args.len())
+        .map(|i| format_ident!("__A{}", i))
         .collect::<Vec<_>>();
-    let inject =
-        inject::resolve_aruments(fixture.sig.inputs.iter().skip(n), resolver, &genercs_idents);
+    let indexes = (0..args.len()).map(syn::Index::from).collect::<Vec<_>>();
+    let fields = args.iter().zip(slots.iter()).map(|(a, slot)| {
+        if a.fixed {
+            quote! { Option<#slot> }
+        } else {
+            quote! { #slot }
+        }
+    });
+    let fixed_slots = args
+        .iter()
+        .zip(slots.iter())
+        .filter(|(a, _)| a.fixed)
+        .map(|(_, slot)| slot);
+    let (empty_slots, empty_values): (Vec<_>, Vec<_>) = args
+        .iter()
+        .zip(slots.iter())
+        .map(|(a, slot)| {
+            if a.fixed {
+                (quote! { #slot }, quote! { None::<#slot> })
+            } else {
+                (quote! { () }, quote! { () })
+            }
+        })
+        .unzip();
+    let setters = args.iter().zip(indexes.iter()).map(|(a, index)| {
+        let setters = [
+            resolver::fixtures::builder_setter(a.ident),
+            format_ident!("__arg_{}", index.index),
+        ];
+        let attrs = [quote! {}, quote! { #[doc(hidden)] }];
+        if a.fixed {
+            let slot = &slots[index.index as usize];
+            return quote! {
+                #(
+                    #attrs
+                    pub fn #setters(mut self, value: #slot) -> Self {
+                        self.#index = Some(value);
+                        self
+                    }
+                )*
+            };
+        }
+        let set_slots = slots.iter().zip(indexes.iter()).map(|(slot, i)| {
+            if i == index {
+                quote! { (__V,) }
+            } else {
+                quote! { #slot }
+            }
+        });
+        let set_slots = quote! { #(#set_slots),* };
+        let values = indexes.iter().map(|i| {
+            if i == index {
+                quote! { (value,) }
+            } else {
+                quote! { self.#i }
+            }
+        });
+        let values = quote! { #(#values),* };
+        quote! {
+            #(
+                #attrs
+                pub fn #setters<__V>(self, value: __V) -> #builder<#set_slots> {
+                    #builder(#values)
+                }
+            )*
+        }
+    });
+    let not_fixed = args
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| !a.fixed)
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let builds = (0..=not_fixed.len())
+        .map(|n| {
+            let mut given = vec![false; args.len()];
+            for &i in not_fixed.iter().take(n) {
+                given[i] = true;
+            }
+            given
+        })
+        .filter(|given| can_build(fixture, &args, given))
+        .map(|given| render_build_impl(fixture, &args, &given, resolver, info, guarded));
 
-    let sign_args = fn_args(fixture).take(n);
-    let fixture_args = fn_args_idents(fixture).cloned().collect::<Vec<_>>();
-    let name = Ident::new(&format!("partial_{}", n), Span::call_site());
+    (
+        quote! {
+            pub fn builder<#(#fixed_slots),*>() -> #builder<#(#empty_slots),*> {
+                #builder(#(#empty_values),*)
+            }
+        },
+        quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types, dead_code)]
+            #vis struct #builder<#(#slots),*>(#(#fields),*);
 
-    let call_get = render_exec_call(
-        parse_quote! { Self::get },
-        &fixture_args,
-        asyncness.is_some(),
-    );
+            #[allow(dead_code)]
+            impl<#(#slots),*> #builder<#(#slots),*> {
+                #(#setters)*
+            }
 
-    quote! {
-        #[allow(unused_mut)]
-        pub #asyncness fn #name #generics (#(#sign_args),*) #output #where_clause {
-            #inject
-            #call_get
-        }
-    }
+            #(#builds)*
+        },
+    )
 }
 
 #[cfg(test)]
@@ -118,15 +1300,17 @@ mod should {
     use crate::parse::{Attribute, Attributes};
 
     use super::*;
-    use crate::test::assert_eq;
+    use crate::test::{assert_eq, *};
     use mytest::*;
     use rstest_reuse::*;
+    use rstest_test::assert_in;
 
     #[derive(Clone)]
     struct FixtureOutput {
         orig: ItemFn,
         fixture: ItemStruct,
         core_impl: ItemImpl,
+        builder: Vec<syn::Item>,
     }
 
     impl Parse for FixtureOutput {
@@ -135,6 +1319,13 @@ mod should {
                 fixture: input.parse()?,
                 core_impl: input.parse()?,
                 orig: input.parse()?,
+                builder: {
+                    let mut items = vec![];
+                    while !input.is_empty() {
+                        items.push(input.parse()?);
+                    }
+                    items
+                },
             })
         }
     }
@@ -312,6 +1503,210 @@ mod should {
         assert_eq!(expected.sig, default_decl);
     }
 
+    fn methods(item: &syn::Item) -> Vec<syn::ImplItemMethod> {
+        match item {
+            syn::Item::Impl(i) => i
+                .items
+                .iter()
+                .filter_map(|ii| match ii {
+                    syn::ImplItem::Method(f) => Some(f.clone()),
+                    _ => None,
+                })
+                .collect(),
+            _ => panic!("Expected an impl"),
+        }
+    }
+
+    #[test]
+    fn implement_a_builder_with_a_setter_for_each_argument() {
+        let (_, out) = parse_fixture(
+            r#"
+            pub fn user<'a, O: Default>(name: String, age: u8, tag: &'a str) -> O
+                where O: From<u8>
+            { }
+            "#,
+        );
+
+        let with = select_method(out.core_impl, "builder").unwrap();
+        let expected: syn::ItemStruct = parse_quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types, dead_code)]
+            pub struct __user_builder<__A0, __A1, __A2>(Option<__A0>, Option<__A1>, Option<__A2>);
+        };
+        let expected_output: ReturnType = parse_quote! { -> __user_builder<__A0, __A1, __A2> };
+        assert_eq!(expected_output, with.sig.output);
+        assert_eq!(syn::Item::Struct(expected), out.builder[0]);
+        assert_eq!(
+            vec![
+                "with_name",
+                "__arg_0",
+                "with_age",
+                "__arg_1",
+                "with_tag",
+                "__arg_2"
+            ],
+            methods(&out.builder[1])
+                .iter()
+                .map(|m| m.sig.ident.to_string())
+                .collect::<Vec<_>>()
+        );
+        let build_impl: syn::ItemImpl = match &out.builder[2] {
+            syn::Item::Impl(i) => i.clone(),
+            _ => panic!("Expected an impl"),
+        };
+        let expected_self: Type = parse_quote! { __user_builder<String, u8, &'a str> };
+        assert_eq!(expected_self, *build_impl.self_ty);
+        let expected_generics: syn::Generics = parse_quote! { <'a> };
+        assert_eq!(expected_generics.params, build_impl.generics.params);
+        let build = &methods(&out.builder[2])[0];
+        let expected: syn::Signature = parse_quote! {
+            fn build<O: Default>(self) -> O where O: From<u8>
+        };
+        assert_eq!(expected.generics.params, build.sig.generics.params);
+        assert_eq!(
+            expected.generics.where_clause,
+            build.sig.generics.where_clause
+        );
+        assert_eq!(expected.output, build.sig.output);
+    }
+
+    fn build_impls(out: &FixtureOutput) -> Vec<(syn::Generics, Type)> {
+        out.builder[2..]
+            .iter()
+            .map(|item| match item {
+                syn::Item::Impl(i) => (i.generics.clone(), i.self_ty.as_ref().clone()),
+                _ => panic!("Expected an impl"),
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case::impl_trait(
+        "pub fn user(name: impl AsRef<str>, age: u8) -> User { }",
+        "<__I0_0: AsRef<str>>",
+        "(__I0_0,)"
+    )]
+    #[case::type_parameter(
+        "pub fn user<N: AsRef<str>>(name: N, age: u8) -> User { }",
+        "<N: AsRef<str>>",
+        "(N,)"
+    )]
+    #[case::nested_type_parameter(
+        "pub fn user<N>(name: Vec<N>, age: u8) -> User { }",
+        "<N>",
+        "(Vec<N>,)"
+    )]
+    fn implement_the_builder_also_if_some_argument_is(
+        #[case] code: &str,
+        #[case] generics: &str,
+        #[case] slot: &str,
+    ) {
+        let (_, out) = parse_fixture(code);
+
+        let with = select_method(out.core_impl.clone(), "builder").unwrap();
+        let expected_output: ReturnType = parse_quote! { -> __user_builder<(), __A1> };
+        assert_eq!(expected_output, with.sig.output);
+        let expected: syn::ItemStruct = parse_quote! {
+            #[doc(hidden)]
+            #[allow(non_camel_case_types, dead_code)]
+            pub struct __user_builder<__A0, __A1>(__A0, Option<__A1>);
+        };
+        assert_eq!(syn::Item::Struct(expected), out.builder[0]);
+        let setter = &methods(&out.builder[1])[0];
+        let expected: syn::Signature = parse_quote! {
+            fn with_name<__V>(self, value: __V) -> __user_builder<(__V,), __A1>
+        };
+        assert_eq!(expected.generics, setter.sig.generics);
+        assert_eq!(expected.output, setter.sig.output);
+        let slot: Type = slot.ast();
+        assert_eq!(
+            vec![
+                (parse_quote! {}, parse_quote! { __user_builder<(), u8> }),
+                (
+                    parse_str::<syn::Generics>(generics).unwrap(),
+                    parse_quote! { __user_builder<#slot, u8> }
+                ),
+            ],
+            build_impls(&out)
+        );
+    }
+
+    #[test]
+    fn not_implement_the_build_that_cannot_resolve_a_missing_argument() {
+        let (_, out) = parse_fixture("pub fn user<N>(name: N, age: u8) -> Vec<N> { }");
+
+        assert_eq!(
+            vec![(
+                parse_quote! { <N> },
+                parse_quote! { __user_builder<(N,), u8> }
+            )],
+            build_impls(&out)
+        );
+    }
+
+    #[test]
+    fn implement_a_build_for_each_argument_that_is_not_fixed() {
+        let args = (0..70)
+            .map(|i| format!("a{}: impl ToString", i))
+            .collect::<Vec<_>>();
+        let (_, out) = parse_fixture(format!(
+            "pub fn test({}, n: u32) -> String {{ }}",
+            args.join(", ")
+        ));
+        let slots = |given: usize| -> Type {
+            let slots = (0..70).map(|i| -> Type {
+                if i < given {
+                    let param = format_ident!("__I{}_0", i);
+                    parse_quote! { (#param,) }
+                } else {
+                    parse_quote! { () }
+                }
+            });
+            parse_quote! { __test_builder<#(#slots,)* u32> }
+        };
+
+        let impls = build_impls(&out);
+
+        assert_eq!(71, impls.len());
+        assert_eq!(slots(0), impls[0].1);
+        assert_eq!(slots(1), impls[1].1);
+        assert_eq!(slots(70), impls[70].1);
+    }
+
+    #[test]
+    fn render_the_associated_fixture_functions_in_the_same_impl() {
+        let item_fn: ItemFn = "fn started(port: u16) -> Self { }".ast();
+
+        let tokens = render_associated(item_fn.clone(), Default::default());
+        let item_impl: ItemImpl = parse_quote! { impl Server { #tokens } };
+
+        assert_eq!(
+            vec![
+                "started",
+                "__started_get",
+                "__started_default",
+                "__started_guarded_default",
+                "__started_partial_1",
+                "__started_guarded_partial_1"
+            ],
+            methods(&syn::Item::Impl(item_impl.clone()))
+                .iter()
+                .map(|m| m.sig.ident.to_string())
+                .collect::<Vec<_>>()
+        );
+        let default = select_method(item_impl.clone(), "__started_default").unwrap();
+        let guarded = select_method(item_impl, "__started_guarded_default").unwrap();
+        assert_eq!(item_fn.vis, default.vis);
+        assert!(default.block.display_code().contains(
+            &quote! { Self::__started_guarded_default(Default::default(), Self::__started_default) }
+                .display_code()
+        ));
+        assert!(guarded
+            .block
+            .display_code()
+            .contains(&quote! { Self::__started_get(port) }.display_code()));
+    }
+
     #[test]
     fn implement_partial_methods() {
         let (item_fn, out) = parse_fixture(
@@ -347,6 +1742,52 @@ mod should {
         }
     }
 
+    #[rstest]
+    #[case::fixed_arguments(
+        "pub fn test(mut s: String, v: &u32, a: &mut [i32]) -> usize { }",
+        "Self::builder().__arg_0(s).__arg_1(v).build()"
+    )]
+    #[case::generic_argument(
+        "pub fn test<S: AsRef<str>>(s: S, v: impl ToString, a: u32) -> usize { }",
+        "Self::builder().__arg_0(s).__arg_1(v).build()"
+    )]
+    #[case::async_fixture(
+        "pub async fn test(s: String, v: u32, a: u32) -> usize { }",
+        "Self::builder().__arg_0(s).__arg_1(v).build().await"
+    )]
+    fn implement_partial_methods_by_the_builder(#[case] code: &str, #[case] expected: &str) {
+        let (_, out) = parse_fixture(code);
+
+        let partial = select_method(out.core_impl.clone(), "partial_2").unwrap();
+        let guarded = select_method(out.core_impl, "__guarded_partial_2").unwrap();
+        let expected = expected.ast::<Expr>();
+
+        assert_eq!(
+            partial.block.display_code(),
+            quote! { { #expected } }.display_code()
+        );
+        assert_in!(
+            guarded.block.display_code(),
+            "Self::builder().__arg_0(s).__arg_1(v).__guarded_build(__shared, |builder| builder.build())"
+                .ast::<Expr>()
+                .display_code()
+        );
+    }
+
+    #[test]
+    fn implement_partial_methods_without_builder_if_it_cannot_build() {
+        let (_, out) = parse_fixture("pub fn test<T>(t: T, v: T) -> usize { }");
+
+        let partial = select_method(out.core_impl, "__guarded_partial_1").unwrap();
+
+        assert!(!partial.block.display_code().contains("builder"));
+        assert_in!(
+            partial.block.display_code(),
+            quote! { let v = v::__guarded_default(__shared.clone(), | | v::default()); }
+                .display_code()
+        );
+    }
+
     #[test]
     fn clean_generics_in_partial_methods() {
         let (_, out) = parse_fixture(
@@ -420,4 +1861,365 @@ mod should {
 
         assert_eq!(expected.sig, partial.sig);
     }
+
+    fn parse_once_fixture<S: AsRef<str>>(code: S) -> FixtureOutput {
+        let item_fn = parse_str::<ItemFn>(code.as_ref()).unwrap();
+
+        let mut info = FixtureInfo::default();
+        info.attributes.set_once(ident("once"));
+
+        parse2(render(item_fn, info)).unwrap()
+    }
+
+    #[rstest]
+    #[case::get("get", "pub fn get(a: u32, b: &str) -> &'static String { }")]
+    #[case::default("default", "pub fn default() -> &'static String { }")]
+    #[case::partial_1("partial_1", "pub fn partial_1(a: u32) -> &'static String { }")]
+    #[case::partial_2(
+        "partial_2",
+        "pub fn partial_2(a: u32, b: &str) -> &'static String { }"
+    )]
+    fn once_fixture_methods_should_return_static_reference(
+        #[case] method: &str,
+        #[case] expected: &str,
+    ) {
+        let out = parse_once_fixture(r#"pub fn test(a: u32, b: &str) -> String { }"#);
+
+        let expected = parse_str::<ItemFn>(expected).unwrap();
+        let signature = select_method(out.core_impl, method).unwrap().sig;
+
+        assert_eq!(expected.sig, signature);
+    }
+
+    #[test]
+    fn once_fixture_should_call_the_fixture_function_just_once() {
+        let out = parse_once_fixture(r#"pub fn test() -> String { }"#);
+
+        let body = select_method(out.core_impl, "get").unwrap().block;
+
+        assert!(body.display_code().contains("call_once"));
+    }
+
+    #[test]
+    fn once_fixture_without_return_type_should_not_return_anything() {
+        let out = parse_once_fixture(r#"pub fn test() { }"#);
+
+        let default_decl = select_method(out.core_impl, "default").unwrap().sig;
+
+        assert_eq!(ReturnType::Default, default_decl.output);
+    }
+
+    fn parse_teardown_fixture<S: AsRef<str>>(code: S) -> FixtureOutput {
+        let item_fn = parse_str::<ItemFn>(code.as_ref()).unwrap();
+
+        let mut info = FixtureInfo::default();
+        info.attributes
+            .set_teardown(ident("teardown"), expr("|s| drop(s)"));
+
+        parse2(render(item_fn, info)).unwrap()
+    }
+
+    #[rstest]
+    #[case::get("get", "pub fn get(a: u32, b: &str) -> String { }")]
+    #[case::default("default", "pub fn default() -> String { }")]
+    #[case::partial_1("partial_1", "pub fn partial_1(a: u32) -> String { }")]
+    #[case::guarded_default("__guarded_default", "pub fn __guarded_default(__shared: ::std::rc::Rc<::std::cell::RefCell<::std::vec::Vec<::std::boxed::Box<dyn ::std::any::Any>>>>, _: fn() -> String) -> (::rstest_fixtures::Fixture<String, impl ::rstest_fixtures::TearDown, impl FnOnce(String)>, impl Sized) { }")]
+    fn teardown_fixture_should_return_fixture_just_from_the_guarded_methods(
+        #[case] method: &str,
+        #[case] expected: &str,
+    ) {
+        let out = parse_teardown_fixture(r#"pub fn test(a: u32, b: &str) -> String { }"#);
+
+        let expected = parse_str::<ItemFn>(expected).unwrap();
+        let signature = select_method(out.core_impl, method).unwrap().sig;
+
+        assert_eq!(expected.sig, signature);
+    }
+
+    #[test]
+    fn teardown_fixture_should_wrap_the_fixture_function_result() {
+        let out = parse_teardown_fixture(r#"pub fn test() -> String { }"#);
+
+        let get = select_method(out.core_impl.clone(), "get").unwrap().block;
+        let guarded = select_method(out.core_impl, "__guarded_default")
+            .unwrap()
+            .block;
+
+        assert_eq!(get.display_code(), quote! { { test() } }.display_code());
+        assert_in!(
+            guarded.display_code(),
+            quote! { ::rstest_fixtures::with_teardown(Self::get(), |s| drop(s)) }.display_code()
+        );
+    }
+
+    #[test]
+    fn teardown_fixture_should_keep_injected_fixtures_guards_in_reverse_order() {
+        let out = parse_teardown_fixture(r#"pub fn test(a: u32, b: u32) -> String { }"#);
+
+        let body = select_method(out.core_impl, "__guarded_default")
+            .unwrap()
+            .block;
+
+        assert!(body.display_code().contains(
+            &quote! {
+                (
+                    ::rstest_fixtures::with_teardown(Self::get(a, b), |s| drop(s)),
+                    (__b_guard, __a_guard,)
+                )
+            }
+            .display_code()
+        ));
+    }
+
+    #[test]
+    fn teardown_fixture_should_lend_its_value() {
+        let out = parse_teardown_fixture(r#"pub fn test() -> String { }"#);
+
+        let lend = select_method(out.core_impl, "__lend").unwrap();
+
+        assert_eq!(
+            lend.sig,
+            parse_str::<ItemFn>(
+                "pub fn __lend<__T, __G: ::rstest_fixtures::TearDown, __F: FnOnce(__T), __D>(
+                    fixture: &mut (::rstest_fixtures::Fixture<__T, __G, __F>, __D),
+                ) -> &mut __T {}"
+            )
+            .unwrap()
+            .sig
+        );
+    }
+
+    #[test]
+    fn teardown_fixture_should_give_a_clone_of_its_value() {
+        let out = parse_teardown_fixture(r#"pub fn test() -> String { }"#);
+
+        let take = select_method(out.core_impl, "__take").unwrap();
+
+        assert_eq!(
+            take.sig,
+            parse_str::<ItemFn>(
+                "pub fn __take<__T: Clone, __G: ::rstest_fixtures::TearDown, __F: FnOnce(__T), __D>(
+                    fixture: &mut (::rstest_fixtures::Fixture<__T, __G, __F>, __D),
+                ) -> __T {}"
+            )
+            .unwrap()
+            .sig
+        );
+    }
+
+    #[test]
+    fn fixture_without_fixtures_to_inject_should_not_be_guarded() {
+        let (_, out) = parse_fixture(r#"pub fn test() -> String { }"#);
+
+        let default = select_method(out.core_impl.clone(), "default").unwrap();
+
+        assert!(select_method(out.core_impl.clone(), "__guarded_default").is_none());
+        assert!(select_method(out.core_impl.clone(), "__take").is_none());
+        assert!(select_method(out.core_impl, "__lend").is_none());
+        assert_eq!(default.sig.output, parse_quote! { -> String });
+        assert_eq!(
+            default.block.display_code(),
+            quote! { { Self::get() } }.display_code()
+        );
+    }
+
+    #[test]
+    fn fixture_without_teardown_should_carry_the_injected_fixtures_guards() {
+        let (_, out) = parse_fixture(r#"pub fn test(a: u32, b: u32) -> String { }"#);
+
+        let guarded = select_method(out.core_impl.clone(), "__guarded_default").unwrap();
+        let default = select_method(out.core_impl.clone(), "default").unwrap();
+
+        assert_eq!(
+            guarded.sig.output,
+            parse_quote! { -> (Option<String>, impl Sized) }
+        );
+        assert!(guarded
+            .block
+            .display_code()
+            .contains(&quote! { (Some(Self::get(a, b)), (__b_guard, __a_guard,)) }.display_code()));
+        assert_eq!(default.sig.output, parse_quote! { -> String });
+        assert_eq!(
+            default.block.display_code(),
+            quote! {
+                {
+                    let (fixture, guards) = Self::__guarded_default(Default::default(), Self::default);
+                    std::mem::forget(guards);
+                    fixture.unwrap()
+                }
+            }
+            .display_code()
+        );
+        assert!(select_method(out.core_impl, "__lend").is_none());
+    }
+
+    #[test]
+    fn async_fixture_should_inject_its_future() {
+        let (_, out) = parse_fixture(r#"pub async fn test(a: u32) -> String { }"#);
+
+        let guarded = select_method(out.core_impl.clone(), "__guarded_default").unwrap();
+        let default = select_method(out.core_impl, "default").unwrap();
+
+        assert!(guarded.sig.asyncness.is_none());
+        assert_eq!(
+            guarded.sig.output,
+            parse_quote! {
+                -> (Option<impl std::future::Future<Output = String> >, impl Sized)
+            }
+        );
+        assert_eq!(
+            default.block.display_code(),
+            quote! {
+                {
+                    let (fixture, guards) = Self::__guarded_default(Default::default(), | | ());
+                    std::mem::forget(guards);
+                    fixture.unwrap().await
+                }
+            }
+            .display_code()
+        );
+    }
+
+    #[rstest]
+    #[case::get("get", "pub fn get() -> rstest_fixtures::Fixture<String, MyGuard> { }")]
+    #[case::default(
+        "default",
+        "pub fn default() -> rstest_fixtures::Fixture<String, MyGuard> { }"
+    )]
+    fn fixture_that_returns_fixture_should_have_teardown(
+        #[case] method: &str,
+        #[case] expected: &str,
+    ) {
+        let item_fn = parse_str::<ItemFn>(
+            r#"pub fn test() -> rstest_fixtures::Fixture<String, MyGuard> { }"#,
+        )
+        .unwrap();
+
+        let out: FixtureOutput = parse2(render(item_fn, Default::default())).unwrap();
+
+        let expected = parse_str::<ItemFn>(expected).unwrap();
+        let signature = select_method(out.core_impl, method).unwrap().sig;
+
+        assert_eq!(expected.sig, signature);
+    }
+
+    #[rstest]
+    #[case::user_type("Fixture<String, MyGuard>", false)]
+    #[case::other_module("my::Fixture<String, MyGuard>", false)]
+    #[case::wrong_arguments("rstest_fixtures::Fixture<String>", false)]
+    #[case::crate_path("rstest_fixtures::Fixture<String, MyGuard>", true)]
+    #[case::absolute_path("::rstest_fixtures::Fixture<String, MyGuard>", true)]
+    fn recognize_just_the_rstest_fixtures_fixture_type(#[case] ty: &str, #[case] expected: bool) {
+        let rt = parse_str::<ReturnType>(&format!("-> {}", ty)).unwrap();
+
+        assert_eq!(expected, fixture_value_type(&rt).is_some());
+    }
+
+    fn parse_params_fixture<S: AsRef<str>>(code: S) -> FixtureOutput {
+        let item_fn = parse_str::<ItemFn>(code.as_ref()).unwrap();
+
+        let mut info = FixtureInfo::default();
+        info.data
+            .items
+            .push(values_list("kind", &["1", "2"]).into());
+        info.attributes.add_params(vec![ident("backend")]);
+
+        parse2(render(item_fn, info)).unwrap()
+    }
+
+    #[test]
+    fn parametrized_fixture_should_be_injected_by_the_values_indexes() {
+        let out = parse_params_fixture(r#"pub fn db(kind: u32, backend: u32) -> u32 { }"#);
+
+        let injecting = select_method(out.core_impl.clone(), "__params_default").unwrap();
+        let default = select_method(out.core_impl, "default").unwrap();
+
+        assert_eq!(
+            injecting.sig.inputs.first().unwrap(),
+            &parse_quote! { __params: &[usize] }
+        );
+        assert_in!(
+            injecting.block.display_code(),
+            quote! {
+                let kind = match __params[0] {
+                    0usize => 1,
+                    1usize => 2,
+                    _ => unreachable!("Invalid fixture parameter's index")
+                };
+            }
+            .display_code()
+        );
+        assert_in!(
+            injecting.block.display_code(),
+            quote! {
+                let backend = backend::__params_default(
+                    &__params[1usize..][..backend::__PARAMS],
+                    __shared.clone(),
+                    | | backend::default()
+                );
+            }
+            .display_code()
+        );
+        assert_in!(
+            default.block.display_code(),
+            quote! {
+                Self::__params_default(&[0usize; db::__PARAMS], Default::default(), Self::default)
+            }
+            .display_code()
+        );
+    }
+
+    #[test]
+    fn parametrized_fixture_should_reject_the_arguments_without_params() {
+        let out = parse_params_fixture(r#"pub fn db(kind: u32, backend: u32) -> u32 { }"#);
+
+        let check = out
+            .builder
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Trait(t) => Some(t.ident.clone()),
+                _ => None,
+            })
+            .unwrap();
+        let stubs = out
+            .builder
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Impl(i) if i.self_ty == parse_quote! { db } => Some(i.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let guarded_default = stubs
+            .iter()
+            .find_map(|i| select_method(i.clone(), "__guarded_default"))
+            .unwrap();
+
+        assert_eq!(
+            check,
+            ident("db_is_a_parametrized_fixture_mark_the_argument_with_params")
+        );
+        assert_in!(
+            guarded_default.sig.generics.display_code(),
+            quote! { __F: db_is_a_parametrized_fixture_mark_the_argument_with_params }
+                .display_code()
+        );
+        assert!(stubs
+            .iter()
+            .any(|i| select_method(i.clone(), "__guarded_partial_2").is_some()));
+    }
+
+    #[test]
+    fn not_parametrized_fixture_should_not_count_the_values_indexes() {
+        let (_, out) = parse_fixture(r#"pub fn test(a: u32) -> String { }"#);
+
+        assert!(!out
+            .core_impl
+            .items
+            .iter()
+            .any(|item| matches!(item, syn::ImplItem::Const(_))));
+        assert!(!out
+            .builder
+            .iter()
+            .any(|item| matches!(item, syn::Item::Trait(_))));
+    }
 }
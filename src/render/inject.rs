@@ -1,24 +1,219 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{parse_quote, Expr, FnArg, Ident, Stmt, Type};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{parse_quote, spanned::Spanned, visit::Visit, Expr, ExprPath, FnArg, Ident, Stmt, Type};
 
 use crate::{
     refident::{MaybeIdent, MaybeType},
-    resolver::Resolver,
+    resolver::{self, Resolver},
     utils::IsLiteralExpression,
 };
 
-pub(crate) fn resolve_aruments<'a>(
+/// Resolve arguments and keep every injected fixture in a guard: the argument takes the value
+/// given by the fixture (see `ArgumentResolver::lend()`). Guards are bound to local variables
+/// and their names are returned in the arguments' order.
+pub(crate) fn resolve_aruments_and_guards<'a>(
     args: impl Iterator<Item = &'a FnArg>,
     resolver: &impl Resolver,
     generic_types: &[Ident],
+) -> (TokenStream, Vec<Ident>) {
+    let argument_resolver = ArgumentResolver::new(resolver, generic_types);
+    let args = args.collect::<Vec<_>>();
+    let fallback = render_fallback(args.iter().cloned(), resolver);
+    let mut guards = vec![];
+    let define_vars = args
+        .into_iter()
+        .map(|arg| {
+            let define = argument_resolver.resolve(arg);
+            let keep = argument_resolver.lend(arg).map(|(guard, lend)| {
+                let ident = arg.maybe_ident();
+                guards.push(guard.clone());
+                // A `#[teardown]` value taken by a not `Clone` argument is reported there
+                let take = quote_spanned! {arg.span() =>
+                    let #ident = #lend(&mut #guard);
+                };
+                quote! {
+                    let mut #guard = #ident;
+                    #take
+                }
+            });
+            quote! { #define #keep }
+        })
+        .collect::<Vec<_>>();
+    (
+        quote! {
+            #fallback
+            #(#define_vars)*
+        },
+        guards,
+    )
+}
+
+/// The fixtures that don't need the teardown guards (see `render::fixture`) don't define the
+/// `__guarded_*()`, `__take()` and `__lend()` functions used to inject them: the trait
+/// rendered here implements the ones called to resolve `args` for every type. They call the
+/// given fixture's public function and keep the value in an `Option`. The functions defined
+/// by the fixtures take precedence.
+pub(crate) fn render_fallback<'a>(
+    args: impl Iterator<Item = &'a FnArg>,
+    resolver: &impl Resolver,
 ) -> TokenStream {
-    let define_vars = args.map(|arg| ArgumentResolver::new(resolver, generic_types).resolve(arg));
+    let argument_resolver = ArgumentResolver::new(resolver, &[]);
+    let cache_type = resolver::fixtures::shared_cache_type();
+    let mut members = InjectedMembers::default();
+    for arg in args {
+        if let Some(define) = argument_resolver.resolve(arg) {
+            members.visit_stmt(&define);
+        }
+        if let Some((_, lend)) = argument_resolver.lend(arg) {
+            let lend = &lend.path.segments.last().unwrap().ident;
+            members.functions.insert(
+                lend.to_string(),
+                Member::Code(quote! {
+                    fn #lend<__V, __D>(fixture: &mut (Option<__V>, __D)) -> __V {
+                        fixture.0.take().unwrap()
+                    }
+                }),
+            );
+        }
+    }
+    let functions = members.functions.values().map(|f| match f {
+        Member::Default(name) => quote! {
+            fn #name<__V, __F: FnOnce() -> __V>(_: #cache_type, fallback: __F) -> (Option<__V>, ()) {
+                (Some(fallback()), ())
+            }
+        },
+        Member::Partial(name, n) => {
+            let types = (0..*n).map(|i| format_ident!("__A{}", i)).collect::<Vec<_>>();
+            let values = (0..*n).map(|i| format_ident!("value_{}", i)).collect::<Vec<_>>();
+            quote! {
+                fn #name<#(#types,)* __V, __F: FnOnce(#(#types),*) -> __V>(
+                    _: #cache_type,
+                    #(#values: #types,)*
+                    fallback: __F
+                ) -> (Option<__V>, ()) {
+                    (Some(fallback(#(#values),*)), ())
+                }
+            }
+        }
+        Member::Build => quote! {
+            fn __guarded_build<__V, __F: FnOnce(Self) -> __V>(
+                self,
+                _: #cache_type,
+                fallback: __F
+            ) -> (Option<__V>, ())
+            where
+                Self: Sized
+            {
+                (Some(fallback(self)), ())
+            }
+        },
+        Member::Code(code) => code.clone(),
+    });
+    if members.functions.is_empty() {
+        return Default::default();
+    }
     quote! {
-        #(#define_vars)*
+        #[allow(dead_code)]
+        trait __PlainFixture {
+            #(#functions)*
+        }
+
+        impl<__T: ?Sized> __PlainFixture for __T {}
+    }
+}
+
+/// Some of the resolved `args` use the test's shared fixtures cache.
+pub(crate) fn uses_shared_cache<'a>(
+    args: impl Iterator<Item = &'a FnArg>,
+    resolver: &impl Resolver,
+) -> bool {
+    let argument_resolver = ArgumentResolver::new(resolver, &[]);
+    let mut members = InjectedMembers::default();
+    for define in args.filter_map(|arg| argument_resolver.resolve(arg)) {
+        members.visit_stmt(&define);
     }
+    members.uses_cache
+}
+
+/// A fixture's function called to inject the fixtures.
+enum Member {
+    Default(Ident),
+    Partial(Ident, usize),
+    Build,
+    Code(TokenStream),
+}
+
+/// Collect the `__guarded_*()` fixtures' functions called by the resolved arguments.
+#[derive(Default)]
+struct InjectedMembers {
+    functions: BTreeMap<String, Member>,
+    uses_cache: bool,
+}
+
+impl<'ast> Visit<'ast> for InjectedMembers {
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Expr::Path(p) = call.func.as_ref() {
+            let name = &p.path.segments.last().unwrap().ident;
+            let name_str = name.to_string();
+            let partial = "guarded_partial_";
+            let member = if name_str.ends_with("guarded_default") {
+                Some(Member::Default(name.clone()))
+            } else {
+                name_str
+                    .find(partial)
+                    .and_then(|i| name_str[i + partial.len()..].parse().ok())
+                    .map(|n| Member::Partial(name.clone(), n))
+            };
+            if let Some(member) = member {
+                self.functions.insert(name_str, member);
+            }
+        }
+        syn::visit::visit_expr_call(self, call)
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        if call.method == "__guarded_build" {
+            self.functions
+                .insert(call.method.to_string(), Member::Build);
+        }
+        syn::visit::visit_expr_method_call(self, call)
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        self.uses_cache |= path.is_ident(&resolver::fixtures::shared_cache());
+        syn::visit::visit_path(self, path)
+    }
+}
+
+/// Resolve the argument like `resolve_aruments_and_guards()` but, if it's resolved by a
+/// fixture, return the guard's name and the fixture's `__take()` or `__lend()` function rather
+/// than keep the fixture: the caller defines the guard and the fallback (see
+/// `render_fallback()`).
+pub(crate) fn resolve_argument_and_lend(
+    arg: &FnArg,
+    resolver: &impl Resolver,
+    generic_types: &[Ident],
+) -> (Option<Stmt>, Option<(Ident, ExprPath)>) {
+    let argument_resolver = ArgumentResolver::new(resolver, generic_types);
+    (argument_resolver.resolve(arg), argument_resolver.lend(arg))
+}
+
+/// The arguments resolved by a fixture and the fixtures' paths.
+pub(crate) fn fixtures_paths<'a>(
+    args: impl Iterator<Item = &'a FnArg>,
+    resolver: &impl Resolver,
+) -> Vec<(Ident, ExprPath)> {
+    let argument_resolver = ArgumentResolver::new(resolver, &[]);
+    args.filter_map(|arg| {
+        let ident = arg.maybe_ident()?;
+        argument_resolver
+            .resolving_fixture(ident)
+            .map(|path| (ident.clone(), path))
+    })
+    .collect()
 }
 
 struct ArgumentResolver<'resolver, 'idents, 'f, R>
@@ -45,13 +240,10 @@ where
     fn resolve(&self, arg: &FnArg) -> Option<Stmt> {
         let ident = arg.maybe_ident()?;
         let arg_type = arg.maybe_type()?;
-        let fixture_name = self.fixture_name(ident);
 
         let mut fixture = self
-            .resolver
-            .resolve(&fixture_name)
-            .map(|e| e.clone())
-            .unwrap_or_else(|| default_fixture_resolve(&fixture_name));
+            .resolve_argument(ident)
+            .unwrap_or_else(|| default_fixture_resolve(&self.fixture_path(ident)));
 
         if fixture.is_literal() && self.type_can_be_get_from_literal_str(arg_type) {
             fixture = Cow::Owned((self.magic_conversion)(fixture, arg_type));
@@ -61,6 +253,52 @@ where
         })
     }
 
+    /// If `arg` is resolved by a fixture (the `__guarded_*()` fixture's functions return the
+    /// value with its teardown guards), return the name of the guard that keeps the fixture and
+    /// the fixture's function that gives the argument's value: `__lend()` if the argument is
+    /// a reference (a `#[teardown]` fixture lends the value that it keeps), `__take()`
+    /// otherwise.
+    fn lend(&self, arg: &FnArg) -> Option<(Ident, ExprPath)> {
+        let ident = arg.maybe_ident()?;
+        let fixture_path = self.resolving_fixture(ident)?;
+        let member = match arg.maybe_type() {
+            Some(Type::Reference(_)) => "__lend",
+            _ => "__take",
+        };
+        Some((
+            format_ident!("__{}_guard", ident),
+            resolver::fixtures::member(&fixture_path, member),
+        ))
+    }
+
+    /// The path of the fixture that resolve the argument, if the argument is resolved by
+    /// a fixture.
+    fn resolving_fixture(&self, ident: &Ident) -> Option<ExprPath> {
+        let fixture_path = self.fixture_path(ident);
+        let is_fixture = self
+            .resolve_argument(ident)
+            .map(|e| is_fixture_call(&e, &fixture_path))
+            .unwrap_or(true);
+        Some(fixture_path).filter(|_| is_fixture)
+    }
+
+    /// Resolve the argument by its name or, if it's not resolved, by the name without
+    /// the leading `_`
+    fn resolve_argument(&self, ident: &Ident) -> Option<Cow<'_, Expr>> {
+        self.resolver
+            .resolve(ident)
+            .or_else(|| self.resolver.resolve(&self.fixture_name(ident)))
+    }
+
+    /// The path of the fixture that resolve the argument: the one given by `#[from(...)]`
+    /// or the argument's name without the leading `_`
+    fn fixture_path(&self, ident: &Ident) -> ExprPath {
+        self.resolver
+            .fixture_path(ident)
+            .map(|p| p.into_owned())
+            .unwrap_or_else(|| resolver::fixtures::name_path(self.fixture_name(ident).into_owned()))
+    }
+
     fn fixture_name<'a>(&self, ident: &'a Ident) -> Cow<'a, Ident> {
         let id_str = ident.to_string();
         if id_str.starts_with("_") && !id_str.starts_with("__") {
@@ -91,8 +329,42 @@ where
     }
 }
 
-fn default_fixture_resolve(ident: &Ident) -> Cow<Expr> {
-    Cow::Owned(parse_quote! { #ident::default() })
+fn is_fixture_call(expr: &Expr, fixture_path: &ExprPath) -> bool {
+    let qualified_type = |p: &ExprPath| p.qself.as_ref().map(|q| q.ty.clone());
+    match expr {
+        Expr::Call(call) => match call.func.as_ref() {
+            Expr::Path(p) if qualified_type(p) == qualified_type(fixture_path) => {
+                let segments = &p.path.segments;
+                let fixture_segments = &fixture_path.path.segments;
+                let len = fixture_segments.len();
+                let same_prefix = |n: usize| {
+                    segments
+                        .iter()
+                        .zip(fixture_segments.iter())
+                        .take(n)
+                        .all(|(s, f)| s.ident == f.ident)
+                };
+                if resolver::fixtures::is_associated(fixture_path) {
+                    // `<Type>::__name_member()` or `Self::__name_member()`
+                    let prefix = format!("__{}_", fixture_segments[len - 1].ident);
+                    segments.len() == len
+                        && same_prefix(len - 1)
+                        && segments[len - 1].ident.to_string().starts_with(&prefix)
+                } else {
+                    segments.len() == len + 1 && same_prefix(len)
+                }
+            }
+            _ => false,
+        },
+        // Named values are given by the fixture's builder:
+        // `path::builder().with_name(v).__guarded_build()`
+        Expr::MethodCall(m) => is_fixture_call(&m.receiver, fixture_path),
+        _ => false,
+    }
+}
+
+fn default_fixture_resolve(path: &ExprPath) -> Cow<'static, Expr> {
+    Cow::Owned(resolver::fixtures::render_call(path, &[], &[]))
 }
 
 fn handling_magic_conversion_code(fixture: Cow<Expr>, arg_type: &Type) -> Expr {
@@ -150,17 +422,34 @@ fn handling_magic_conversion_code(fixture: Cow<Expr>, arg_type: &Type) -> Expr {
 mod should {
     use super::*;
     use crate::{
+        parse::Fixture,
         test::{assert_eq, *},
         utils::fn_args,
     };
     use mytest::*;
+    use rstest_test::assert_in;
 
     #[rstest]
-    #[case::as_is("fix: String", "let fix = fix::default();")]
-    #[case::without_underscore("_fix: String", "let _fix = fix::default();")]
-    #[case::do_not_remove_inner_underscores("f_i_x: String", "let f_i_x = f_i_x::default();")]
-    #[case::do_not_remove_double_underscore("__fix: String", "let __fix = __fix::default();")]
-    #[case::without_mut("mut fix: String", "let fix = fix::default();")]
+    #[case::as_is(
+        "fix: String",
+        "let fix = fix::__guarded_default(__shared.clone(), || fix::default());"
+    )]
+    #[case::without_underscore(
+        "_fix: String",
+        "let _fix = fix::__guarded_default(__shared.clone(), || fix::default());"
+    )]
+    #[case::do_not_remove_inner_underscores(
+        "f_i_x: String",
+        "let f_i_x = f_i_x::__guarded_default(__shared.clone(), || f_i_x::default());"
+    )]
+    #[case::do_not_remove_double_underscore(
+        "__fix: String",
+        "let __fix = __fix::__guarded_default(__shared.clone(), || __fix::default());"
+    )]
+    #[case::without_mut(
+        "mut fix: String",
+        "let fix = fix::__guarded_default(__shared.clone(), || fix::default());"
+    )]
     fn call_fixture(#[case] arg_str: &str, #[case] expected: &str) {
         let arg = arg_str.ast();
 
@@ -188,6 +477,169 @@ mod should {
         assert_eq!(injected, expected.ast());
     }
 
+    #[rstest]
+    #[case::default_fixture("fix: String", None, true)]
+    #[case::without_underscore("_fix: String", None, true)]
+    #[case::partial_fixture(
+        "fix: String",
+        Some("fix::__guarded_partial_1(__shared.clone(), 42)"),
+        true
+    )]
+    #[case::value("fix: String", Some("42"), false)]
+    #[case::other_call(
+        "fix: String",
+        Some("other::__guarded_default(__shared.clone())"),
+        false
+    )]
+    #[case::params_fixture(
+        "fix: String",
+        Some("fix::__params_default(&[1], __shared.clone(), || fix::default())"),
+        true
+    )]
+    #[case::block(
+        "fix: String",
+        Some("{ other::__guarded_default(__shared.clone()) }"),
+        false
+    )]
+    fn lend_just_from_fixtures(
+        #[case] arg_str: &str,
+        #[case] resolved: Option<&str>,
+        #[case] expected: bool,
+    ) {
+        let arg = arg_str.ast();
+        let mut resolver = std::collections::HashMap::new();
+        let resolved = resolved.map(expr);
+        if let Some(e) = resolved.as_ref() {
+            resolver.insert("fix".to_owned(), e);
+        }
+
+        let lend = ArgumentResolver::new(&resolver, &[]).lend(&arg);
+
+        assert_eq!(expected, lend.is_some());
+    }
+
+    #[rstest]
+    #[case::value("_fix: String", "fix::__take")]
+    #[case::reference("_fix: &String", "fix::__lend")]
+    #[case::mut_reference("_fix: &mut String", "fix::__lend")]
+    fn lend_by_fixture_lend_function(#[case] arg_str: &str, #[case] expected: &str) {
+        let arg = arg_str.ast();
+
+        let (guard, lend) = ArgumentResolver::new(&EmptyResolver {}, &[])
+            .lend(&arg)
+            .unwrap();
+
+        assert_eq!(ident("___fix_guard"), guard);
+        assert_eq!(lend, expected.ast());
+    }
+
+    #[test]
+    fn keep_the_fixture_in_the_guard_and_lend_the_value() {
+        let function: syn::ItemFn = "fn test(_fix: &mut String){}".ast();
+
+        let (inject, _) = resolve_aruments_and_guards(fn_args(&function), &EmptyResolver {}, &[]);
+
+        let cache_type = resolver::fixtures::shared_cache_type();
+        assert_eq!(
+            inject.display_code(),
+            quote! {
+                #[allow(dead_code)]
+                trait __PlainFixture {
+                    fn __guarded_default<__V, __F: FnOnce() -> __V>(_: #cache_type, fallback: __F) -> (Option<__V>, ()) {
+                        (Some(fallback()), ())
+                    }
+                    fn __lend<__V, __D>(fixture: &mut (Option<__V>, __D)) -> __V {
+                        fixture.0.take().unwrap()
+                    }
+                }
+                impl<__T: ?Sized> __PlainFixture for __T {}
+                let _fix = fix::__guarded_default(__shared.clone(), | | fix::default());
+                let mut ___fix_guard = _fix;
+                let _fix = fix::__lend(&mut ___fix_guard);
+            }
+            .display_code()
+        );
+    }
+
+    #[rstest]
+    #[case::partial(
+        vec![fixture("fix", vec!["42"])],
+        "fn __guarded_partial_1<__A0, __V, __F: FnOnce(__A0) -> __V>"
+    )]
+    #[case::builder(
+        vec![fixture("fix", vec![]).with_named(vec![arg_value("age", "42")])],
+        "fn __guarded_build<__V, __F: FnOnce(Self) -> __V>"
+    )]
+    #[case::associated(
+        vec![fixture("fix", vec![]).with_resolve(parse_quote! { Self::started })],
+        "fn __started_guarded_default<__V, __F: FnOnce() -> __V>"
+    )]
+    #[case::take(vec![], "fn __take<__V, __D>")]
+    fn fall_back_to_the_plain_fixtures_functions(
+        #[case] data: Vec<Fixture>,
+        #[case] expected: &str,
+    ) {
+        let function: syn::ItemFn = "fn test(fix: u32){}".ast();
+        let resolver = crate::resolver::fixtures::get(data.iter());
+
+        let fallback = render_fallback(fn_args(&function), &resolver);
+
+        assert_in!(
+            fallback.display_code(),
+            expected.ast::<TokenStream>().display_code()
+        );
+    }
+
+    #[test]
+    fn not_fall_back_if_no_fixture_is_injected() {
+        let function: syn::ItemFn = "fn test(fix: u32){}".ast();
+        let value = expr("42");
+        let mut resolver = std::collections::HashMap::new();
+        resolver.insert("fix".to_owned(), &value);
+
+        let fallback = render_fallback(fn_args(&function), &resolver);
+
+        assert!(fallback.is_empty());
+        assert!(!uses_shared_cache(fn_args(&function), &resolver));
+    }
+
+    #[test]
+    fn lend_by_the_given_fixture_path() {
+        let arg = "admin: User".ast();
+        let data =
+            vec![fixture("admin", vec![r#""Bob""#])
+                .with_resolve(parse_quote! { crate::users::user })];
+        let resolver = crate::resolver::fixtures::get(data.iter());
+
+        let (guard, lend) = ArgumentResolver::new(&resolver, &[]).lend(&arg).unwrap();
+
+        assert_eq!(ident("__admin_guard"), guard);
+        assert_eq!(lend, "crate::users::user::__take".ast());
+    }
+
+    #[test]
+    fn lend_by_the_associated_fixture() {
+        let arg = "server: Server".ast();
+        let data = vec![fixture("server", vec!["42"]).with_resolve(parse_quote! { Self::started })];
+        let resolver = crate::resolver::fixtures::get(data.iter());
+
+        let (_, lend) = ArgumentResolver::new(&resolver, &[]).lend(&arg).unwrap();
+
+        assert_eq!(lend, "Self::__started_take".ast());
+    }
+
+    #[test]
+    fn keep_guards_in_arguments_order() {
+        let function: syn::ItemFn = "fn test(a: u32, b: u32, c: u32){}".ast();
+        let mut resolver = std::collections::HashMap::new();
+        let value = expr("42");
+        resolver.insert("b".to_owned(), &value);
+
+        let (_, guards) = resolve_aruments_and_guards(fn_args(&function), &resolver, &[]);
+
+        assert_eq!(vec![ident("__a_guard"), ident("__c_guard")], guards);
+    }
+
     fn _mock_conversion_code(fixture: Cow<Expr>, arg_type: &Type) -> Expr {
         parse_quote! {
             #fixture as #arg_type
@@ -1,14 +1,21 @@
+mod covering;
+mod expected;
 pub(crate) mod fixture;
+pub(crate) mod names;
+pub(crate) mod params;
+pub(crate) mod suite;
 mod test;
+mod timeout;
+pub(crate) mod types;
 mod wrapper;
 
-use std::collections::HashMap;
-use syn::token::Async;
+use std::collections::{HashMap, HashSet};
+use syn::{spanned::Spanned, token::Async};
 
 use proc_macro2::{Span, TokenStream};
 use syn::{
-    parse_quote, Attribute, Expr, FnArg, Generics, Ident, ItemFn, Path, ReturnType, Stmt, Type,
-    WherePredicate,
+    parse_quote, Attribute, Expr, ExprPath, FnArg, Generics, Ident, ItemFn, Path, ReturnType, Stmt,
+    Type, WherePredicate,
 };
 
 use quote::{format_ident, quote};
@@ -16,8 +23,13 @@ use quote::{format_ident, quote};
 use crate::utils::attr_ends_with;
 use crate::{
     parse::{
+        combinations::{
+            accept_prefix, accepted_combinations, CombinationsFilter, CombinationsMode, Matrix,
+        },
+        future::AwaitedFutures,
         rstest::{RsTestAttributes, RsTestData, RsTestInfo},
-        testcase::TestCase,
+        runtime::Runtime,
+        testcase::{is_placeholder, Expected, TestCase},
         vlist::ValueList,
     },
     utils::attr_is,
@@ -31,8 +43,17 @@ use wrapper::WrapByModule;
 pub(crate) use fixture::render as fixture;
 pub(crate) mod inject;
 
-pub(crate) fn single(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
-    let resolver = resolver::fixtures::get(info.data.fixtures());
+pub(crate) fn single(test: ItemFn, info: RsTestInfo) -> TokenStream {
+    let name = test.sig.ident.clone();
+    single_named(&name, test, &info)
+}
+
+/// Render the test `name` that embeds and calls the `test` function.
+fn single_named(name: &Ident, mut test: ItemFn, info: &RsTestInfo) -> TokenStream {
+    let resolver = (
+        resolver::fixtures::get(info.data.fixtures()),
+        resolver::values::get(info.data.values()),
+    );
     let args = test.sig.inputs.iter().cloned().collect::<Vec<_>>();
     let attrs = std::mem::replace(&mut test.attrs, Default::default());
     let asyncness = test.sig.asyncness.clone();
@@ -45,7 +66,7 @@ pub(crate) fn single(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         .collect::<Vec<_>>();
 
     single_test_case(
-        &test.sig.ident,
+        name,
         &test.sig.ident,
         &args,
         &attrs,
@@ -55,122 +76,320 @@ pub(crate) fn single(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         resolver,
         &info.attributes,
         &generic_types,
+        None,
     )
 }
 
 pub(crate) fn parametrize(test: ItemFn, info: RsTestInfo) -> TokenStream {
-    let RsTestInfo { data, attributes } = info;
-    let resolver_fixtures = resolver::fixtures::get(data.fixtures());
+    let rendered_cases = parametrize_cases(&test, &info.data, &info.attributes);
 
-    let rendered_cases = cases_data(&data, test.sig.ident.span())
-        .map(|(name, attrs, resolver)| {
-            TestCaseRender::new(name, attrs, (resolver, &resolver_fixtures))
-        })
-        .map(|case| case.render(&test, &attributes))
-        .collect();
+    test_group(test, rendered_cases, &info.attributes)
+}
 
-    test_group(test, rendered_cases)
+fn parametrize_cases(
+    test: &ItemFn,
+    data: &RsTestData,
+    attributes: &RsTestAttributes,
+) -> TokenStream {
+    let resolver_fixtures = (
+        resolver::fixtures::get(data.fixtures()),
+        resolver::values::get(data.values()),
+    );
+
+    cases_data(data, attributes, test.sig.ident.span())
+        .map(|(name, attrs, expected, resolver)| {
+            TestCaseRender::new(name, attrs, expected, (resolver, &resolver_fixtures))
+        })
+        .map(|case| case.render(test, attributes))
+        .collect()
 }
 
-impl ValueList {
+/// A matrix's dimension: a values list or the zipped values lists that take their values
+/// together.
+struct Dimension<'a>(Vec<&'a ValueList>);
+
+impl<'a> Dimension<'a> {
     fn render(
         &self,
         test: &ItemFn,
         resolver: &dyn Resolver,
         attrs: &[syn::Attribute],
+        expected: Option<&Expected>,
         attributes: &RsTestAttributes,
+        accepted: &AcceptedCombinations,
+        prefix: &[usize],
     ) -> TokenStream {
         let span = test.sig.ident.span();
+        let allow_labels = self.allow_labels(attributes);
         let test_cases = self
-            .argument_data(resolver)
-            .map(|(name, r)| TestCaseRender::new(Ident::new(&name, span), attrs, r))
-            .map(|test_case| test_case.render(&test, &attributes));
+            .argument_data(resolver, attributes)
+            .enumerate()
+            .filter(|(index, _)| accepted.accept(prefix, *index))
+            .map(|(index, (name, r))| {
+                let attrs = self.attrs(attrs, index);
+                TestCaseRender::new(Ident::new(&name, span), &attrs, expected, r)
+                    .render(&test, &attributes)
+            })
+            .map(|test_case| quote! { #allow_labels #test_case });
 
         quote! { #(#test_cases)* }
     }
 
-    fn argument_data<'a>(
+    /// Labels and values' names can contain upper case letters
+    fn allow_labels(&self, attributes: &RsTestAttributes) -> TokenStream {
+        if self
+            .0
+            .iter()
+            .all(|vlist| vlist.labels.is_empty() && vlist.names.iter().all(Option::is_none))
+            && !attributes.names_from_values()
+        {
+            Default::default()
+        } else {
+            quote! { #[allow(non_snake_case)] }
+        }
+    }
+
+    /// The `attrs` followed by the attributes of the `index`-th values.
+    fn attrs(&self, attrs: &[syn::Attribute], index: usize) -> Vec<syn::Attribute> {
+        attrs
+            .iter()
+            .chain(self.0.iter().flat_map(|vlist| vlist.attrs(index)))
+            .cloned()
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.0
+            .iter()
+            .map(|vlist| vlist.values.len())
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// The value's label or, if `names = values` is used and the value has no name, the
+    /// value itself.
+    fn value_tag(vlist: &ValueList, index: usize, attributes: &RsTestAttributes) -> Option<String> {
+        vlist.label(index).map(ToOwned::to_owned).or_else(|| {
+            names::value_suffix(attributes, &vlist.values[index]).filter(|_| !vlist.is_named(index))
+        })
+    }
+
+    /// The `index`-th test's name: the zipped values are named `zip_<index>` followed by
+    /// the values' names or tags.
+    fn name(&self, index: usize, attributes: &RsTestAttributes) -> String {
+        let (name, tags) = match self.0.as_slice() {
+            [vlist] => (
+                vlist.name(index),
+                Self::value_tag(vlist, index, attributes)
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+            ),
+            lists => (
+                format!("zip_{:0len$}", index + 1, len = self.len().display_len()),
+                lists
+                    .iter()
+                    .filter_map(|vlist| {
+                        if vlist.is_named(index) {
+                            Some(vlist.name(index))
+                        } else {
+                            Self::value_tag(vlist, index, attributes)
+                        }
+                    })
+                    .collect(),
+            ),
+        };
+        std::iter::once(name)
+            .chain(tags)
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+
+    fn argument_data(
         &'a self,
         resolver: &'a dyn Resolver,
-    ) -> impl Iterator<Item = (String, Box<(&'a dyn Resolver, (String, Expr))>)> + 'a {
-        let max_len = self.values.len();
-        self.values.iter().enumerate().map(move |(index, expr)| {
-            let name = format!(
-                "{}_{:0len$}",
-                self.arg,
-                index + 1,
-                len = max_len.display_len()
-            );
-            let resolver_this = (self.arg.to_string(), expr.clone());
-            (name, Box::new((resolver, resolver_this)))
+        attributes: &'a RsTestAttributes,
+    ) -> impl Iterator<Item = (String, Box<(&'a dyn Resolver, HashMap<String, Expr>)>)> + 'a {
+        (0..self.len()).map(move |index| {
+            let resolver_this = self
+                .0
+                .iter()
+                .map(|vlist| (vlist.arg.to_string(), vlist.values[index].clone()))
+                .collect::<HashMap<_, _>>();
+            (
+                self.name(index, attributes),
+                Box::new((resolver, resolver_this)),
+            )
         })
     }
 }
 
+/// The combinations' prefixes (as dimensions' values indexes) to render: the `#[exclude]`
+/// and `#[only]` filters prune them while the matrix is rendered, the n-wise modes render
+/// just the prefixes of the covering array's rows.
+enum AcceptedCombinations<'a> {
+    Filtered(&'a Matrix<'a>, Vec<&'a CombinationsFilter>),
+    Covering(HashSet<Vec<usize>>),
+}
+
+impl<'a> AcceptedCombinations<'a> {
+    fn new(matrix: &'a Matrix<'a>, data: &'a RsTestData, attributes: &RsTestAttributes) -> Self {
+        let filters = data.filters().collect::<Vec<_>>();
+        match attributes.combinations() {
+            CombinationsMode::All | CombinationsMode::Zip => Self::Filtered(matrix, filters),
+            CombinationsMode::NWise(strength) => {
+                let rows = match accepted_combinations(matrix, &filters) {
+                    Some(candidates) => covering::covering_subset(&candidates, strength),
+                    None => covering::covering_array(&matrix.sizes(), strength),
+                };
+                Self::Covering(
+                    rows.iter()
+                        .flat_map(|c| (1..=c.len()).map(move |len| c[..len].to_vec()))
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    fn accept(&self, prefix: &[usize], index: usize) -> bool {
+        let mut node = prefix.to_vec();
+        node.push(index);
+        match self {
+            Self::Filtered(matrix, filters) => accept_prefix(matrix, filters, &node),
+            Self::Covering(prefixes) => prefixes.contains(&node),
+        }
+    }
+}
+
 fn _matrix_recursive<'a>(
     test: &ItemFn,
-    list_values: &'a [&'a ValueList],
+    dimensions: &'a [Dimension<'a>],
     resolver: &dyn Resolver,
-    attrs: &'a [syn::Attribute],
+    attrs: &[syn::Attribute],
+    expected: Option<&Expected>,
     attributes: &RsTestAttributes,
+    accepted: &AcceptedCombinations,
+    prefix: &[usize],
 ) -> TokenStream {
-    if list_values.len() == 0 {
+    if dimensions.len() == 0 {
         return Default::default();
     }
-    let vlist = list_values[0];
-    let list_values = &list_values[1..];
+    let dimension = &dimensions[0];
+    let dimensions = &dimensions[1..];
 
-    if list_values.len() == 0 {
-        vlist.render(test, resolver, attrs, attributes)
+    if dimensions.len() == 0 {
+        dimension.render(
+            test, resolver, attrs, expected, attributes, accepted, prefix,
+        )
     } else {
         let span = test.sig.ident.span();
-        let modules = vlist.argument_data(resolver).map(move |(name, resolver)| {
-            _matrix_recursive(test, list_values, &resolver, attrs, attributes)
-                .wrap_by_mod(&Ident::new(&name, span))
-        });
+        let allow_labels = dimension.allow_labels(attributes);
+        let modules = dimension
+            .argument_data(resolver, attributes)
+            .enumerate()
+            .filter(|(index, _)| accepted.accept(prefix, *index))
+            .filter_map(move |(index, (name, resolver))| {
+                let prefix = [prefix, &[index]].concat();
+                let attrs = dimension.attrs(attrs, index);
+                let module = _matrix_recursive(
+                    test, dimensions, &resolver, &attrs, expected, attributes, accepted, &prefix,
+                );
+                // The filters can remove all the combinations of an accepted prefix
+                if module.is_empty() {
+                    return None;
+                }
+                let module = module.wrap_by_mod(&Ident::new(&name, span));
+                Some(quote! { #allow_labels #module })
+            });
 
         quote! { #(#modules)* }
     }
 }
 
 pub(crate) fn matrix(test: ItemFn, info: RsTestInfo) -> TokenStream {
-    let RsTestInfo {
-        data, attributes, ..
-    } = info;
+    let rendered_cases = matrix_cases(&test, &info.data, &info.attributes);
+
+    test_group(test, rendered_cases, &info.attributes)
+}
+
+fn matrix_cases(test: &ItemFn, data: &RsTestData, attributes: &RsTestAttributes) -> TokenStream {
     let span = test.sig.ident.span();
 
-    let cases = cases_data(&data, span).collect::<Vec<_>>();
+    let cases = cases_data(data, attributes, span).collect::<Vec<_>>();
 
-    let resolver = resolver::fixtures::get(data.fixtures());
-    let rendered_cases = if cases.is_empty() {
-        let list_values = data.list_values().collect::<Vec<_>>();
-        _matrix_recursive(&test, &list_values, &resolver, &[], &attributes)
+    let resolver = (
+        resolver::fixtures::get(data.fixtures()),
+        resolver::values::get(data.values()),
+    );
+    let matrix = Matrix::from_test(data, attributes);
+    let dimensions = (0..matrix.dimensions.len())
+        .map(|d| Dimension(matrix.dimension(d).collect()))
+        .collect::<Vec<_>>();
+    let accepted = AcceptedCombinations::new(&matrix, data, attributes);
+    if cases.is_empty() {
+        _matrix_recursive(
+            test,
+            &dimensions,
+            &resolver,
+            &[],
+            None,
+            attributes,
+            &accepted,
+            &[],
+        )
     } else {
         cases
             .into_iter()
-            .map(|(case_name, attrs, case_resolver)| {
-                let list_values = data.list_values().collect::<Vec<_>>();
+            .map(|(case_name, attrs, expected, case_resolver)| {
                 _matrix_recursive(
-                    &test,
-                    &list_values,
+                    test,
+                    &dimensions,
                     &(case_resolver, &resolver),
                     attrs,
-                    &attributes,
+                    expected,
+                    attributes,
+                    &accepted,
+                    &[],
                 )
                 .wrap_by_mod(&case_name)
             })
             .collect()
-    };
-
-    test_group(test, rendered_cases)
+    }
 }
 
-fn resolve_default_test_attr(is_async: bool) -> TokenStream {
-    if is_async {
-        quote! { #[async_std::test] }
-    } else {
-        quote! { #[test] }
+fn resolve_default_test_attr(is_async: bool, runtime: Option<Runtime>) -> Option<TokenStream> {
+    if !is_async {
+        return Some(quote! { #[test] });
     }
+    runtime.map(|runtime| match runtime {
+        Runtime::AsyncStd => quote! { #[async_std::test] },
+        Runtime::Tokio => quote! { #[tokio::test] },
+        Runtime::TokioMultiThread => quote! { #[tokio::test(flavor = "multi_thread")] },
+        // smol doesn't provide a test attribute: the test body is executed by `block_on`
+        Runtime::Smol => quote! { #[test] },
+    })
+}
+
+fn ambiguous_runtime_error(name: &Ident, features: &[&str]) -> TokenStream {
+    syn::Error::new(
+        name.span(),
+        format!(
+            "Cannot select a runtime for async test: more than one of rstest's runtime \
+            features is enabled ({}). Use #[rstest(runtime = <name>)], add a test attribute \
+            like #[tokio::test] or enable just one of them",
+            features.join(", ")
+        ),
+    )
+    .to_compile_error()
+}
+
+fn missed_runtime_error(name: &Ident) -> TokenStream {
+    syn::Error::new(
+        name.span(),
+        "Cannot find a runtime for async test: enable one of `async-std`, `tokio` or `smol` \
+        rstest's features, use #[rstest(runtime = <name>)] or add a test attribute \
+        like #[tokio::test]",
+    )
+    .to_compile_error()
 }
 
 fn render_exec_call(fn_path: Path, args: &[Ident], is_async: bool) -> TokenStream {
@@ -181,6 +400,74 @@ fn render_exec_call(fn_path: Path, args: &[Ident], is_async: bool) -> TokenStrea
     }
 }
 
+/// Await the `#[future(awt)]` arguments before call the function: one after the other
+/// in the arguments' order or concurrently if `join` is required.
+fn render_await_futures(awaited: &AwaitedFutures) -> TokenStream {
+    let args = &awaited.args;
+    if !awaited.join {
+        return quote! {
+            #(let #args = #args.await;)*
+        };
+    }
+    let pinned = args
+        .iter()
+        .map(|a| format_ident!("__{}_future", a))
+        .collect::<Vec<_>>();
+    let outputs = args
+        .iter()
+        .map(|a| format_ident!("__{}_output", a))
+        .collect::<Vec<_>>();
+    let poll = render_poll_fn(quote! {
+        |cx: &mut std::task::Context<'_>| {
+            #(
+                if #outputs.is_none() {
+                    if let std::task::Poll::Ready(v) =
+                        std::future::Future::poll(#pinned.as_mut(), cx) {
+                        #outputs = Some(v);
+                    }
+                }
+            )*
+            if #(#outputs.is_some())&&* {
+                std::task::Poll::Ready((#(#outputs.take().unwrap(),)*))
+            } else {
+                std::task::Poll::Pending
+            }
+        }
+    });
+    quote! {
+        let (#(#args,)*) = {
+            #(
+                let mut #pinned = Box::pin(#args);
+                let mut #outputs = None;
+            )*
+            #poll.await
+        };
+    }
+}
+
+/// A future that completes when the `poll` closure returns `Ready`: a stand-in for
+/// `std::future::poll_fn()` that is not available in older compilers.
+fn render_poll_fn(poll: TokenStream) -> TokenStream {
+    quote! {
+        {
+            struct __PollFn<F>(F);
+            impl<T, F> std::future::Future for __PollFn<F>
+            where
+                F: FnMut(&mut std::task::Context<'_>) -> std::task::Poll<T> + Unpin,
+            {
+                type Output = T;
+                fn poll(
+                    mut self: std::pin::Pin<&mut Self>,
+                    cx: &mut std::task::Context<'_>,
+                ) -> std::task::Poll<T> {
+                    (self.0)(cx)
+                }
+            }
+            __PollFn(#poll)
+        }
+    }
+}
+
 /// Render a single test case:
 ///
 /// * `name` - Test case name
@@ -191,6 +478,7 @@ fn render_exec_call(fn_path: Path, args: &[Ident], is_async: bool) -> TokenStrea
 /// * `test_impl` - If you want embed test function (should be the one called by `testfn_name`)
 /// * `resolver` - The resolver used to resolve injected values
 /// * `attributes` - Test attributes to select test behaviour
+/// * `expected` - The case's expected result: the test returns `()` if it's given
 ///
 fn single_test_case<'a>(
     name: &Ident,
@@ -203,22 +491,38 @@ fn single_test_case<'a>(
     resolver: impl Resolver,
     attributes: &'a RsTestAttributes,
     generic_types: &[Ident],
+    expected: Option<&Expected>,
 ) -> TokenStream {
-    let (attrs, trace_me): (Vec<_>, Vec<_>) =
+    let (mut attrs, trace_me): (Vec<_>, Vec<_>) =
         attrs.iter().cloned().partition(|a| !attr_is(a, "trace"));
+    attrs.extend(expected::should_panic_attr(expected));
+    let (attrs, timeout) = match timeout::extract_timeout(attrs) {
+        Ok(extracted) => extracted,
+        Err(e) => return e.to_compile_error(),
+    };
     let mut attributes = attributes.clone();
     if trace_me.len() > 0 {
         attributes.add_trace(format_ident!("trace"));
     }
-    let inject = inject::resolve_aruments(args.iter(), &resolver, generic_types);
+    // Every test that injects some fixtures has its own shared fixtures and the guards are
+    // just kept alive till the end of the test
+    let shared_cache = Some(resolver::fixtures::render_shared_cache())
+        .filter(|_| inject::uses_shared_cache(args.iter(), &resolver));
+    let (inject, _guards) =
+        inject::resolve_aruments_and_guards(args.iter(), &resolver, generic_types);
     let args = args
         .iter()
         .filter_map(MaybeIdent::maybe_ident)
         .cloned()
         .collect::<Vec<_>>();
+    let await_futures = render_await_futures(&attributes.awaited());
     let trace_args = trace_arguments(args.iter(), &attributes);
 
     let is_async = asyncness.is_some();
+    let runtime = attributes
+        .runtime()
+        .map(|runtime| Ok(Some(runtime)))
+        .unwrap_or_else(Runtime::from_features);
     // If no injected attribut provided use the default one
     let test_attr = if attrs
         .iter()
@@ -226,19 +530,57 @@ fn single_test_case<'a>(
     {
         None
     } else {
-        Some(resolve_default_test_attr(is_async))
+        match &runtime {
+            Err(features) if is_async => return ambiguous_runtime_error(name, features),
+            _ => match resolve_default_test_attr(is_async, runtime.clone().unwrap_or_default()) {
+                Some(test_attr) => Some(test_attr),
+                None => return missed_runtime_error(name),
+            },
+        }
+    };
+    let runtime = runtime.unwrap_or_default();
+    // The runtime of a given test attribute can be different from the default one
+    let timer_runtime = match test_attr {
+        Some(_) => runtime,
+        None => attrs.iter().find_map(Runtime::from_test_attribute),
+    };
+    let mut execute = render_exec_call(testfn_name.clone().into(), &args, is_async);
+    let output = match expected {
+        Some(expected) => {
+            execute = expected::render_check(execute, expected);
+            &ReturnType::Default
+        }
+        None => output,
+    };
+
+    let mut body = quote! {
+        #test_impl
+        #shared_cache
+        #inject
+        #await_futures
+        #trace_args
+        println!("{:-^40}", " TEST START ");
+        #execute
+    };
+    if let Some(timeout) = &timeout {
+        body = if is_async {
+            timeout::render_async_timeout(name, timeout, body, timer_runtime)
+        } else {
+            timeout::render_sync_timeout(name, timeout, body)
+        };
+    }
+    let (asyncness, body) = match runtime {
+        Some(Runtime::Smol) if is_async && test_attr.is_some() => {
+            (None, quote! { smol::block_on(async { #body }) })
+        }
+        _ => (asyncness, body),
     };
-    let execute = render_exec_call(testfn_name.clone().into(), &args, is_async);
 
     quote! {
         #test_attr
         #(#attrs)*
         #asyncness fn #name() #output {
-            #test_impl
-            #inject
-            #trace_args
-            println!("{:-^40}", " TEST START ");
-            #execute
+            #body
         }
     }
 }
@@ -288,6 +630,8 @@ fn generics_clean_up<'a>(
                 self.0
                     .insert(i.path.segments.first().unwrap().ident.clone());
             }
+            // Also the nested ones: `Vec<T>`
+            syn::visit::visit_type_path(self, i)
         }
     }
     let mut outs: Used = Default::default();
@@ -321,14 +665,21 @@ fn generics_clean_up<'a>(
 struct TestCaseRender<'a> {
     name: Ident,
     attrs: &'a [syn::Attribute],
+    expected: Option<&'a Expected>,
     resolver: Box<dyn Resolver + 'a>,
 }
 
 impl<'a> TestCaseRender<'a> {
-    pub fn new<R: Resolver + 'a>(name: Ident, attrs: &'a [syn::Attribute], resolver: R) -> Self {
+    pub fn new<R: Resolver + 'a>(
+        name: Ident,
+        attrs: &'a [syn::Attribute],
+        expected: Option<&'a Expected>,
+        resolver: R,
+    ) -> Self {
         TestCaseRender {
             name,
             attrs,
+            expected,
             resolver: Box::new(resolver),
         }
     }
@@ -357,13 +708,24 @@ impl<'a> TestCaseRender<'a> {
             self.resolver,
             &attributes,
             &generic_types,
+            self.expected,
         )
     }
 }
 
-fn test_group(mut test: ItemFn, rendered_cases: TokenStream) -> TokenStream {
+fn test_group(
+    mut test: ItemFn,
+    rendered_cases: TokenStream,
+    attributes: &RsTestAttributes,
+) -> TokenStream {
     let fname = &test.sig.ident;
     test.attrs = vec![];
+    let tracked_files = attributes.tracked_files();
+    let allow_names = if attributes.names_from_values() || attributes.name_template().is_some() {
+        quote! { #![allow(non_snake_case)] }
+    } else {
+        Default::default()
+    };
 
     quote! {
         #[cfg(test)]
@@ -371,8 +733,11 @@ fn test_group(mut test: ItemFn, rendered_cases: TokenStream) -> TokenStream {
 
         #[cfg(test)]
         mod #fname {
+            #allow_names
             use super::*;
 
+            #(const _: &[u8] = include_bytes!(#tracked_files);)*
+
             #rendered_cases
         }
     }
@@ -402,22 +767,57 @@ fn format_case_name(case: &TestCase, index: usize, display_len: usize) -> String
     )
 }
 
-fn cases_data(
-    data: &RsTestData,
+/// The resolver of a case: the case's values and the fixtures' paths of the omitted and `_`
+/// arguments that don't have a default value. These paths are spanned on the case to report
+/// a missed fixture where the value is missed.
+type CaseResolver<'a> = (HashMap<String, &'a syn::Expr>, HashMap<String, ExprPath>);
+
+fn cases_data<'a>(
+    data: &'a RsTestData,
+    attributes: &RsTestAttributes,
     name_span: Span,
-) -> impl Iterator<Item = (Ident, &[syn::Attribute], HashMap<String, &syn::Expr>)> {
-    let display_len = data.cases().count().display_len();
-    data.cases().enumerate().map({
-        move |(n, case)| {
-            let resolver_case = data
+) -> impl Iterator<
+    Item = (
+        Ident,
+        &'a [syn::Attribute],
+        Option<&'a Expected>,
+        CaseResolver<'a>,
+    ),
+> {
+    let names = names::cases_names(data, attributes);
+    let case_args = data
+        .case_args()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let defaults = data
+        .values()
+        .map(|v| v.name.to_string())
+        .collect::<Vec<_>>();
+    data.cases().zip(names).map({
+        move |(case, name)| {
+            let values = case.values(&case_args);
+            let fixtures_paths = data
                 .case_args()
-                .map(|a| a.to_string())
-                .zip(case.args.iter())
+                .filter(|arg| !defaults.contains(&arg.to_string()))
+                .filter_map(|arg| {
+                    let span = match values.iter().find(|(name, _)| arg == name) {
+                        Some((_, value)) if is_placeholder(value) => value.span(),
+                        Some(_) => return None,
+                        None => case.span(),
+                    };
+                    let fixture = Ident::new(&params::fixture_name(arg).to_string(), span);
+                    Some((arg.to_string(), resolver::fixtures::name_path(fixture)))
+                })
+                .collect::<HashMap<_, _>>();
+            let resolver_case = values
+                .into_iter()
+                .filter(|(_, arg)| !is_placeholder(arg))
                 .collect::<HashMap<_, _>>();
             (
-                Ident::new(&format_case_name(case, n + 1, display_len), name_span),
+                Ident::new(&name, name_span),
                 case.attrs.as_slice(),
-                resolver_case,
+                case.expected.as_ref(),
+                (resolver_case, fixtures_paths),
             )
         }
     })
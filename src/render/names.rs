@@ -0,0 +1,242 @@
+/// Compute the names of the generated tests when `names = values` or `name = "..."` are
+/// used: the names are always valid identifiers and, when the template gives the same
+/// name to more cases, the case's index is used.
+use std::collections::HashMap;
+
+use syn::{Expr, Ident, Lit, UnOp};
+
+use super::{format_case_name, DisplayLen};
+use crate::{
+    parse::{
+        names::{NameTemplate, TemplatePart},
+        rstest::{RsTestAttributes, RsTestData},
+    },
+    utils::{sanitize, sanitize_chars},
+};
+
+/// A readable rendering of simple values: literals, negative numbers and paths (just the
+/// last segment). `None` for all other expressions.
+pub(crate) fn value_name(expr: &Expr) -> Option<String> {
+    let name = match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(s) => s.value(),
+            Lit::Char(c) => c.value().to_string(),
+            Lit::Int(i) => i.base10_digits().to_owned(),
+            Lit::Float(f) => f.base10_digits().to_owned(),
+            Lit::Bool(b) => b.value.to_string(),
+            _ => return None,
+        },
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+            return value_name(&unary.expr).map(|name| format!("neg_{}", name))
+        }
+        Expr::Path(path) if path.qself.is_none() => path.path.segments.last()?.ident.to_string(),
+        Expr::Group(group) => return value_name(&group.expr),
+        _ => return None,
+    };
+    Some(sanitize_chars(&name)).filter(|n| !n.is_empty())
+}
+
+/// The value's name appended to the value list's name.
+pub(crate) fn value_suffix(attributes: &RsTestAttributes, expr: &Expr) -> Option<String> {
+    if attributes.names_from_values() {
+        value_name(expr)
+    } else {
+        None
+    }
+}
+
+fn render_template(template: &NameTemplate, values: &HashMap<String, &Expr>) -> Option<String> {
+    let name = template
+        .parts
+        .iter()
+        .map(|part| match part {
+            TemplatePart::Text(text) => Some(text.clone()),
+            TemplatePart::Arg(arg) => values.get(arg).and_then(|&v| value_name(v)),
+        })
+        .collect::<Option<String>>()?;
+    Some(sanitize(&name)).filter(|n| !n.is_empty())
+}
+
+fn is_valid_ident(name: &str) -> bool {
+    syn::parse_str::<Ident>(name).is_ok()
+}
+
+/// The cases' names: the description given by `case::description` is always used.
+pub(crate) fn cases_names(data: &RsTestData, attributes: &RsTestAttributes) -> Vec<String> {
+    let display_len = data.cases().count().display_len();
+    let template = attributes.name_template();
+    let case_args = data
+        .case_args()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    let rendered = data
+        .cases()
+        .map(|case| {
+            if case.description.is_some() {
+                return None;
+            }
+            match &template {
+                Some(template) => {
+                    let values = case.values(&case_args).into_iter().collect();
+                    render_template(template, &values)
+                }
+                None if attributes.names_from_values() => {
+                    let values = case
+                        .values(&case_args)
+                        .into_iter()
+                        .filter_map(|(_, value)| value_name(value))
+                        .collect::<Vec<_>>();
+                    Some(values.join("_")).filter(|n| !n.is_empty())
+                }
+                None => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut occurrences = HashMap::new();
+    rendered
+        .iter()
+        .flatten()
+        .for_each(|name| *occurrences.entry(name.as_str()).or_insert(0) += 1);
+    data.cases()
+        .zip(rendered.iter())
+        .enumerate()
+        .map(|(n, (case, rendered))| {
+            let default = format_case_name(case, n + 1, display_len);
+            match rendered {
+                Some(name)
+                    if template.is_some()
+                        && occurrences[name.as_str()] == 1
+                        && is_valid_ident(name) =>
+                {
+                    name.clone()
+                }
+                Some(name) => format!("{}_{}", default, name.trim_start_matches('_')),
+                None => default,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::{
+        parse::{
+            names::NamesDefinition,
+            rstest::{RsTestInfo, RsTestItem},
+            testcase::TestCase,
+        },
+        test::{assert_eq, *},
+    };
+    use mytest::rstest;
+
+    #[rstest]
+    #[case::string(r#""Hello world!""#, Some("Hello_world_"))]
+    #[case::empty_string(r#""""#, None)]
+    #[case::int("42", Some("42"))]
+    #[case::negative("-42", Some("neg_42"))]
+    #[case::float("-1.5", Some("neg_1_5"))]
+    #[case::char("'a'", Some("a"))]
+    #[case::bool("true", Some("true"))]
+    #[case::path("State::Processing", Some("Processing"))]
+    #[case::constant("MAX", Some("MAX"))]
+    #[case::call("State::new()", None)]
+    #[case::macro_call("vec![1, 2]", None)]
+    #[case::byte_string(r#"b"abc""#, None)]
+    fn render_simple_values(#[case] value: &str, #[case] expected: Option<&str>) {
+        assert_eq!(expected.map(ToOwned::to_owned), value_name(&expr(value)));
+    }
+
+    fn info(names: Option<&str>, args: &[&str], cases: &[TestCase]) -> RsTestInfo {
+        let mut info = RsTestInfo::default();
+        info.data
+            .items
+            .extend(args.iter().map(|a| RsTestItem::CaseArgName(ident(a))));
+        info.data
+            .items
+            .extend(cases.iter().cloned().map(Into::into));
+        if let Some(names) = names {
+            info.attributes
+                .set_names(syn::parse_str::<NamesDefinition>(names).unwrap());
+        }
+        info
+    }
+
+    fn case(args: &[&str]) -> TestCase {
+        args.iter().collect()
+    }
+
+    #[test]
+    fn not_change_the_default_names() {
+        let info = info(None, &["a"], &[case(&["42"]), case(&["State::A"])]);
+
+        assert_eq!(
+            vec!["case_1", "case_2"],
+            cases_names(&info.data, &info.attributes)
+        );
+    }
+
+    #[test]
+    fn append_the_simple_values_to_the_cases_names() {
+        let info = info(
+            Some("names = values"),
+            &["a", "b"],
+            &[
+                case(&["-42", "State::Processing"]),
+                case(&["vec![1]", "2"]),
+                case(&["vec![1]", "foo()"]),
+                case(&["1", "2"]).with_description("described"),
+            ],
+        );
+
+        assert_eq!(
+            vec![
+                "case_1_neg_42_Processing",
+                "case_2_2",
+                "case_3",
+                "case_4_described"
+            ],
+            cases_names(&info.data, &info.attributes)
+        );
+    }
+
+    #[test]
+    fn use_the_name_template() {
+        let info = info(
+            Some(r#"name = "{input}_yields_{expected}""#),
+            &["input", "expected"],
+            &[
+                case(&["0", "0"]),
+                case(&["1", "State::One"]),
+                case(&["vec![2]", "2"]),
+                case(&["1", "State::One"]),
+                case(&["3", "4"]).with_description("described"),
+            ],
+        );
+
+        assert_eq!(
+            vec![
+                "_0_yields_0",
+                "case_2_1_yields_One",
+                "case_3",
+                "case_4_1_yields_One",
+                "case_5_described"
+            ],
+            cases_names(&info.data, &info.attributes)
+        );
+    }
+
+    #[test]
+    fn fall_back_to_index_if_the_template_renders_a_keyword() {
+        let info = info(
+            Some(r#"name = "{a}""#),
+            &["a"],
+            &[case(&["true"]), case(&["ok"])],
+        );
+
+        assert_eq!(
+            vec!["case_1_true", "ok"],
+            cases_names(&info.data, &info.attributes)
+        );
+    }
+}
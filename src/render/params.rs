@@ -0,0 +1,360 @@
+/// Render parametrized fixtures' handshake: the procedural macro cannot look at the
+/// fixture definition from the test, so every parametrized fixture define a `macro_rules!`
+/// (imported with the fixture's name) that collect its dimensions and every test or
+/// fixture that uses it with `#[params]` call it to know its values.
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse_quote, Expr, Ident, ItemFn, Visibility};
+
+use crate::{
+    parse::{
+        params::{ParamsDim, ParamsGroup},
+        rstest::{RsTestData, RsTestInfo, RsTestItem},
+        vlist::ValueList,
+        Fixture,
+    },
+    resolver,
+};
+
+/// The fixture's name that resolve the argument `ident`: the leading `_` is removed.
+pub(crate) fn fixture_name(ident: &Ident) -> Ident {
+    let id_str = ident.to_string();
+    if id_str.starts_with("_") && !id_str.starts_with("__") {
+        Ident::new(&id_str[1..], ident.span())
+    } else {
+        ident.clone()
+    }
+}
+
+/// The label of a fixture's value: the last segment of the value's path if any.
+pub(crate) fn value_label(expr: &Expr) -> Option<Ident> {
+    match expr {
+        Expr::Path(p) if p.qself.is_none() => p.path.segments.last().map(|s| s.ident.clone()),
+        _ => None,
+    }
+}
+
+/// `macro_rules!` cannot be exported as `pub` without `#[macro_export]`: the fixture's
+/// macro visibility is clamped to the crate.
+pub(crate) fn macro_visibility(vis: &Visibility) -> Visibility {
+    match vis {
+        Visibility::Public(_) => parse_quote! { pub(crate) },
+        other => other.clone(),
+    }
+}
+
+fn macro_name(fixture: &Ident) -> Ident {
+    format_ident!("__rstest_params_{}", fixture)
+}
+
+/// Call the first fixture's macro: every macro append its group to the collected
+/// ones and call the next one till the last one that call `__rstest_params!` with
+/// all collected groups and the `payload`.
+fn render_macros_chain(fixtures: &[Ident], payload: TokenStream) -> TokenStream {
+    let first = &fixtures[0];
+    let rest = &fixtures[1..];
+    quote! {
+        #first! { [#(#rest)*] [] { #payload } }
+    }
+}
+
+/// Define the fixture's macro that append `group` to the collected groups and import it
+/// with the fixture's name.
+pub(crate) fn render_fixture_macro(
+    vis: &Visibility,
+    fixture: &Ident,
+    group: &ParamsGroup,
+) -> TokenStream {
+    let name = macro_name(fixture);
+    let vis = macro_visibility(vis);
+    quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #name {
+            ([] [$($collected:tt)*] $($payload:tt)*) => {
+                ::rstest::__rstest_params! { [$($collected)* #group] $($payload)* }
+            };
+            ([$next:ident $($rest:ident)*] [$($collected:tt)*] $($payload:tt)*) => {
+                $next! { [$($rest)*] [$($collected)* #group] $($payload)* }
+            };
+        }
+
+        #[allow(unused_imports)]
+        #vis use #name as #fixture;
+    }
+}
+
+/// Render the fixture's macro: if the fixture uses other parametrized fixtures their
+/// dimensions should be collected before.
+pub(crate) fn render_fixture_handshake(
+    vis: &Visibility,
+    fixture: &Ident,
+    dims: Vec<ParamsDim>,
+    params: &[Ident],
+) -> TokenStream {
+    if params.is_empty() {
+        render_fixture_macro(vis, fixture, &ParamsGroup(dims))
+    } else {
+        let fixtures = params.iter().map(fixture_name).collect::<Vec<_>>();
+        render_macros_chain(&fixtures, quote! { fixture #vis #fixture #(#dims)* })
+    }
+}
+
+/// The fixture's group is composed by its own dimensions followed by the ones of
+/// the parametrized fixtures that it uses.
+pub(crate) fn render_collected_fixture_macro(
+    vis: &Visibility,
+    fixture: &Ident,
+    dims: Vec<ParamsDim>,
+    groups: Vec<ParamsGroup>,
+) -> TokenStream {
+    let dims = dims
+        .into_iter()
+        .chain(groups.into_iter().flat_map(|g| g.0))
+        .collect();
+    render_fixture_macro(vis, fixture, &ParamsGroup(dims))
+}
+
+/// First step for tests that use parametrized fixtures: call the fixtures' macros to
+/// collect their dimensions and render the test again through `__rstest_params!`.
+pub(crate) fn render_test_handshake(
+    args: TokenStream,
+    test: &ItemFn,
+    info: &RsTestInfo,
+) -> TokenStream {
+    let fixtures = info
+        .attributes
+        .params()
+        .map(fixture_name)
+        .collect::<Vec<_>>();
+    render_macros_chain(&fixtures, quote! { test { #args } #test })
+}
+
+/// Replace every `#[params]` fixture by a values list: one value for each combination
+/// of the fixture's dimensions.
+pub(crate) fn expand_params(
+    info: &mut RsTestInfo,
+    groups: Vec<ParamsGroup>,
+) -> Result<(), syn::Error> {
+    let params = info.attributes.params().cloned().collect::<Vec<_>>();
+    if params.len() != groups.len() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!(
+                "Cannot determinate the parameters count: expected {} parametrized fixtures but found {}",
+                params.len(),
+                groups.len()
+            ),
+        ));
+    }
+    for (arg, group) in params.iter().zip(groups.into_iter()) {
+        let value_list = params_value_list(&mut info.data, arg, &group);
+        info.data.items.push(value_list.into());
+    }
+    Ok(())
+}
+
+/// Every value calls the fixture's `__params_*()` function with the combination's indexes.
+fn params_value_list(data: &mut RsTestData, arg: &Ident, group: &ParamsGroup) -> ValueList {
+    let name = fixture_name(arg);
+    let path = resolver::fixtures::name_path(name.clone());
+    let fixture = take_fixture(data, arg);
+    let (positional, named) = fixture
+        .as_ref()
+        .map(|f| (f.positional.0.as_slice(), f.named.as_slice()))
+        .unwrap_or_default();
+
+    let (values, labels) = combinations(&group.0)
+        .into_iter()
+        .map(|indexes| {
+            let value = resolver::fixtures::render_params_call(
+                &path,
+                &parse_quote! { &[#(#indexes),*] },
+                positional,
+                named,
+            );
+            (value, label(&group.0, &indexes))
+        })
+        .unzip();
+    ValueList {
+        labels,
+        ..ValueList::new(name, values)
+    }
+}
+
+/// Remove the explicit fixture's resolution (if any).
+fn take_fixture(data: &mut RsTestData, arg: &Ident) -> Option<Fixture> {
+    let position = data.items.iter().position(|it| match it {
+        RsTestItem::Fixture(f) => &f.name == arg,
+        _ => false,
+    })?;
+    match data.items.remove(position) {
+        RsTestItem::Fixture(f) => Some(f),
+        _ => unreachable!(),
+    }
+}
+
+fn combinations(dims: &[ParamsDim]) -> Vec<Vec<usize>> {
+    dims.iter().fold(vec![vec![]], |acc, dim| {
+        acc.into_iter()
+            .flat_map(|indexes| {
+                (0..dim.len()).map(move |i| {
+                    let mut indexes = indexes.clone();
+                    indexes.push(i);
+                    indexes
+                })
+            })
+            .collect()
+    })
+}
+
+fn label(dims: &[ParamsDim], indexes: &[usize]) -> Option<String> {
+    let labels = dims
+        .iter()
+        .zip(indexes)
+        .filter_map(|(dim, &i)| dim.labels[i].as_ref().map(|l| l.to_string()))
+        .collect::<Vec<_>>();
+    if labels.is_empty() {
+        None
+    } else {
+        Some(labels.join("_"))
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    fn dim(name: &str, labels: &[&str]) -> ParamsDim {
+        ParamsDim::new(
+            ident(name),
+            labels
+                .iter()
+                .map(|&l| if l == "_" { None } else { Some(ident(l)) })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn compute_label_from_value_path() {
+        assert_eq!(Some(ident("Sqlite")), value_label(&expr("Backend::Sqlite")));
+        assert_eq!(Some(ident("SQLITE")), value_label(&expr("SQLITE")));
+        assert_eq!(None, value_label(&expr("42")));
+        assert_eq!(None, value_label(&expr("Backend::new()")));
+    }
+
+    #[test]
+    fn combine_all_dimensions_values() {
+        let dims = vec![dim("a", &["A", "B"]), dim("b", &["_", "_", "_"])];
+
+        let combinations = combinations(&dims);
+
+        assert_eq!(
+            vec![
+                vec![0, 0],
+                vec![0, 1],
+                vec![0, 2],
+                vec![1, 0],
+                vec![1, 1],
+                vec![1, 2]
+            ],
+            combinations
+        );
+    }
+
+    #[test]
+    fn join_labels_and_skip_missed_ones() {
+        let dims = vec![dim("a", &["A", "B"]), dim("b", &["_", "C"])];
+
+        assert_eq!(Some("B".to_owned()), label(&dims, &[1, 0]));
+        assert_eq!(Some("A_C".to_owned()), label(&dims, &[0, 1]));
+        assert_eq!(None, label(&[dim("b", &["_", "C"])], &[0]));
+    }
+
+    #[test]
+    fn replace_params_fixtures_by_values_list() {
+        let mut info: RsTestInfo = Default::default();
+        info.attributes.add_params(vec![ident("_backend")]);
+        info.data.items.push(fixture("_backend", vec!["42"]).into());
+
+        expand_params(
+            &mut info,
+            vec![ParamsGroup(vec![dim("backend", &["Sqlite", "_"])])],
+        )
+        .unwrap();
+
+        assert_eq!(0, info.data.fixtures().count());
+        let vlist = info.data.list_values().next().unwrap();
+        assert_eq!(ident("backend"), vlist.arg);
+        assert_eq!(
+            vec![
+                expr("backend::__params_partial_1(&[0usize], __shared.clone(), 42, |__value_0| backend::partial_1(__value_0))"),
+                expr("backend::__params_partial_1(&[1usize], __shared.clone(), 42, |__value_0| backend::partial_1(__value_0))"),
+            ],
+            vlist.values
+        );
+        assert_eq!(vec![Some("Sqlite".to_owned()), None], vlist.labels);
+    }
+
+    #[test]
+    fn raise_error_if_the_groups_count_is_wrong() {
+        let mut info: RsTestInfo = Default::default();
+        info.attributes.add_params(vec![ident("a"), ident("b")]);
+
+        let error =
+            expand_params(&mut info, vec![ParamsGroup(vec![dim("a", &["_"])])]).unwrap_err();
+
+        assert_in!(error.to_string(), "Cannot determinate the parameters count");
+    }
+
+    #[test]
+    fn use_just_own_dimensions_if_no_params() {
+        let vis: Visibility = parse_quote! { pub };
+
+        let rendered =
+            render_fixture_handshake(&vis, &ident("backend"), vec![dim("backend", &["A"])], &[]);
+
+        assert_in!(
+            rendered.to_string(),
+            "macro_rules ! __rstest_params_backend"
+        );
+        assert_in!(
+            rendered.to_string(),
+            "pub (crate) use __rstest_params_backend as backend"
+        );
+        assert_in!(rendered.to_string(), "((backend [A]))");
+    }
+
+    #[test]
+    fn call_the_used_fixtures_macros_chain() {
+        let vis: Visibility = parse_quote! {};
+
+        let rendered = render_fixture_handshake(
+            &vis,
+            &ident("db"),
+            vec![],
+            &[ident("_backend"), ident("engine")],
+        );
+
+        assert_eq!(
+            rendered.to_string(),
+            quote! { backend! { [engine] [] { fixture db } } }.to_string()
+        );
+    }
+
+    #[test]
+    fn collect_own_dimensions_before_the_used_ones() {
+        let vis: Visibility = parse_quote! {};
+
+        let rendered = render_collected_fixture_macro(
+            &vis,
+            &ident("db"),
+            vec![dim("db", &["X"])],
+            vec![ParamsGroup(vec![dim("backend", &["A", "B"])])],
+        );
+
+        assert_in!(rendered.to_string(), "((db [X]) (backend [A B]))");
+    }
+}
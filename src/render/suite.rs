@@ -0,0 +1,264 @@
+/// `#[rstest_suite]` contract tests: the suite module is replaced by a `macro_rules!`
+/// (imported with the module's name) that pass the module and the macro's arguments to
+/// `__rstest_suite!`. Every instance is a module named by the implementation type where
+/// the generic tests are rendered for the given type.
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, FnArg, GenericParam, Ident, Item, ItemFn, ItemMod, Type};
+
+use super::{params::macro_visibility, types::wrapper};
+use crate::{
+    parse::suite::SuiteInstance,
+    utils::{attr_ends_with, sanitize, snake_case},
+};
+
+/// Define the suite's macro.
+pub(crate) fn render_suite_macro(suite: &ItemMod) -> TokenStream {
+    let name = &suite.ident;
+    let macro_name = format_ident!("__rstest_suite_{}", name);
+    let vis = macro_visibility(&suite.vis);
+    quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            ($($args:tt)*) => {
+                ::rstest::__rstest_suite! { #suite $($args)* }
+            };
+        }
+
+        #[allow(unused_imports)]
+        #vis use #macro_name as #name;
+    }
+}
+
+/// The name of the instance's module: the implementation type in `snake_case`.
+fn instance_name(ty: &Type) -> Option<Ident> {
+    let rendered = ty.to_token_stream().to_string().replace(' ', "");
+    let name = snake_case(sanitize(&rendered).trim_matches('_'));
+    if name.is_empty() {
+        None
+    } else {
+        Some(Ident::new(&name, Span::call_site()))
+    }
+}
+
+pub(crate) fn render_instance(instance: SuiteInstance) -> TokenStream {
+    let SuiteInstance {
+        mut suite,
+        ty,
+        name,
+        fixture,
+    } = instance;
+    suite.ident = match name.or_else(|| instance_name(&ty)) {
+        Some(name) => name,
+        None => {
+            return syn::Error::new_spanned(
+                &ty,
+                "Cannot name the suite's module by this type: use a named type",
+            )
+            .to_compile_error()
+        }
+    };
+    if let Some((_, items)) = suite.content.as_mut() {
+        let tests = std::mem::take(items);
+        items.push(parse_quote! {
+            #[allow(unused_imports)]
+            use super::*;
+        });
+        for item in tests {
+            match item {
+                Item::Fn(test) if is_generic_test(&test) => {
+                    items.extend(instantiate(test, &ty, fixture.as_ref()))
+                }
+                item => items.push(item),
+            }
+        }
+    }
+    suite.into_token_stream()
+}
+
+fn is_generic_test(item_fn: &ItemFn) -> bool {
+    let rstest: syn::PathSegment = parse_quote! { rstest };
+    item_fn
+        .attrs
+        .iter()
+        .any(|attr| attr_ends_with(attr, &rstest))
+        && type_param(item_fn).is_some()
+}
+
+/// The suite's tests are generic in their first type parameter.
+fn type_param(item_fn: &ItemFn) -> Option<&Ident> {
+    item_fn
+        .sig
+        .generics
+        .params
+        .iter()
+        .find_map(|param| match param {
+            GenericParam::Type(tp) => Some(&tp.ident),
+            _ => None,
+        })
+}
+
+fn is_type_param(ty: &Type, param: &Ident) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident(param))
+}
+
+/// Replace the generic test by a test for `ty` that calls it by turbofish: the arguments
+/// of the type parameter's type without any attribute are resolved by `fixture`.
+fn instantiate(mut test: ItemFn, ty: &Type, fixture: Option<&syn::Path>) -> Vec<Item> {
+    let param = type_param(&test).cloned().unwrap();
+    let generic = format_ident!("__rstest_suite_{}", test.sig.ident);
+    let mut instance = wrapper(&test, &[(&param, ty)], &parse_quote! { #generic });
+    if let Some(fixture) = fixture {
+        for (arg, instance_arg) in test.sig.inputs.iter().zip(instance.sig.inputs.iter_mut()) {
+            if let (FnArg::Typed(arg), FnArg::Typed(instance_arg)) = (arg, instance_arg) {
+                if arg.attrs.is_empty() && is_type_param(&arg.ty, &param) {
+                    instance_arg.attrs.push(parse_quote! { #[from(#fixture)] });
+                }
+            }
+        }
+    }
+    test.sig.ident = generic;
+    test.attrs = vec![];
+    for arg in test.sig.inputs.iter_mut() {
+        if let FnArg::Typed(arg) = arg {
+            arg.attrs = vec![];
+        }
+    }
+    vec![Item::Fn(test), Item::Fn(instance)]
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    fn instance(code: &str) -> ItemMod {
+        syn::parse2(render_instance(code.ast())).unwrap()
+    }
+
+    fn items(module: &ItemMod) -> &[Item] {
+        &module.content.as_ref().unwrap().1
+    }
+
+    #[test]
+    fn define_the_suite_macro() {
+        let suite: ItemMod = "pub mod contract { fn test() {} }".ast();
+
+        let rendered = render_suite_macro(&suite).to_string();
+
+        assert_in!(rendered, "macro_rules ! __rstest_suite_contract");
+        assert_in!(rendered, ":: rstest :: __rstest_suite ! { pub mod contract");
+        assert_in!(
+            rendered,
+            "pub (crate) use __rstest_suite_contract as contract"
+        );
+    }
+
+    #[test]
+    fn name_the_module_by_the_type() {
+        let module = instance("mod contract {} InMemory");
+
+        assert_eq!("in_memory", module.ident.to_string());
+        assert_eq!(
+            &parse_quote! {
+                #[allow(unused_imports)]
+                use super::*;
+            } as &Item,
+            &items(&module)[0]
+        );
+    }
+
+    #[test]
+    fn use_the_given_module_name() {
+        let module = instance("mod contract {} InMemory as memory");
+
+        assert_eq!("memory", module.ident.to_string());
+    }
+
+    #[test]
+    fn name_the_module_by_the_generic_type() {
+        let module = instance("mod contract {} FileStore<TempDir>");
+
+        assert_eq!("file_store_temp_dir", module.ident.to_string());
+    }
+
+    #[test]
+    fn render_the_generic_tests_for_the_type() {
+        let module = instance(
+            r#"
+            mod contract {
+                use super::*;
+
+                #[rstest]
+                #[case(42)]
+                fn put<S: Storage>(#[case] value: u32, mut store: S, #[with(3)] other: S) {
+                    store.put(value)
+                }
+
+                #[fixture]
+                fn value<S: Storage>() -> u32 { 42 }
+
+                #[rstest]
+                fn not_generic(value: u32) {}
+            }
+            InMemory, fixture = stores::in_memory
+            "#,
+        );
+
+        let expected: Vec<Item> = vec![
+            parse_quote! {
+                #[allow(unused_imports)]
+                use super::*;
+            },
+            parse_quote! { use super::*; },
+            parse_quote! {
+                fn __rstest_suite_put<S: Storage>(value: u32, mut store: S, other: S) {
+                    store.put(value)
+                }
+            },
+            parse_quote! {
+                #[rstest]
+                #[case(42)]
+                fn put(
+                    #[case] value: u32,
+                    #[from(stores::in_memory)] store: InMemory,
+                    #[with(3)] other: InMemory
+                ) {
+                    __rstest_suite_put::<InMemory>(value, store, other)
+                }
+            },
+            parse_quote! {
+                #[fixture]
+                fn value<S: Storage>() -> u32 { 42 }
+            },
+            parse_quote! {
+                #[rstest]
+                fn not_generic(value: u32) {}
+            },
+        ];
+        assert_eq!(expected, items(&module));
+    }
+
+    #[test]
+    fn not_inject_the_fixture_if_not_given() {
+        let module = instance(
+            r#"
+            mod contract {
+                #[rstest]
+                fn test<S: Storage>(store: S) {}
+            }
+            InMemory
+            "#,
+        );
+
+        let expected: Item = parse_quote! {
+            #[rstest]
+            fn test(store: InMemory) {
+                __rstest_suite_test::<InMemory>(store)
+            }
+        };
+        assert_eq!(&expected, &items(&module)[2]);
+    }
+}
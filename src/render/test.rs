@@ -71,6 +71,34 @@ mod single_test_should {
         assert_eq!(inner_fn, input_fn);
     }
 
+    #[test]
+    fn create_its_own_shared_fixtures_cache_before_resolve_the_arguments() {
+        let input_fn: ItemFn = r#"pub fn test(fix: String) {}"#.ast();
+
+        let result: ItemFn = single(input_fn, Default::default()).ast();
+        let position = |stmt: &Stmt| result.block.stmts.iter().position(|s| s == stmt);
+
+        let cache = position(&resolver::fixtures::render_shared_cache()).unwrap();
+        let inject = position(
+            &"let fix = fix::__guarded_default(__shared.clone(), || fix::default());".ast(),
+        )
+        .unwrap();
+
+        assert!(cache < inject);
+    }
+
+    #[test]
+    fn not_create_the_shared_fixtures_cache_if_no_fixture_is_injected() {
+        let input_fn: ItemFn = r#"pub fn test() {}"#.ast();
+
+        let result: ItemFn = single(input_fn, Default::default()).ast();
+
+        assert!(!result
+            .block
+            .stmts
+            .contains(&resolver::fixtures::render_shared_cache()));
+    }
+
     #[rstest]
     fn not_copy_any_attributes(
         #[values(
@@ -198,6 +226,83 @@ mod single_test_should {
         assert_eq!(&result.attrs[1..], attributes.as_slice());
     }
 
+    #[rstest]
+    #[case::async_std("runtime = async_std", parse_quote! { #[async_std::test] })]
+    #[case::tokio("runtime = tokio", parse_quote! { #[tokio::test] })]
+    #[case::tokio_multi_thread(
+        "runtime = tokio_multi_thread",
+        parse_quote! { #[tokio::test(flavor = "multi_thread")] }
+    )]
+    fn use_the_given_runtime_test_attribute(#[case] info: &str, #[case] test_attribute: Attribute) {
+        let input_fn: ItemFn = r#"async fn test(_s: String) {}"#.ast();
+
+        let result: ItemFn = single(input_fn, info.ast()).ast();
+
+        assert_eq!(result.attrs[0], test_attribute);
+        assert!(result.sig.asyncness.is_some());
+    }
+
+    #[test]
+    fn execute_smol_test_by_block_on() {
+        let input_fn: ItemFn = r#"async fn test(_s: String) {}"#.ast();
+
+        let result: ItemFn = single(input_fn, "runtime = smol".ast()).ast();
+
+        assert_eq!(result.attrs[0], parse_quote! { #[test] });
+        assert!(result.sig.asyncness.is_none());
+        assert_in!(result.block.display_code(), "smol :: block_on (async {");
+    }
+
+    #[test]
+    fn not_use_runtime_if_test_attribute_is_given() {
+        let mut input_fn: ItemFn = r#"async fn test(_s: String) {}"#.ast();
+        input_fn.attrs = attrs("#[actix_rt::test]");
+
+        let result: ItemFn = single(input_fn, "runtime = smol".ast()).ast();
+
+        assert_eq!(result.attrs, attrs("#[actix_rt::test]"));
+        assert!(result.sig.asyncness.is_some());
+    }
+
+    #[rstest]
+    #[case::sync("", "", "recv_timeout")]
+    #[case::async_std("async", "runtime = async_std", "async_std :: future :: timeout")]
+    #[case::tokio("async", "runtime = tokio", "tokio :: time :: timeout")]
+    #[case::smol("async", "runtime = smol", "smol :: Timer :: after")]
+    fn execute_the_test_with_timeout(
+        #[case] prefix: &str,
+        #[case] runtime: &str,
+        #[case] expected: &str,
+    ) {
+        let mut input_fn: ItemFn = format!(r#"{} fn test(_s: String) {{}} "#, prefix).ast();
+        input_fn.attrs = attrs("#[should_panic] #[timeout(std::time::Duration::from_millis(10))]");
+
+        let result: ItemFn = single(input_fn, runtime.ast()).ast();
+
+        assert_eq!(&result.attrs[1..], attrs("#[should_panic]").as_slice());
+        assert_in!(result.block.display_code(), expected);
+        assert_in!(
+            result.block.display_code(),
+            "std :: time :: Duration :: from_millis (10)"
+        );
+    }
+
+    #[rstest]
+    #[case::tokio("#[tokio::test]", "tokio :: time :: timeout")]
+    #[case::unknown("#[actix_rt::test]", "std :: thread :: sleep (__timeout)")]
+    fn use_the_timer_of_the_given_test_attribute(#[case] attr: &str, #[case] expected: &str) {
+        let mut input_fn: ItemFn = r#"async fn test() {}"#.ast();
+        input_fn.attrs = attrs(&format!(
+            "{} #[timeout(std::time::Duration::from_millis(10))]",
+            attr
+        ));
+
+        let result: ItemFn = single(input_fn, "runtime = async_std".ast()).ast();
+
+        assert_in!(result.block.display_code(), expected);
+        assert_not_in!(result.block.display_code(), "async_std");
+    }
+
     #[rstest]
     #[case::sync(false, false)]
     #[case::async_fn(true, true)]
@@ -211,6 +316,23 @@ mod single_test_should {
 
         assert_eq!(use_await, last_stmt.is_await());
     }
+
+    #[rstest]
+    #[case::in_order(false, "let a = a.await; let b = b.await;")]
+    #[case::join(true, "let (a, b,) =")]
+    fn await_futures_before_call_test(#[case] join: bool, #[case] expected: &str) {
+        let input_fn: ItemFn = r#"async fn test(a: u32, b: u32, c: u32) {}"#.ast();
+        let mut info = RsTestInfo::default();
+        info.attributes.set_awaited(AwaitedFutures {
+            args: vec![ident("a"), ident("b")],
+            join,
+        });
+
+        let result = single(input_fn, info).to_string();
+
+        assert_in!(result, expected.ast::<TokenStream>().to_string());
+        assert_not_in!(result, "c . await");
+    }
 }
 
 struct TestsGroup {
@@ -504,6 +626,85 @@ mod cases_should {
         assert!(!format!("{:?}", output.requested_test.attrs).contains("should_panic"));
     }
 
+    #[test]
+    fn check_the_expected_results() {
+        let (item_fn, info) = TestCaseBuilder::from(r#"fn sum(a: u32, b: u32) -> u32 { a + b }"#)
+            .push_case(parse_meta::<TestCase, _>("case(2, 3 => 5)"))
+            .push_case(parse_meta::<TestCase, _>("case(2, 3)"))
+            .push_case(parse_meta::<TestCase, _>(r#"case(0, 0 => panics "zero")"#))
+            .take();
+
+        let tokens = parametrize(item_fn.clone(), info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+        let check = tests[0].block.stmts.last().unwrap();
+        assert_eq!(ReturnType::Default, tests[0].sig.output);
+        assert_in!(quote! { #check }.to_string(), "assert_eq ! (5 , __result)");
+        assert_eq!(item_fn.sig.output, tests[1].sig.output);
+        assert_eq!(ReturnType::Default, tests[2].sig.output);
+        assert_eq!(
+            attrs(r#"#[should_panic(expected = "zero")]"#),
+            &tests[2].attrs[1..]
+        );
+    }
+
+    #[test]
+    fn use_the_default_values_of_omitted_case_arguments() {
+        let (item_fn, mut info) =
+            TestCaseBuilder::from(r#"fn sum(a: u32, b: u32, c: u32) -> u32 { a + b + c }"#)
+                .push_case(parse_meta::<TestCase, _>("case(1, _, 3)"))
+                .push_case(parse_meta::<TestCase, _>("case(1)"))
+                .take();
+        info.data.items.extend(vec![
+            RsTestItem::ArgumentValue(arg_value("b", "20")),
+            RsTestItem::ArgumentValue(arg_value("c", "30")),
+        ]);
+
+        let tokens = parametrize(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+        let (first, second) = (&tests[0].block, &tests[1].block);
+        let (first, second) = (
+            quote! { #first }.to_string(),
+            quote! { #second }.to_string(),
+        );
+        assert_in!(first, "let b = 20 ;");
+        assert_in!(first, "let c = 3 ;");
+        assert_in!(second, "let b = 20 ;");
+        assert_in!(second, "let c = 30 ;");
+    }
+
+    #[test]
+    fn resolve_omitted_case_arguments_without_default_by_fixtures() {
+        let (item_fn, mut info) =
+            TestCaseBuilder::from(r#"fn sum(a: u32, b: u32, c: u32) -> u32 { a + b + c }"#)
+                .push_case(parse_meta::<TestCase, _>("case(1, _, 3)"))
+                .push_case(parse_meta::<TestCase, _>("case(1)"))
+                .take();
+        info.data
+            .items
+            .push(RsTestItem::ArgumentValue(arg_value("c", "30")));
+
+        let tokens = parametrize(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+        let (first, second) = (&tests[0].block, &tests[1].block);
+        let (first, second) = (
+            quote! { #first }.to_string(),
+            quote! { #second }.to_string(),
+        );
+        assert_in!(
+            first,
+            "let b = b :: __guarded_default (__shared . clone () , | | b :: default ()) ;"
+        );
+        assert_in!(first, "let c = 3 ;");
+        assert_in!(
+            second,
+            "let b = b :: __guarded_default (__shared . clone () , | | b :: default ()) ;"
+        );
+        assert_in!(second, "let c = 30 ;");
+    }
+
     #[test]
     fn should_mark_test_with_given_attributes() {
         let (item_fn, info) =
@@ -548,6 +749,26 @@ mod cases_should {
         assert_eq!(given_attrs.as_slice(), &test_attrs[..l]);
     }
 
+    #[test]
+    fn case_timeout_should_override_function_timeout() {
+        let (mut item_fn, info) = TestCaseBuilder::from(r#"fn test(v: i32){}"#)
+            .push_case(TestCase::from("42").with_attrs(attrs("#[timeout(case_timeout())]")))
+            .push_case(TestCase::from("24"))
+            .take();
+        item_fn.attrs = attrs("#[timeout(fn_timeout())]");
+
+        let tests = TestsGroup::from(parametrize(item_fn, info)).get_all_tests();
+
+        let first = tests[0].block.display_code();
+        let second = tests[1].block.display_code();
+        assert_in!(first, "case_timeout ()");
+        assert_not_in!(first, "fn_timeout ()");
+        assert_in!(second, "fn_timeout ()");
+        assert!(tests
+            .iter()
+            .all(|t| !t.attrs.iter().any(|a| attr_is(a, "timeout"))));
+    }
+
     #[test]
     fn mark_user_function_as_test() {
         let (item_fn, info) = TestCaseBuilder::from(
@@ -872,13 +1093,7 @@ mod matrix_cases_should {
         RsTestData {
             items: fn_args_idents(item_fn)
                 .cloned()
-                .map(|it| {
-                    ValueList {
-                        arg: it,
-                        values: vec![],
-                    }
-                    .into()
-                })
+                .map(|it| ValueList::new(it, vec![]).into())
                 .collect(),
         }
     }
@@ -973,6 +1188,44 @@ mod matrix_cases_should {
         }
     }
 
+    #[test]
+    fn add_values_attributes_after_function_and_case_attributes() {
+        let item_fn: ItemFn = r#"#[first] fn test(c: u32, a: u32, b: u32){}"#.ast();
+        let info = RsTestInfo {
+            data: RsTestData {
+                items: vec![
+                    ident("c").into(),
+                    vec!["1"]
+                        .into_iter()
+                        .collect::<TestCase>()
+                        .with_attrs(attrs("#[case_attr]"))
+                        .into(),
+                    syn::parse_str::<ValueList>("a => [1, #[should_panic] 0]")
+                        .unwrap()
+                        .into(),
+                    syn::parse_str::<ValueList>("b => [#[ignore] 2, 3]")
+                        .unwrap()
+                        .into(),
+                ],
+            },
+            ..Default::default()
+        };
+
+        let tokens = matrix(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+        let tests_attrs = tests.iter().map(|t| &t.attrs[1..]).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                attrs("#[first] #[case_attr] #[ignore]"),
+                attrs("#[first] #[case_attr]"),
+                attrs("#[first] #[case_attr] #[should_panic] #[ignore]"),
+                attrs("#[first] #[case_attr] #[should_panic]"),
+            ],
+            tests_attrs
+        );
+    }
+
     #[test]
     fn add_return_type_if_any() {
         let item_fn: ItemFn = "fn function(fix: String) -> Result<i32, String> { Ok(42) }".ast();
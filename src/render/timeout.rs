@@ -0,0 +1,172 @@
+/// Render the `#[timeout(duration)]` test's body wrappers: the sync tests use just the
+/// standard library and the async ones the timer of their runtime.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Expr, Ident};
+
+use crate::{parse::runtime::Runtime, utils::attr_is};
+
+/// Remove all `#[timeout(...)]` attributes and return the last one: case's attributes
+/// follow the function's ones, so a case's timeout overrides the function's one.
+pub(crate) fn extract_timeout(
+    attrs: Vec<Attribute>,
+) -> syn::Result<(Vec<Attribute>, Option<Expr>)> {
+    let (timeouts, attrs): (Vec<_>, Vec<_>) =
+        attrs.into_iter().partition(|a| attr_is(a, "timeout"));
+    let timeout = timeouts
+        .iter()
+        .map(|a| a.parse_args::<Expr>())
+        .collect::<syn::Result<Vec<_>>>()?
+        .pop();
+    Ok((attrs, timeout))
+}
+
+fn timeout_message(name: &Ident) -> TokenStream {
+    quote! {
+        panic!(
+            "Timeout: test `{}::{}` ran for {:?}, more than the {:?} timeout",
+            module_path!(),
+            stringify!(#name),
+            __start.elapsed(),
+            __timeout
+        )
+    }
+}
+
+/// Execute the sync test's `body` in a worker thread (with the test's thread name) and
+/// wait its result at most `timeout`.
+pub(crate) fn render_sync_timeout(name: &Ident, timeout: &Expr, body: TokenStream) -> TokenStream {
+    let message = timeout_message(name);
+    quote! {
+        let __timeout: std::time::Duration = #timeout;
+        let __start = std::time::Instant::now();
+        let (__sender, __receiver) = std::sync::mpsc::channel();
+        let mut __builder = std::thread::Builder::new();
+        if let Some(name) = std::thread::current().name() {
+            __builder = __builder.name(name.to_owned());
+        }
+        let __handle = __builder
+            .spawn(move || {
+                let _ = __sender.send((move || { #body })());
+            })
+            .expect("Cannot spawn the test's thread");
+        match __receiver.recv_timeout(__timeout) {
+            Ok(result) => result,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => #message,
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => {
+                std::panic::resume_unwind(__handle.join().unwrap_err())
+            }
+        }
+    }
+}
+
+/// Wait the async test's `body` at most `timeout` by the timer of the `runtime` that
+/// executes the test: `None` is an unknown runtime and so the timer is a new thread.
+pub(crate) fn render_async_timeout(
+    name: &Ident,
+    timeout: &Expr,
+    body: TokenStream,
+    runtime: Option<Runtime>,
+) -> TokenStream {
+    let message = timeout_message(name);
+    let result = match runtime {
+        Some(Runtime::AsyncStd) => quote! {
+            async_std::future::timeout(__timeout, __test).await.ok()
+        },
+        Some(Runtime::Tokio) | Some(Runtime::TokioMultiThread) => quote! {
+            tokio::time::timeout(__timeout, __test).await.ok()
+        },
+        Some(Runtime::Smol) => quote! {
+            smol::future::or(async { Some(__test.await) }, async {
+                smol::Timer::after(__timeout).await;
+                None
+            })
+            .await
+        },
+        None => render_thread_timer(),
+    };
+    quote! {
+        let __timeout: std::time::Duration = #timeout;
+        let __start = std::time::Instant::now();
+        let __test = async { #body };
+        match #result {
+            Some(result) => result,
+            None => #message,
+        }
+    }
+}
+
+/// Poll the `__test` future till it completes or a timer thread signal that `__timeout`
+/// is elapsed.
+fn render_thread_timer() -> TokenStream {
+    let poll = super::render_poll_fn(quote! {
+        |cx: &mut std::task::Context<'_>| {
+            let polled = std::future::Future::poll(__test.as_mut(), cx);
+            if let std::task::Poll::Ready(result) = polled {
+                return std::task::Poll::Ready(Some(result));
+            }
+            let mut state = __expired.lock().unwrap();
+            if state.0 {
+                std::task::Poll::Ready(None)
+            } else {
+                state.1 = Some(cx.waker().clone());
+                std::task::Poll::Pending
+            }
+        }
+    });
+    quote! {
+        {
+            let __expired = std::sync::Arc::new(
+                std::sync::Mutex::new((false, None::<std::task::Waker>))
+            );
+            {
+                let __expired = __expired.clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(__timeout);
+                    let mut state = __expired.lock().unwrap();
+                    state.0 = true;
+                    if let Some(waker) = state.1.take() {
+                        waker.wake();
+                    }
+                });
+            }
+            let mut __test = Box::pin(__test);
+            #poll.await
+        }
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    #[test]
+    fn use_the_last_timeout() {
+        let test_attrs = attrs("#[should_panic] #[timeout(a)] #[other] #[timeout(b)]");
+
+        let (remain, timeout) = extract_timeout(test_attrs).unwrap();
+
+        assert_eq!(Some(expr("b")), timeout);
+        assert_eq!(attrs("#[should_panic] #[other]"), remain);
+    }
+
+    #[test]
+    fn no_timeout() {
+        let (remain, timeout) = extract_timeout(attrs("#[should_panic]")).unwrap();
+
+        assert_eq!(None, timeout);
+        assert_eq!(1, remain.len());
+    }
+
+    #[test]
+    fn raise_error_if_timeout_has_no_duration() {
+        let error = extract_timeout(attrs("#[timeout]")).unwrap_err();
+
+        assert_in!(
+            error.to_string(),
+            "expected attribute arguments in parentheses"
+        );
+    }
+}
@@ -0,0 +1,232 @@
+/// Type-parametrized tests: for every combination of the `#[types(T = [...])]` lists the
+/// tests are rendered for a not generic wrapper that calls the test function by turbofish.
+/// The arguments' types of the wrapper are the concrete ones, so the magic conversion can
+/// be used and the generic fixtures infer the same types.
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse_quote, visit_mut::VisitMut, FnArg, GenericParam, Ident, ItemFn, Path, Type, Visibility,
+};
+
+use super::{
+    matrix_cases, parametrize_cases, render_exec_call, single_named, test_group,
+    wrapper::WrapByModule,
+};
+use crate::{
+    parse::{rstest::RsTestInfo, types::TypeList},
+    refident::MaybeIdent,
+};
+
+pub(crate) fn render(test: ItemFn, info: RsTestInfo) -> TokenStream {
+    let lists = info.data.type_lists().collect::<Vec<_>>();
+    let rendered = render_lists(&test, &lists, &[], &info);
+
+    test_group(test, rendered, &info.attributes)
+}
+
+/// Render a module for each type of the first list (or a test if there are no other lists,
+/// cases or values).
+fn render_lists(
+    test: &ItemFn,
+    lists: &[&TypeList],
+    types: &[(&Ident, &Type)],
+    info: &RsTestInfo,
+) -> TokenStream {
+    let (list, others) = match lists.split_first() {
+        Some(split) => split,
+        None => return render_cases(test, types, info),
+    };
+    let span = test.sig.ident.span();
+    let callee = callee(test);
+    let is_leaf = others.is_empty() && !info.data.has_cases() && !info.data.has_list_values();
+    list.types
+        .iter()
+        .enumerate()
+        .map(|(index, ty)| {
+            let types = [types, &[(&list.ident, ty)]].concat();
+            let name = Ident::new(&list.name(index), span);
+            let rendered = if is_leaf {
+                single_named(&name, wrapper(test, &types, &callee), info)
+            } else {
+                render_lists(test, others, &types, info).wrap_by_mod(&name)
+            };
+            quote! {
+                #[allow(non_snake_case)]
+                #rendered
+            }
+        })
+        .collect()
+}
+
+fn render_cases(test: &ItemFn, types: &[(&Ident, &Type)], info: &RsTestInfo) -> TokenStream {
+    let mut wrapper = wrapper(test, types, &callee(test));
+    let rendered_cases = if info.data.has_list_values() {
+        matrix_cases(&wrapper, &info.data, &info.attributes)
+    } else {
+        parametrize_cases(&wrapper, &info.data, &info.attributes)
+    };
+    wrapper.attrs = vec![];
+
+    quote! {
+        #wrapper
+
+        #rendered_cases
+    }
+}
+
+/// The wrappers are defined in a submodule of the test function's one.
+fn callee(test: &ItemFn) -> Path {
+    let name = &test.sig.ident;
+    parse_quote! { super::#name }
+}
+
+/// Replace the type parameters by the given types.
+struct TypesReplacer<'a>(&'a [(&'a Ident, &'a Type)]);
+
+impl VisitMut for TypesReplacer<'_> {
+    fn visit_type_mut(&mut self, node: &mut Type) {
+        let replaced = match node {
+            Type::Path(path) if path.qself.is_none() => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.0.iter().find(|(param, _)| *param == ident)),
+            _ => None,
+        };
+        match replaced {
+            Some(&(_, ty)) => *node = ty.clone(),
+            None => syn::visit_mut::visit_type_mut(self, node),
+        }
+    }
+}
+
+/// A not generic (in the given type parameters) function with the same name, attributes and
+/// arguments of the test that calls `callee` (the test function) by turbofish.
+pub(crate) fn wrapper(test: &ItemFn, types: &[(&Ident, &Type)], callee: &Path) -> ItemFn {
+    let is_replaced = |ident: &Ident| types.iter().any(|(param, _)| *param == ident);
+    let generic_args = test
+        .sig
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(tp) => Some(
+                types
+                    .iter()
+                    .find(|(param, _)| *param == &tp.ident)
+                    .map(|(_, ty)| ty.to_token_stream())
+                    .unwrap_or_else(|| tp.ident.to_token_stream()),
+            ),
+            GenericParam::Const(c) => Some(c.ident.to_token_stream()),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let mut sig = test.sig.clone();
+    sig.generics.params = sig
+        .generics
+        .params
+        .into_iter()
+        .filter(|param| !matches!(param, GenericParam::Type(tp) if is_replaced(&tp.ident)))
+        .collect();
+    if sig.generics.params.is_empty() {
+        sig.generics.lt_token = None;
+        sig.generics.gt_token = None;
+    }
+    TypesReplacer(types).visit_signature_mut(&mut sig);
+    for arg in sig.inputs.iter_mut() {
+        let ident = arg.maybe_ident().cloned();
+        if let (FnArg::Typed(pat_type), Some(ident)) = (arg, ident) {
+            pat_type.pat = Box::new(parse_quote! { #ident });
+        }
+    }
+    let args = sig
+        .inputs
+        .iter()
+        .filter_map(MaybeIdent::maybe_ident)
+        .cloned()
+        .collect::<Vec<_>>();
+    let call = render_exec_call(
+        parse_quote! { #callee::<#(#generic_args),*> },
+        &args,
+        sig.asyncness.is_some(),
+    );
+
+    ItemFn {
+        attrs: test.attrs.clone(),
+        vis: Visibility::Inherited,
+        sig,
+        block: parse_quote! {
+            {
+                #call
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    fn types<'a>(idents: &'a [Ident], types: &'a [Type]) -> Vec<(&'a Ident, &'a Type)> {
+        idents.iter().zip(types.iter()).collect()
+    }
+
+    #[test]
+    fn replace_the_type_parameters_and_call_the_test_by_turbofish() {
+        let test: ItemFn = r#"
+            fn test<'a, T: Clone, U, const N: usize>(mut a: T, b: &'a [U; N], c: Vec<T>) -> T
+            where
+                T: Default,
+            {
+                a
+            }
+            "#
+        .ast();
+        let (idents, tys) = (vec![ident("T")], vec!["u8".ast()]);
+
+        let wrapper = wrapper(&test, &types(&idents, &tys), &parse_quote! { super::test });
+
+        let expected: ItemFn = r#"
+            fn test<'a, U, const N: usize>(a: u8, b: &'a [U; N], c: Vec<u8>) -> u8
+            where
+                u8: Default,
+            {
+                super::test::<u8, U, N>(a, b, c)
+            }
+            "#
+        .ast();
+        assert_eq!(expected, wrapper);
+    }
+
+    #[test]
+    fn await_the_async_test() {
+        let test: ItemFn = "async fn test<T>(a: T) {}".ast();
+        let (idents, tys) = (vec![ident("T")], vec!["String".ast()]);
+
+        let wrapper = wrapper(&test, &types(&idents, &tys), &parse_quote! { super::test });
+
+        let expected: ItemFn = r#"
+            async fn test(a: String) {
+                super::test::<String>(a).await
+            }
+            "#
+        .ast();
+        assert_eq!(expected, wrapper);
+    }
+
+    #[test]
+    fn replace_the_qualified_self_types() {
+        let test: ItemFn = "fn test<T: Iterator>(a: T, b: <T as Iterator>::Item) {}".ast();
+        let (idents, tys) = (vec![ident("T")], vec!["std::vec::IntoIter<u8>".ast()]);
+
+        let wrapper = wrapper(&test, &types(&idents, &tys), &parse_quote! { super::test });
+
+        let expected: ItemFn = r#"
+            fn test(a: std::vec::IntoIter<u8>, b: <std::vec::IntoIter<u8> as Iterator>::Item) {
+                super::test::<std::vec::IntoIter<u8> >(a, b)
+            }
+            "#
+        .ast();
+        assert_eq!(expected, wrapper);
+    }
+}
@@ -5,32 +5,234 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-use proc_macro2::{Ident, Span};
-use syn::{parse_quote, Expr};
+use proc_macro2::Ident;
+use syn::{parse_quote, Expr, ExprPath, Stmt, Type};
 
-use crate::parse::Fixture;
+use crate::parse::{fixture::ArgumentValue, Fixture};
 
 pub(crate) mod fixtures {
     use super::*;
+    use quote::{format_ident, quote_spanned};
+    use syn::ext::IdentExt;
 
     pub(crate) fn get<'a>(fixtures: impl Iterator<Item = &'a Fixture>) -> impl Resolver + 'a {
-        fixtures
-            .map(|f| (f.name.to_string(), extract_resolve_expression(f).into()))
-            .collect::<HashMap<_, Expr>>()
+        FixturesResolver(
+            fixtures
+                .map(|f| {
+                    (
+                        f.name.to_string(),
+                        (extract_resolve_expression(f), f.resolve.as_ref()),
+                    )
+                })
+                .collect(),
+        )
     }
 
+    /// Resolve arguments by fixtures' calls and remember the fixtures' paths
+    struct FixturesResolver<'a>(HashMap<String, (Expr, Option<&'a ExprPath>)>);
+
+    impl<'a> Resolver for FixturesResolver<'a> {
+        fn resolve(&self, ident: &Ident) -> Option<Cow<'_, Expr>> {
+            self.0
+                .get(&ident.to_string())
+                .map(|(e, _)| Cow::Borrowed(e))
+        }
+
+        fn fixture_path(&self, ident: &Ident) -> Option<Cow<'_, ExprPath>> {
+            self.0
+                .get(&ident.to_string())
+                .and_then(|(_, p)| p.map(Cow::Borrowed))
+        }
+    }
+
+    /// The injected fixtures are resolved by the `__guarded_*()` fixture's functions but the
+    /// shared ones that resolve just the value.
     fn extract_resolve_expression(fixture: &Fixture) -> syn::Expr {
-        let name = &fixture.name;
-        let positional = &fixture.positional.0;
-        let pname = format!("partial_{}", positional.len());
-        let partial = Ident::new(&pname, Span::call_site());
-        parse_quote! { #name::#partial(#(#positional), *) }
+        let path = fixture.fixture_path();
+        let call = render_call(&path, &fixture.positional.0, &fixture.named);
+        if fixture.shared {
+            render_shared(&path, parse_quote! { #call.0.unwrap() })
+        } else {
+            call
+        }
+    }
+
+    /// The cache of the shared fixtures' instances: every test creates its own one (see
+    /// `render_shared_cache()`) and gives it to the `__guarded_*()` functions of the fixtures
+    /// that it uses as first argument.
+    pub(crate) fn shared_cache() -> Ident {
+        format_ident!("__shared")
+    }
+
+    /// The type of the shared fixtures' cache: it's `rstest_fixtures::SharedCache` but it's
+    /// spelled by `std` types to not require `rstest_fixtures` for the other fixtures.
+    pub(crate) fn shared_cache_type() -> Type {
+        parse_quote! {
+            ::std::rc::Rc<
+                ::std::cell::RefCell<::std::vec::Vec<::std::boxed::Box<dyn ::std::any::Any>>>
+            >
+        }
+    }
+
+    /// Create the test's cache of the shared fixtures' instances.
+    pub(crate) fn render_shared_cache() -> Stmt {
+        let (cache, cache_type) = (shared_cache(), shared_cache_type());
+        parse_quote! {
+            let #cache: #cache_type = Default::default();
+        }
+    }
+
+    /// Resolve the fixture at `path` by the instance shared in the test: `value` creates it.
+    /// The instance is identified by the type that defines the fixture (the fixture's struct
+    /// or the associated fixture's type) and the fixture's name.
+    pub(crate) fn render_shared(path: &ExprPath, value: Expr) -> Expr {
+        let name = &path.path.segments.last().unwrap().ident;
+        let key: Type = match &path.qself {
+            Some(qself) => (*qself.ty).clone(),
+            None => owner_type(path).unwrap_or_else(|| parse_quote! { #path }),
+        };
+        let name = syn::LitStr::new(&name.unraw().to_string(), name.span());
+        let cache = shared_cache();
+        parse_quote! {
+            ::rstest_fixtures::shared::<#key, _>(&#cache, #name, || #value)
+        }
+    }
+
+    /// The associated fixtures (`#[fixture]` on an `impl` block) are referred by `Type::name`,
+    /// by the qualified path `<Type>::name` or by `Self::name` in the same `impl`: every other
+    /// path is a module's fixture.
+    pub(crate) fn is_associated(path: &ExprPath) -> bool {
+        path.qself.is_some() || owner_type(path).is_some()
+    }
+
+    /// The type of the associated fixture referred by `Type::name`: the path's prefix is a type
+    /// if it ends with an `UpperCamelCase` name (like `Self`), as the naming conventions write
+    /// the types but not the modules.
+    fn owner_type(path: &ExprPath) -> Option<Type> {
+        let segments = &path.path.segments;
+        let prefix = segments.iter().take(segments.len().checked_sub(1)?);
+        let owner = prefix.clone().last()?.ident.unraw().to_string();
+        let leading = &path.path.leading_colon;
+        if owner.starts_with(|c: char| c.is_ascii_uppercase()) {
+            Some(parse_quote! { #leading #(#prefix)::* })
+        } else {
+            None
+        }
+    }
+
+    /// The path of the fixture `name` defined in the current scope.
+    pub(crate) fn name_path(name: Ident) -> ExprPath {
+        ExprPath {
+            attrs: vec![],
+            qself: None,
+            path: name.into(),
+        }
+    }
+
+    /// The function `member` of the associated fixture `name`: `__name_member`.
+    pub(crate) fn associated_member(name: &Ident, member: &str) -> Ident {
+        format_ident!("__{}_{}", name.unraw(), member.trim_start_matches('_'))
+    }
+
+    /// The path of the fixture's function `member`: `path::member` or `Type::__name_member`
+    /// for the associated fixtures. The member is spanned on the fixture's name, so a wrong
+    /// path is reported where it's written.
+    pub(crate) fn member(path: &ExprPath, member: &str) -> ExprPath {
+        let mut path = path.clone();
+        let associated = is_associated(&path);
+        let last = path.path.segments.last_mut().unwrap();
+        let span = last.ident.span();
+        if associated {
+            last.ident = associated_member(&last.ident, member);
+            last.ident.set_span(span);
+        } else {
+            path.path
+                .segments
+                .push(format_ident!("{}", member, span = span).into());
+        }
+        path
+    }
+
+    /// The fixture builder's setter of the `arg` argument: `with_arg` (spanned on `arg`).
+    pub(crate) fn builder_setter(arg: &Ident) -> Ident {
+        format_ident!("with_{}", arg.unraw(), span = arg.span())
+    }
+
+    /// Call the fixture at `path` with the given values: by `__guarded_partial_n()` if just
+    /// the positional ones are given or by the fixture's builder if some values are named (the
+    /// builder has a setter for each argument so an unknown name is reported here). The
+    /// `__guarded_*()` functions take the test's shared fixtures cache and return the value
+    /// with the teardown guards. Just the fixtures that need the guards define them: the last
+    /// argument is the public function that the other ones are called by (see
+    /// `render::inject::render_fallback()`).
+    pub(crate) fn render_call(
+        path: &ExprPath,
+        positional: &[Expr],
+        named: &[ArgumentValue],
+    ) -> Expr {
+        render_injecting_call(path, "__guarded_", None, positional, named)
+    }
+
+    /// Call the parametrized fixture at `path` like `render_call()` but by its `__params_*()`
+    /// functions: they take the `params` indexes of the values to use before the cache.
+    pub(crate) fn render_params_call(
+        path: &ExprPath,
+        params: &Expr,
+        positional: &[Expr],
+        named: &[ArgumentValue],
+    ) -> Expr {
+        render_injecting_call(path, "__params_", Some(params), positional, named)
+    }
+
+    /// The fallback is spanned on the fixture's name: the parametrized fixtures' `__guarded_*()`
+    /// functions don't accept it (see `render::fixture::render_params_stubs()`).
+    fn render_injecting_call(
+        path: &ExprPath,
+        prefix: &str,
+        params: Option<&Expr>,
+        positional: &[Expr],
+        named: &[ArgumentValue],
+    ) -> Expr {
+        let cache = shared_cache();
+        let params = params.into_iter();
+        let span = path.path.segments.last().unwrap().ident.span();
+        if !named.is_empty() {
+            let setters = (0..positional.len()).map(|i| format_ident!("__arg_{}", i));
+            let names = named.iter().map(|v| builder_setter(&v.name));
+            let values = named.iter().map(|v| &v.expr);
+            let builder = member(path, "builder");
+            let build = format_ident!("{}build", prefix);
+            let fallback = quote_spanned! {span => |builder| builder.build() };
+            return parse_quote! {
+                #builder()#(.#setters(#positional))*#(.#names(#values))*
+                    .#build(#(#params,)* #cache.clone(), #fallback)
+            };
+        }
+        if positional.is_empty() {
+            let (guarded, default) = (
+                member(path, &format!("{}default", prefix)),
+                member(path, "default"),
+            );
+            let fallback = quote_spanned! {span => || #default() };
+            return parse_quote! { #guarded(#(#params,)* #cache.clone(), #fallback) };
+        }
+        let n = positional.len();
+        let guarded = member(path, &format!("{}partial_{}", prefix, n));
+        let partial = member(path, &format!("partial_{}", n));
+        let values = (0..n)
+            .map(|i| format_ident!("__value_{}", i, span = span))
+            .collect::<Vec<_>>();
+        let fallback = quote_spanned! {span => |#(#values),*| #partial(#(#values),*) };
+        parse_quote! {
+            #guarded(#(#params,)* #cache.clone(), #(#positional,)* #fallback)
+        }
     }
 
     #[cfg(test)]
     mod should {
         use super::*;
         use crate::test::{assert_eq, *};
+        use mytest::rstest;
 
         #[test]
         fn resolve_by_use_the_given_name() {
@@ -39,7 +241,158 @@ pub(crate) mod fixtures {
 
             let resolved = resolver.resolve(&ident("pippo")).unwrap().into_owned();
 
-            assert_eq!(resolved, "pippo::partial_0()".ast());
+            assert_eq!(
+                resolved,
+                "pippo::__guarded_default(__shared.clone(), || pippo::default())".ast()
+            );
+        }
+
+        #[test]
+        fn resolve_by_the_given_path() {
+            let data = vec![
+                fixture("admin", vec!["42"]).with_resolve(parse_quote! { crate::users::user }),
+                fixture("conn", vec![]).with_resolve(parse_quote! { db }),
+                fixture("pippo", vec![]),
+            ];
+            let resolver = get(data.iter());
+
+            assert_eq!(
+                resolver.resolve(&ident("admin")).unwrap().into_owned(),
+                "crate::users::user::__guarded_partial_1(__shared.clone(), 42, |__value_0| crate::users::user::partial_1(__value_0))".ast()
+            );
+            assert_eq!(
+                resolver.resolve(&ident("conn")).unwrap().into_owned(),
+                "db::__guarded_default(__shared.clone(), || db::default())".ast()
+            );
+            assert_eq!(
+                Some(parse_quote! { crate::users::user }),
+                resolver
+                    .fixture_path(&ident("admin"))
+                    .map(|p| p.into_owned())
+            );
+            assert!(resolver.fixture_path(&ident("pippo")).is_none());
+        }
+
+        #[test]
+        fn resolve_the_named_values_by_the_fixture_builder() {
+            let data = vec![
+                fixture("user", vec![r#""Bob""#]).with_named(vec![arg_value("age", "30")]),
+                fixture("admin", vec![])
+                    .with_named(vec![arg_value("age", "42"), arg_value("name", r#""root""#)])
+                    .with_resolve(parse_quote! { users::user }),
+            ];
+            let resolver = get(data.iter());
+
+            assert_eq!(
+                resolver.resolve(&ident("user")).unwrap().into_owned(),
+                r#"user::builder().__arg_0("Bob").with_age(30).__guarded_build(__shared.clone(), |builder| builder.build())"#
+                    .ast()
+            );
+            assert_eq!(
+                resolver.resolve(&ident("admin")).unwrap().into_owned(),
+                r#"users::user::builder().with_age(42).with_name("root").__guarded_build(__shared.clone(), |builder| builder.build())"#.ast()
+            );
+        }
+
+        #[test]
+        fn call_the_parametrized_fixtures_with_the_values_indexes() {
+            let (path, params) = (parse_quote! { db }, expr("&[1usize, 0usize]"));
+
+            assert_eq!(
+                render_params_call(&path, &params, &[], &[]),
+                "db::__params_default(&[1usize, 0usize], __shared.clone(), || db::default())".ast()
+            );
+            assert_eq!(
+                render_params_call(&path, &params, &[expr("42")], &[]),
+                "db::__params_partial_1(&[1usize, 0usize], __shared.clone(), 42, |__value_0| db::partial_1(__value_0))".ast()
+            );
+            assert_eq!(
+                render_params_call(&path, &params, &[], &[arg_value("name", r#""db""#)]),
+                r#"db::builder().with_name("db").__params_build(&[1usize, 0usize], __shared.clone(), |builder| builder.build())"#.ast()
+            );
+        }
+
+        #[rstest]
+        #[case::fixture("user", "default", "user::default")]
+        #[case::module("users::user", "partial_1", "users::user::partial_1")]
+        #[case::type_path("TestServer::started", "default", "TestServer::__started_default")]
+        #[case::type_path_in_module(
+            "server::TestServer::started",
+            "default",
+            "server::TestServer::__started_default"
+        )]
+        #[case::associated("<TestServer>::started", "default", "<TestServer>::__started_default")]
+        #[case::self_type("Self::started", "partial_2", "Self::__started_partial_2")]
+        #[case::hidden("Self::started", "__lend", "Self::__started_lend")]
+        #[case::type_in_module(
+            "<server::TestServer>::started",
+            "get",
+            "<server::TestServer>::__started_get"
+        )]
+        fn render_the_path_of_the_fixture_functions(
+            #[case] path: &str,
+            #[case] name: &str,
+            #[case] expected: &str,
+        ) {
+            let path: ExprPath = path.ast();
+
+            assert_eq!(expected.ast::<ExprPath>(), member(&path, name));
+        }
+
+        #[rstest]
+        #[case::fixture("user", false)]
+        #[case::module("users::user", false)]
+        #[case::self_type("Self::started", true)]
+        #[case::type_path("TestServer::started", true)]
+        #[case::type_path_in_module("server::TestServer::started", true)]
+        #[case::raw_module("r#mod::user", false)]
+        #[case::qualified("<TestServer>::started", true)]
+        #[case::qualified_in_module("<server::TestServer>::started", true)]
+        fn recognize_the_associated_fixtures_by_the_path_form(
+            #[case] path: &str,
+            #[case] expected: bool,
+        ) {
+            assert_eq!(expected, is_associated(&path.ast()));
+        }
+
+        #[rstest]
+        #[case::fixture(
+            "user",
+            r#"::rstest_fixtures::shared::<user, _>(&__shared, "user", || user::__guarded_default(__shared.clone(), || user::default()).0.unwrap())"#
+        )]
+        #[case::module(
+            "users::user",
+            r#"::rstest_fixtures::shared::<users::user, _>(&__shared, "user", || users::user::__guarded_default(__shared.clone(), || users::user::default()).0.unwrap())"#
+        )]
+        #[case::associated(
+            "Self::started",
+            r#"::rstest_fixtures::shared::<Self, _>(&__shared, "started", || Self::__started_guarded_default(__shared.clone(), || Self::__started_default()).0.unwrap())"#
+        )]
+        #[case::type_path(
+            "server::TestServer::started",
+            r#"::rstest_fixtures::shared::<server::TestServer, _>(&__shared, "started", || server::TestServer::__started_guarded_default(__shared.clone(), || server::TestServer::__started_default()).0.unwrap())"#
+        )]
+        #[case::qualified(
+            "<server::TestServer>::started",
+            r#"::rstest_fixtures::shared::<server::TestServer, _>(&__shared, "started", || <server::TestServer>::__started_guarded_default(__shared.clone(), || <server::TestServer>::__started_default()).0.unwrap())"#
+        )]
+        fn resolve_the_shared_fixtures_by_the_shared_instance(
+            #[case] path: &str,
+            #[case] expected: &str,
+        ) {
+            let data = vec![fixture("arg", vec![]).with_resolve(path.ast()).shared()];
+            let resolver = get(data.iter());
+
+            assert_eq!(
+                resolver.resolve(&ident("arg")).unwrap().into_owned(),
+                expected.ast()
+            );
+        }
+
+        #[test]
+        fn name_the_builder_setters_without_raw_prefix() {
+            assert_eq!(ident("with_type"), builder_setter(&"r#type".ast()));
+            assert_eq!(ident("with_age"), builder_setter(&ident("age")));
         }
     }
 }
@@ -81,43 +434,73 @@ pub(crate) mod values {
 
 /// A trait that `resolve` the given ident to expression code to assign the value.
 pub(crate) trait Resolver {
-    fn resolve(&self, ident: &Ident) -> Option<Cow<Expr>>;
+    fn resolve(&self, ident: &Ident) -> Option<Cow<'_, Expr>>;
+
+    /// The path of the fixture that resolve `ident` if it's not the argument's name.
+    fn fixture_path(&self, _ident: &Ident) -> Option<Cow<'_, ExprPath>> {
+        None
+    }
 }
 
 impl<'a> Resolver for HashMap<String, &'a Expr> {
-    fn resolve(&self, ident: &Ident) -> Option<Cow<Expr>> {
+    fn resolve(&self, ident: &Ident) -> Option<Cow<'_, Expr>> {
         let ident = ident.to_string();
         self.get(&ident).map(|&c| Cow::Borrowed(c))
     }
 }
 
 impl<'a> Resolver for HashMap<String, Expr> {
-    fn resolve(&self, ident: &Ident) -> Option<Cow<Expr>> {
+    fn resolve(&self, ident: &Ident) -> Option<Cow<'_, Expr>> {
         let ident = ident.to_string();
         self.get(&ident).map(|c| Cow::Borrowed(c))
     }
 }
 
+/// Just the fixtures' paths: the arguments are resolved by calling their `default()`.
+impl Resolver for HashMap<String, ExprPath> {
+    fn resolve(&self, _ident: &Ident) -> Option<Cow<'_, Expr>> {
+        None
+    }
+
+    fn fixture_path(&self, ident: &Ident) -> Option<Cow<'_, ExprPath>> {
+        self.get(&ident.to_string()).map(Cow::Borrowed)
+    }
+}
+
 impl<R1: Resolver, R2: Resolver> Resolver for (R1, R2) {
-    fn resolve(&self, ident: &Ident) -> Option<Cow<Expr>> {
+    fn resolve(&self, ident: &Ident) -> Option<Cow<'_, Expr>> {
         self.0.resolve(ident).or_else(|| self.1.resolve(ident))
     }
+
+    fn fixture_path(&self, ident: &Ident) -> Option<Cow<'_, ExprPath>> {
+        self.0
+            .fixture_path(ident)
+            .or_else(|| self.1.fixture_path(ident))
+    }
 }
 
 impl<R: Resolver + ?Sized> Resolver for &R {
-    fn resolve(&self, ident: &Ident) -> Option<Cow<Expr>> {
+    fn resolve(&self, ident: &Ident) -> Option<Cow<'_, Expr>> {
         (*self).resolve(ident)
     }
+
+    fn fixture_path(&self, ident: &Ident) -> Option<Cow<'_, ExprPath>> {
+        (*self).fixture_path(ident)
+    }
 }
 
 impl<R: Resolver + ?Sized> Resolver for Box<R> {
-    fn resolve(&self, ident: &Ident) -> Option<Cow<Expr>> {
+    fn resolve(&self, ident: &Ident) -> Option<Cow<'_, Expr>> {
         (**self).resolve(ident)
     }
+
+    fn fixture_path(&self, ident: &Ident) -> Option<Cow<'_, ExprPath>> {
+        (**self).fixture_path(ident)
+    }
 }
 
 impl Resolver for (String, Expr) {
-    fn resolve(&self, ident: &Ident) -> Option<Cow<Expr>> {
+    fn resolve(&self, ident: &Ident) -> Option<Cow<'_, Expr>> {
         if self.0 == ident.to_string() {
             Some(Cow::Borrowed(&self.1))
         } else {
@@ -139,10 +139,7 @@ pub(crate) fn arg_value(name: impl AsRef<str>, value: impl AsRef<str>) -> Argume
 }
 
 pub(crate) fn values_list<S: AsRef<str>>(arg: &str, values: &[S]) -> ValueList {
-    ValueList {
-        arg: ident(arg),
-        values: values.into_iter().map(|s| expr(s)).collect(),
-    }
+    ValueList::new(ident(arg), values.into_iter().map(|s| expr(s)).collect())
 }
 
 pub(crate) fn first_arg_ident(ast: &ItemFn) -> &Ident {
@@ -227,8 +224,10 @@ impl<A: AsRef<str>> FromIterator<A> for TestCase {
     fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
         TestCase {
             args: iter.into_iter().map(expr).collect(),
+            named: Default::default(),
             attrs: Default::default(),
             description: None,
+            expected: None,
         }
     }
 }
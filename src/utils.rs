@@ -1,4 +1,5 @@
 use crate::refident::MaybeIdent;
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
 use quote::format_ident;
 
 /// Contains some unsorted functions used across others modules
@@ -51,6 +52,76 @@ impl<E: AsRef<Expr>> IsLiteralExpression for E {
     }
 }
 
+/// Replace every char that cannot be used in an identifier by `_`: a leading digit is
+/// prefixed by `_`.
+pub(crate) fn sanitize(name: &str) -> String {
+    let name = sanitize_chars(name);
+    match name.chars().next() {
+        Some(c) if c.is_ascii_digit() => format!("_{}", name),
+        _ => name,
+    }
+}
+
+/// Replace every char that cannot be used in an identifier by `_`: use it for the pieces
+/// of an identifier.
+pub(crate) fn sanitize_chars(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+pub(crate) fn contains_impl_trait(t: &syn::Type) -> bool {
+    use syn::visit::Visit;
+    #[derive(Default)]
+    struct ImplTraitFinder(bool);
+    impl<'ast> Visit<'ast> for ImplTraitFinder {
+        fn visit_type_impl_trait(&mut self, _i: &'ast syn::TypeImplTrait) {
+            self.0 = true;
+        }
+    }
+    let mut finder = ImplTraitFinder::default();
+    finder.visit_type(t);
+    finder.0
+}
+
+/// Convert a `CamelCase` name in the `snake_case` form: the other chars are kept as they are.
+pub(crate) fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |n| n.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+/// Set `span` to all `tokens`: used for the code parsed from strings.
+pub(crate) fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut tt| {
+            if let TokenTree::Group(g) = &tt {
+                let mut group = Group::new(g.delimiter(), respan(g.stream(), span));
+                group.set_span(span);
+                tt = TokenTree::Group(group);
+            } else {
+                tt.set_span(span);
+            }
+            tt
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use syn::parse_quote;
@@ -79,4 +150,19 @@ mod test {
         assert!(fn_args_has_ident(&item_fn, &ident("first")));
         assert!(!fn_args_has_ident(&item_fn, &ident("third")));
     }
+
+    #[test]
+    fn sanitize_should_replace_invalid_chars() {
+        assert_eq!("a_b_c", sanitize(" a-b c "));
+        assert_eq!("_42_is_the_answer", sanitize("42 is the answer"));
+    }
+
+    #[test]
+    fn snake_case_should_split_the_words() {
+        assert_eq!("in_memory", snake_case("InMemory"));
+        assert_eq!("http_store", snake_case("HTTPStore"));
+        assert_eq!("store_v2_cache", snake_case("StoreV2Cache"));
+        assert_eq!("already_snake", snake_case("already_snake"));
+        assert_eq!("file_store_temp", snake_case("FileStore_Temp"));
+    }
 }
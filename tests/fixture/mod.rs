@@ -18,6 +18,14 @@ fn run_test(res: &str) -> (std::process::Output, String) {
     )
 }
 
+fn add_rstest_fixtures_dependency(prj: &Project) {
+    let path = std::env::current_dir().unwrap().join("rstest_fixtures");
+    prj.add_dependency(
+        "rstest_fixtures",
+        &format!(r#"{{path="{}"}}"#, path.to_str().unwrap()),
+    );
+}
+
 mod should {
     use super::*;
 
@@ -40,6 +48,118 @@ mod should {
             .assert(output);
     }
 
+    #[test]
+    fn build_the_fixture_by_its_builder() {
+        let (output, _) = run_test("builder.rs");
+
+        TestResults::new()
+            .ok("build_by_default")
+            .ok("override_an_argument_in_the_middle")
+            .ok("override_all_arguments")
+            .ok("use_in_helpers")
+            .ok("raw_identifiers")
+            .ok("impl_trait_arguments")
+            .ok("generic_arguments")
+            .ok("many_generic_arguments")
+            .ok("named_values_of_generic_fixtures")
+            .assert(output);
+    }
+
+    #[test]
+    fn resolve_associated_fixtures() {
+        let (output, _) = run_test("associated.rs");
+
+        TestResults::new()
+            .ok("other::from_other_module")
+            .ok("other::by_type_path_in_module")
+            .ok("by_type_path")
+            .ok("by_type_path_with_values")
+            .ok("use_associated")
+            .ok("partial_injection")
+            .ok("default_value")
+            .ok("compact")
+            .ok("once")
+            .ok("call_the_function")
+            .assert(output);
+    }
+
+    #[test]
+    fn report_wrong_associated_fixture_path_where_it_is_written() {
+        let (output, name) = run_test("errors_associated_path.rs");
+        let stderr = output.stderr.str();
+
+        assert_in!(stderr, format!("--> {}/src/lib.rs:6:37", name));
+        assert_in!(
+            stderr,
+            "6 | fn not_a_fixture(#[from(TestServer::started)] server: TestServer) {}\n  |                                     ^^^^^^^ associated item not found in `TestServer`"
+        );
+    }
+
+    #[test]
+    fn report_invalid_associated_fixtures() {
+        let (output, name) = run_test("errors_associated.rs");
+        let stderr = output.stderr.str();
+
+        assert_in!(
+            stderr,
+            format!(
+                r#"
+                error: Cannot apply #[fixture] to a method with a `self` receiver: use an associated function.
+                 --> {}/src/lib.rs:8:15
+                  |
+                8 |     fn method(&self) -> u32 {{
+                  |               ^^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+        assert_in!(
+            stderr,
+            format!(
+                r#"
+                error: Cannot use #[values] or #[params] in associated fixture.
+                  --> {}/src/lib.rs:14:8
+                "#,
+                name
+            )
+            .unindent()
+        );
+        assert_in!(
+            stderr,
+            format!(
+                r#"
+                error: #[fixture] cannot be applied to a trait implementation: use an inherent impl block
+                  --> {}/src/lib.rs:20:6
+                "#,
+                name
+            )
+            .unindent()
+        );
+        assert_in!(
+            stderr,
+            format!(
+                r#"
+                error: #[fixture] on an impl block doesn't take any argument
+                  --> {}/src/lib.rs:26:11
+                "#,
+                name
+            )
+            .unindent()
+        );
+        assert_in!(
+            stderr,
+            format!(
+                r#"
+                error: Cannot use `Self` in a fixture outside an impl block marked with #[fixture]: mark the impl block with #[fixture] to define associated fixtures.
+                  --> {}/src/lib.rs:33:28
+                "#,
+                name
+            )
+            .unindent()
+        );
+    }
+
     #[test]
     fn be_accessible_from_other_module() {
         let (output, _) = run_test("from_other_module.rs");
@@ -84,6 +204,437 @@ mod should {
         }
     }
 
+    #[test]
+    fn compute_once_fixture_just_once() {
+        let (output, _) = run_test("once.rs");
+
+        TestResults::new()
+            .ok("base")
+            .ok("cases::case_1")
+            .ok("cases::case_2")
+            .ok("cases::case_3")
+            .ok("compact")
+            .ok("no_return")
+            .ok("in_other_fixture")
+            .assert(output);
+    }
+
+    mod not_compile_once_fixture {
+        use super::*;
+        use std::process::Output;
+
+        use lazy_static::lazy_static;
+
+        fn execute() -> &'static (Output, String) {
+            lazy_static! {
+                static ref OUTPUT: (Output, String) = run_test("errors_once.rs");
+            }
+            assert_ne!(Some(0), OUTPUT.0.status.code(), "Should not compile");
+            &OUTPUT
+        }
+
+        #[test]
+        fn if_async() {
+            let (output, name) = execute();
+
+            assert_in!(
+                output.stderr.str(),
+                format!(
+                    r#"
+                    error: Cannot apply #[once] to async fixture.
+                     --> {}/src/lib.rs:4:3
+                      |
+                    4 | #[once]
+                      |   ^^^^
+                    "#,
+                    name
+                )
+                .unindent()
+            );
+        }
+
+        #[test]
+        fn if_generic() {
+            let (output, name) = execute();
+
+            assert_in!(
+                output.stderr.str(),
+                format!(
+                    r#"
+                    error: Cannot apply #[once] on generic fixture.
+                     --> {}/src/lib.rs:9:3
+                      |
+                    9 | #[once]
+                      |   ^^^^
+                    "#,
+                    name
+                )
+                .unindent()
+            );
+        }
+
+        #[test]
+        fn if_return_impl_type() {
+            let (output, name) = execute();
+
+            assert_in!(
+                output.stderr.str(),
+                format!(
+                    r#"
+                    error: Cannot apply #[once] on fixture that returns an `impl` type.
+                      --> {}/src/lib.rs:15:3
+                       |
+                    15 | #[once]
+                       |   ^^^^
+                    "#,
+                    name
+                )
+                .unindent()
+            );
+        }
+
+        #[test]
+        fn if_return_type_is_not_sync() {
+            let (output, name) = execute();
+
+            assert_in!(
+                output.stderr.str(),
+                format!(
+                    r#"
+                    error[E0277]: `Cell<u32>` cannot be shared between threads safely
+                      --> {}/src/lib.rs:22:37
+                       |
+                    22 | fn error_not_sync_once_fixture() -> std::cell::Cell<u32> {{
+                       |                                     ^^^"#,
+                    name
+                )
+                .unindent()
+            );
+        }
+    }
+
+    #[test]
+    fn expand_tests_that_use_parametrized_fixtures() {
+        let (output, _) = run_test("params.rs");
+
+        assert_not_in!(output.stderr.str(), "should have a snake case name");
+        TestResults::new()
+            .ok("direct::backend_1_Sqlite")
+            .ok("direct::backend_2_Postgres")
+            .ok("transitive::db_1_Sqlite")
+            .ok("transitive::db_2_Sqlite")
+            .ok("transitive::db_3_Sqlite")
+            .ok("transitive::db_4_Postgres")
+            .ok("transitive::db_5_Postgres")
+            .ok("transitive::db_6_Postgres")
+            .ok("with_timeout::db_1_Sqlite")
+            .ok("with_timeout::db_2_Sqlite")
+            .ok("with_timeout::db_3_Sqlite")
+            .ok("with_timeout::db_4_Postgres")
+            .ok("with_timeout::db_5_Postgres")
+            .ok("with_timeout::db_6_Postgres")
+            .ok("partial::db_1_Sqlite")
+            .ok("partial::db_2_Sqlite")
+            .ok("partial::db_3_Sqlite")
+            .ok("partial::db_4_Postgres")
+            .ok("partial::db_5_Postgres")
+            .ok("partial::db_6_Postgres")
+            .ok("with_cases::case_1::backend_1_Sqlite")
+            .ok("with_cases::case_1::backend_2_Postgres")
+            .ok("with_cases::case_2::backend_1_Sqlite")
+            .ok("with_cases::case_2::backend_2_Postgres")
+            .ok("call_with_the_first_values")
+            .ok("other::from_other_module::engine_1")
+            .ok("other::from_other_module::engine_2")
+            .ok("other::from_other_module::engine_3")
+            .assert(output);
+    }
+
+    #[test]
+    fn resolve_fixtures_by_the_given_path() {
+        let (output, _) = run_test("from.rs");
+
+        TestResults::new()
+            .ok("resolve_by_path")
+            .ok("resolve_by_path_with_arguments")
+            .ok("same_fixture_with_different_arguments")
+            .ok("in_fixture")
+            .ok("ignore_underscore")
+            .ok("compact_syntax")
+            .assert(output);
+    }
+
+    #[test]
+    fn not_compile_invalid_parametrized_fixture() {
+        let (output, name) = run_test("errors_params.rs");
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot use #[values] or #[params] in async fixture.
+                 --> {}/src/lib.rs:5:1
+                  |
+                5 | async fn error_async_values(v: u32) -> u32 {{
+                  | ^^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot apply #[once] to parametrized fixture.
+                  --> {}/src/lib.rs:10:3
+                   |
+                10 | #[once]
+                   |   ^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: cannot find macro `not_parametrized` in this scope
+                  --> {}/src/lib.rs:22:52
+                   |
+                22 | fn error_params_not_parametrized_fixture(#[params] not_parametrized: u32) {{}}
+                   |                                                    ^^^^^^^^^^^^^^^^"#,
+                name
+            )
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn not_compile_if_a_parametrized_fixture_is_used_without_params() {
+        let (output, name) = run_test("errors_params_missed.rs");
+        let stderr = output.stderr.str();
+
+        assert_in!(
+            stderr,
+            "backend_is_a_parametrized_fixture_mark_the_argument_with_params` is not satisfied"
+        );
+        assert_in!(stderr, format!("  --> {}/src/lib.rs:21:18\n", name));
+        assert_in!(
+            stderr,
+            "db_is_a_parametrized_fixture_mark_the_argument_with_params` is not satisfied"
+        );
+        assert_in!(stderr, format!("  --> {}/src/lib.rs:26:15\n", name));
+        assert_in!(stderr, format!("  --> {}/src/lib.rs:30:16\n", name));
+        // `#[from(backend)] _other`
+        assert_in!(stderr, format!("  --> {}/src/lib.rs:33:22\n", name));
+        // The placeholder of the case that omits the value
+        assert_in!(stderr, format!("  --> {}/src/lib.rs:37:11\n", name));
+    }
+
+    #[test]
+    fn tear_down_fixtures_at_the_end_of_the_test() {
+        let prj = prj("teardown.rs");
+        add_rstest_fixtures_dependency(&prj);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("use_db")
+            .ok("use_db_value")
+            .ok("use_temp_file")
+            .ok("use_partial")
+            .ok("use_named")
+            .ok("use_named_nested")
+            .ok("use_given_db")
+            .ok("use_nested")
+            .ok("use_repo")
+            .fail("fail")
+            .ok("user_fixture_type_is_a_plain_value")
+            .ok("teardown_after_test")
+            .ok("teardown_the_value_used_by_the_test")
+            .ok("teardown_after_the_test_that_takes_the_value")
+            .ok("call_the_fixtures_functions_without_teardown")
+            .ok("teardown_the_dependencies_of_fixtures_without_teardown")
+            .ok("teardown_partial")
+            .ok("teardown_named")
+            .ok("teardown_named_nested")
+            .ok("not_teardown_the_given_values")
+            .ok("teardown_in_reverse_dependency_order")
+            .ok("teardown_when_test_panics")
+            .assert(output);
+    }
+
+    #[test]
+    fn not_compile_teardown_fixture() {
+        let prj = prj("errors_teardown.rs");
+        add_rstest_fixtures_dependency(&prj);
+        let output = prj.run_tests().unwrap();
+        let name = prj.get_name();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot apply #[teardown] to async fixture.
+                 --> {}/src/lib.rs:4:3
+                  |
+                4 | #[teardown(|_| ())]
+                  |   ^^^^^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot apply #[teardown] to #[once] fixture.
+                  --> {}/src/lib.rs:11:3
+                   |
+                11 | #[teardown(|_| ())]
+                   |   ^^^^^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot return `rstest_fixtures::Fixture` from async fixture.
+                  --> {}/src/lib.rs:17:40
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        // The `#[teardown]` fixtures keep their value: they give a clone of it
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error[E0277]: the trait bound `NotClone: Clone` is not satisfied
+                  --> {}/src/lib.rs:30:36
+                   |
+                30 | fn error_teardown_fixture_by_value(kept: NotClone) {{}}
+                   |                                    ^^^^ the trait `Clone` is not implemented for `NotClone`
+                "#,
+                name
+            )
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn share_the_fixtures_in_the_test() {
+        let prj = prj("shared.rs");
+        add_rstest_fixtures_dependency(&prj);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("share_the_instance")
+            .ok("not_shared_arguments_get_their_instance")
+            .ok("shared_fixture")
+            .ok("shared_fixture_is_per_test")
+            .ok("shared_fixture_is_per_test_also_in_the_same_thread")
+            .ok("shared_fixture_is_not_shared_outside_the_tests")
+            .ok("shared_associated_fixture")
+            .assert(output);
+    }
+
+    #[test]
+    fn not_compile_shared_fixture() {
+        let prj = prj("errors_shared.rs");
+        add_rstest_fixtures_dependency(&prj);
+        let output = prj.run_tests().unwrap();
+        let name = prj.get_name();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot apply #[shared] to async fixture.
+                 --> {}/src/lib.rs:4:3
+                  |
+                4 | #[shared]
+                  |   ^^^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot apply #[shared] on generic fixture.
+                  --> {}/src/lib.rs:10:3
+                   |
+                10 | #[shared]
+                   |   ^^^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot apply #[shared] to a fixture with teardown.
+                  --> {}/src/lib.rs:16:3
+                   |
+                16 | #[shared]
+                   |   ^^^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot apply #[shared] to a fixture with teardown.
+                  --> {}/src/lib.rs:33:3
+                   |
+                33 | #[shared]
+                   |   ^^^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Cannot use #[shared] with #[with]: the shared instance is the default one
+                  --> {}/src/lib.rs:23:22
+                   |
+                23 | fn error_shared_with(#[shared] #[with(42)] fx: u32) -> u32 {{
+                   |                      ^^^^^^^^^
+                "#,
+                name
+            )
+            .unindent()
+        );
+    }
+
     #[test]
     fn resolve_async_fixture() {
         let prj = prj("async_fixture.rs");
@@ -104,6 +655,19 @@ mod should {
             .assert(output);
     }
 
+    #[test]
+    fn build_async_fixture_by_its_builder() {
+        let prj = prj("async_builder.rs");
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("build_by_default")
+            .ok("override_an_argument")
+            .assert(output);
+    }
+
     #[test]
     fn resolve_fixture_generics_by_fixture_input() {
         let (output, _) = run_test("resolve.rs");
@@ -160,6 +724,47 @@ mod should {
             .assert(output);
     }
 
+    #[test]
+    fn apply_partial_fixture_by_name() {
+        let (output, _) = run_test("named_partial.rs");
+
+        TestResults::new()
+            .ok("default")
+            .ok("just_the_last")
+            .ok("more_named")
+            .ok("positional_and_named")
+            .ok("compact")
+            .ok("from_and_named")
+            .ok("in_fixture")
+            .ok("generic")
+            .assert(output);
+    }
+
+    #[test]
+    fn report_unknown_argument_names_at_the_call_site() {
+        let (output, name) = run_test("errors_named_partial.rs");
+
+        assert_in!(output.stderr.str(), "no method named `with_agee` found");
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                 --> {}/src/lib.rs:9:16
+                "#,
+                name
+            )
+            .unindent()
+        );
+        assert_in!(
+            output.stderr.str(),
+            r#"
+            9 | fn test(#[with(agee = 30)] user: u32) {}
+              |                ^^^^
+            "#
+            .unindent()
+        );
+    }
+
     #[rstest]
     #[case::compact_form("default.rs")]
     #[case::attrs_form("default_in_attrs.rs")]
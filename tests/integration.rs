@@ -24,6 +24,26 @@ pub fn prj() -> Project {
     prj
 }
 
+/// Like `prj()` but `rstest` is used without default features and with the given ones.
+pub fn prj_with_features(features: &[&str]) -> Project {
+    let prj_name = sanitize_name(testname());
+
+    let prj = ROOT_PROJECT.subproject(&prj_name);
+    prj.add_dependency(
+        "rstest",
+        &format!(
+            r#"{{path="{}", default-features=false, features=[{}]}}"#,
+            std::env::current_dir().unwrap().display(),
+            features
+                .iter()
+                .map(|f| format!(r#""{}""#, f))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    );
+    prj
+}
+
 pub fn resources<O: AsRef<Path>>(name: O) -> PathBuf {
     Path::new("tests").join("resources").join(name)
 }
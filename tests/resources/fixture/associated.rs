@@ -0,0 +1,105 @@
+use rstest::*;
+
+#[derive(Debug, PartialEq)]
+pub struct TestServer {
+    port: u16,
+    started: bool,
+}
+
+#[fixture]
+fn port() -> u16 {
+    8080
+}
+
+#[fixture]
+impl TestServer {
+    pub fn new(port: u16) -> Self {
+        Self {
+            port,
+            started: false,
+        }
+    }
+
+    #[fixture]
+    pub fn stopped(port: u16) -> Self {
+        Self::new(port)
+    }
+
+    #[fixture]
+    pub fn started(#[from(Self::stopped)] mut server: Self) -> Self {
+        server.started = true;
+        server
+    }
+
+    #[fixture]
+    fn local(#[default(1234)] port: u16) -> Self {
+        Self::new(port)
+    }
+
+    #[fixture]
+    #[once]
+    fn shared() -> TestServer {
+        Self::new(1)
+    }
+}
+
+mod other {
+    use super::TestServer;
+    use rstest::*;
+
+    #[rstest]
+    fn from_other_module(#[from(<TestServer>::stopped)] server: TestServer) {
+        assert_eq!(TestServer::new(8080), server);
+    }
+
+    #[rstest]
+    fn by_type_path_in_module(#[from(super::TestServer::stopped)] server: TestServer) {
+        assert_eq!(TestServer::new(8080), server);
+    }
+}
+
+#[rstest]
+fn by_type_path(#[from(TestServer::started)] server: TestServer) {
+    assert!(server.started);
+}
+
+#[rstest]
+fn by_type_path_with_values(#[from(TestServer::stopped)] #[with(42)] server: TestServer) {
+    assert_eq!(TestServer::new(42), server);
+}
+
+#[rstest]
+fn use_associated(#[from(<TestServer>::started)] server: TestServer) {
+    assert_eq!(
+        TestServer {
+            port: 8080,
+            started: true
+        },
+        server
+    );
+}
+
+#[rstest]
+fn partial_injection(#[from(<TestServer>::stopped)] #[with(42)] server: TestServer) {
+    assert_eq!(TestServer::new(42), server);
+}
+
+#[rstest]
+fn default_value(#[from(<TestServer>::local)] server: TestServer) {
+    assert_eq!(TestServer::new(1234), server);
+}
+
+#[rstest(<TestServer>::local(4321) as server)]
+fn compact(server: TestServer) {
+    assert_eq!(TestServer::new(4321), server);
+}
+
+#[rstest]
+fn once(#[from(<TestServer>::shared)] server: &TestServer) {
+    assert_eq!(&TestServer::new(1), server);
+}
+
+#[test]
+fn call_the_function() {
+    assert!(TestServer::started(TestServer::stopped(1)).started);
+}
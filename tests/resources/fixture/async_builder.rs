@@ -0,0 +1,16 @@
+use rstest::*;
+
+#[fixture(base = 40)]
+async fn async_sum(base: u32, #[default(2)] delta: u32) -> u32 {
+    base + delta
+}
+
+#[rstest]
+async fn build_by_default() {
+    assert_eq!(42, async_sum::builder().build().await);
+}
+
+#[rstest]
+async fn override_an_argument() {
+    assert_eq!(43, async_sum::builder().with_delta(3).build().await);
+}
@@ -0,0 +1,110 @@
+use rstest::*;
+
+#[fixture]
+fn name() -> &'static str {
+    "Alice"
+}
+
+#[fixture]
+fn user(name: &str, #[default(22)] age: u8, #[default("user")] role: &str) -> String {
+    format!("{} ({}, {})", name, age, role)
+}
+
+#[fixture]
+fn r#ref(#[default(1)] r#type: u32) -> u32 {
+    r#type
+}
+
+#[fixture]
+fn greeting(name: impl AsRef<str>, #[default(1)] times: usize) -> String {
+    name.as_ref().repeat(times)
+}
+
+#[fixture]
+fn len<T: AsRef<str>>(#[default("abc")] text: T) -> usize {
+    text.as_ref().len()
+}
+
+#[fixture]
+fn joined(
+    #[default("a")] a: impl ToString,
+    #[default('b')] b: impl ToString,
+    #[default(3)] c: impl ToString,
+    #[default("d")] d: impl ToString,
+    #[default("e")] e: impl ToString,
+    #[default("f")] f: impl ToString,
+    #[default("g")] g: impl ToString,
+    #[default("h")] h: impl ToString,
+) -> String {
+    [a.to_string(), b.to_string(), c.to_string(), d.to_string()].concat()
+        + &[e.to_string(), f.to_string(), g.to_string(), h.to_string()].concat()
+}
+
+mod helpers {
+    use super::*;
+
+    pub fn admin() -> String {
+        user::builder().with_role("admin").build()
+    }
+}
+
+#[test]
+fn build_by_default() {
+    assert_eq!(user::default(), user::builder().build());
+}
+
+#[test]
+fn override_an_argument_in_the_middle() {
+    assert_eq!("Alice (42, user)", user::builder().with_age(42).build());
+}
+
+#[test]
+fn override_all_arguments() {
+    assert_eq!(
+        "Bob (30, guest)",
+        user::builder()
+            .with_role("guest")
+            .with_name("Bob")
+            .with_age(30)
+            .build()
+    );
+}
+
+#[test]
+fn use_in_helpers() {
+    assert_eq!("Alice (22, admin)", helpers::admin());
+}
+
+#[test]
+fn raw_identifiers() {
+    assert_eq!(2, r#ref::builder().with_type(2).build());
+}
+
+#[test]
+fn impl_trait_arguments() {
+    assert_eq!("AliceAlice", greeting::builder().with_times(2).build());
+    assert_eq!("Bob", greeting::builder().with_name("Bob").build());
+    assert_eq!("Bob", greeting::partial_1(String::from("Bob")));
+}
+
+#[test]
+fn generic_arguments() {
+    assert_eq!(3, len::builder().build());
+    assert_eq!(2, len::builder().with_text(String::from("ab")).build());
+}
+
+#[test]
+fn many_generic_arguments() {
+    assert_eq!("ab3defgh", joined::builder().build());
+    assert_eq!("xy3defgh", joined::builder().with_b("y").with_a('x').build());
+    assert_eq!("xyz4efgh", joined::partial_4('x', "y", 'z', 4));
+}
+
+#[rstest]
+fn named_values_of_generic_fixtures(
+    #[with(times = 3)] greeting: String,
+    #[with(text = "ab")] len: usize,
+) {
+    assert_eq!("AliceAliceAlice", greeting);
+    assert_eq!(2, len);
+}
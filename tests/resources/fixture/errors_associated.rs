@@ -0,0 +1,36 @@
+use rstest::*;
+
+struct S;
+
+#[fixture]
+impl S {
+    #[fixture]
+    fn method(&self) -> u32 {
+        42
+    }
+
+    #[fixture]
+    #[values(1, 2)]
+    fn values(v: u32) -> u32 {
+        v
+    }
+}
+
+#[fixture]
+impl Default for S {
+    fn default() -> Self {
+        S
+    }
+}
+
+#[fixture(a = 1)]
+impl S {}
+
+struct T;
+
+impl T {
+    #[fixture]
+    fn unmarked(v: u32) -> Self {
+        T
+    }
+}
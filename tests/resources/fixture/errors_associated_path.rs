@@ -0,0 +1,6 @@
+use rstest::*;
+
+struct TestServer;
+
+#[rstest]
+fn not_a_fixture(#[from(TestServer::started)] server: TestServer) {}
@@ -0,0 +1,9 @@
+use rstest::*;
+
+#[fixture]
+fn user(#[default(1)] id: u32, #[default(20)] age: u8) -> u32 {
+    id + age as u32
+}
+
+#[rstest]
+fn test(#[with(agee = 30)] user: u32) {}
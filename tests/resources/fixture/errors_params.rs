@@ -0,0 +1,22 @@
+use rstest::*;
+
+#[fixture]
+#[values(1, 2)]
+async fn error_async_values(v: u32) -> u32 {
+    v
+}
+
+#[fixture]
+#[once]
+#[values(1, 2)]
+fn error_once_values(v: u32) -> u32 {
+    v
+}
+
+#[fixture]
+fn not_parametrized() -> u32 {
+    42
+}
+
+#[rstest]
+fn error_params_not_parametrized_fixture(#[params] not_parametrized: u32) {}
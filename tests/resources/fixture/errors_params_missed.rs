@@ -0,0 +1,38 @@
+use rstest::*;
+
+#[fixture]
+#[values(1, 2)]
+fn backend(kind: u32) -> u32 {
+    kind
+}
+
+#[fixture]
+fn db(#[params] backend: u32) -> u32 {
+    backend
+}
+
+#[fixture]
+fn plain() -> u32 {
+    42
+}
+
+#[cfg(test)]
+#[fixture]
+fn error_fixture(backend: u32, plain: u32) -> u32 {
+    backend + plain
+}
+
+#[rstest]
+fn error_test(db: u32, plain: u32) {}
+
+#[rstest]
+#[case(1)]
+fn error_cases(db: u32, #[case] v: u32) {}
+
+#[rstest]
+fn error_from(#[from(backend)] _other: u32) {}
+
+#[rstest]
+#[case(1, 2)]
+#[case(1, _)]
+fn error_omitted(#[case] v: u32, #[case] backend: u32) {}
@@ -0,0 +1,36 @@
+use rstest::*;
+
+#[fixture]
+#[shared]
+async fn error_async_shared_fixture() -> u32 {
+    42
+}
+
+#[fixture]
+#[shared]
+fn error_generic_shared_fixture<T: Default + Clone + 'static>() -> T {
+    T::default()
+}
+
+#[fixture]
+#[shared]
+#[teardown(|_| ())]
+fn error_teardown_shared_fixture() -> u32 {
+    42
+}
+
+#[fixture]
+fn error_shared_with(#[shared] #[with(42)] fx: u32) -> u32 {
+    fx
+}
+
+#[fixture]
+fn fx() -> u32 {
+    42
+}
+
+#[fixture]
+#[shared]
+fn error_shared_fixture_type() -> rstest_fixtures::Fixture<u32, ()> {
+    rstest_fixtures::Fixture::new(42, ())
+}
@@ -0,0 +1,30 @@
+use rstest::*;
+
+#[fixture]
+#[teardown(|_| ())]
+async fn error_async_teardown_fixture() -> u32 {
+    42
+}
+
+#[fixture]
+#[once]
+#[teardown(|_| ())]
+fn error_once_teardown_fixture() -> u32 {
+    42
+}
+
+#[fixture]
+async fn error_async_fixture_type() -> rstest_fixtures::Fixture<u32, ()> {
+    rstest_fixtures::Fixture::new(42, ())
+}
+
+struct NotClone;
+
+#[fixture]
+#[teardown(|_| ())]
+fn kept() -> NotClone {
+    NotClone
+}
+
+#[rstest]
+fn error_teardown_fixture_by_value(kept: NotClone) {}
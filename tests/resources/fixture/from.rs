@@ -0,0 +1,64 @@
+use rstest::*;
+
+mod fixtures {
+    use rstest::*;
+
+    #[derive(Debug, PartialEq)]
+    pub struct Db(pub String);
+
+    #[fixture]
+    pub fn db(#[default("main")] name: &str) -> Db {
+        Db(name.to_owned())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct User(String, u32);
+
+#[fixture]
+fn user(#[default("Alice")] name: &str, #[default(22)] age: u32) -> User {
+    User(name.to_owned(), age)
+}
+
+#[fixture]
+fn team(#[from(user)] leader: User, #[from(user)] #[with("Bob")] member: User) -> Vec<User> {
+    vec![leader, member]
+}
+
+#[rstest]
+fn resolve_by_path(#[from(fixtures::db)] conn: fixtures::Db) {
+    assert_eq!(fixtures::Db("main".to_owned()), conn);
+}
+
+#[rstest]
+fn resolve_by_path_with_arguments(#[from(crate::fixtures::db)] #[with("other")] conn: fixtures::Db) {
+    assert_eq!(fixtures::Db("other".to_owned()), conn);
+}
+
+#[rstest]
+fn same_fixture_with_different_arguments(
+    #[from(user)] #[with("Bob", 42)] admin: User,
+    #[from(user)] guest: User,
+    user: User,
+) {
+    assert_eq!(User("Bob".to_owned(), 42), admin);
+    assert_eq!(User("Alice".to_owned(), 22), guest);
+    assert_eq!(guest, user);
+}
+
+#[rstest]
+fn in_fixture(team: Vec<User>) {
+    assert_eq!(
+        vec![User("Alice".to_owned(), 22), User("Bob".to_owned(), 22)],
+        team
+    );
+}
+
+#[rstest]
+fn ignore_underscore(#[from(user)] _admin: User) {}
+
+#[rstest(user("Bob") as admin, fixtures::db as conn)]
+fn compact_syntax(admin: User, conn: fixtures::Db) {
+    assert_eq!(User("Bob".to_owned(), 22), admin);
+    assert_eq!(fixtures::Db("main".to_owned()), conn);
+}
@@ -0,0 +1,83 @@
+use rstest::*;
+
+#[derive(Debug, PartialEq)]
+pub struct User {
+    name: String,
+    age: u8,
+    admin: bool,
+}
+
+impl User {
+    fn new(name: &str, age: u8, admin: bool) -> Self {
+        Self {
+            name: name.to_owned(),
+            age,
+            admin,
+        }
+    }
+}
+
+#[fixture]
+fn name() -> &'static str {
+    "Alice"
+}
+
+#[fixture]
+fn age() -> u8 {
+    20
+}
+
+#[fixture]
+fn user(name: &str, age: u8, #[default(false)] admin: bool) -> User {
+    User::new(name, age, admin)
+}
+
+#[fixture]
+fn admin(#[with(admin = true)] user: User) -> User {
+    user
+}
+
+#[fixture]
+fn pair<T: Default>(#[default(2)] second: u32) -> (T, u32) {
+    (T::default(), second)
+}
+
+#[rstest]
+fn default(user: User) {
+    assert_eq!(User::new("Alice", 20, false), user);
+}
+
+#[rstest]
+fn just_the_last(#[with(admin = true)] user: User) {
+    assert_eq!(User::new("Alice", 20, true), user);
+}
+
+#[rstest]
+fn more_named(#[with(admin = true, age = 42)] user: User) {
+    assert_eq!(User::new("Alice", 42, true), user);
+}
+
+#[rstest]
+fn positional_and_named(#[with("Bob", admin = true)] user: User) {
+    assert_eq!(User::new("Bob", 20, true), user);
+}
+
+#[rstest(user(age = 30))]
+fn compact(user: User) {
+    assert_eq!(User::new("Alice", 30, false), user);
+}
+
+#[rstest]
+fn from_and_named(#[from(user)] #[with(age = 30)] other: User) {
+    assert_eq!(User::new("Alice", 30, false), other);
+}
+
+#[rstest]
+fn in_fixture(admin: User) {
+    assert_eq!(User::new("Alice", 20, true), admin);
+}
+
+#[rstest]
+fn generic(#[with(second = 3)] pair: (String, u32)) {
+    assert_eq!((String::new(), 3), pair);
+}
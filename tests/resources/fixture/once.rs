@@ -0,0 +1,56 @@
+use rstest::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static CALLS: AtomicU32 = AtomicU32::new(0);
+
+#[fixture]
+#[once]
+fn once_fixture() -> u32 {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    42
+}
+
+#[fixture(::once)]
+fn once_compact() -> Vec<u32> {
+    vec![1, 2, 3]
+}
+
+#[fixture]
+#[once]
+fn once_no_return() {
+    println!("Exec fixture() just once");
+}
+
+#[fixture]
+fn injected(once_fixture: &u32) -> u32 {
+    *once_fixture * 2
+}
+
+#[rstest]
+fn base(once_fixture: &u32) {
+    assert_eq!(&42, once_fixture);
+    assert_eq!(1, CALLS.load(Ordering::SeqCst));
+}
+
+#[rstest]
+#[case(2)]
+#[case(3)]
+#[case(7)]
+fn cases(once_fixture: &u32, #[case] divisor: u32) {
+    assert_eq!(0, *once_fixture % divisor);
+    assert_eq!(1, CALLS.load(Ordering::SeqCst));
+}
+
+#[rstest]
+fn compact(once_compact: &Vec<u32>) {
+    assert_eq!(&vec![1, 2, 3], once_compact);
+}
+
+#[rstest]
+fn no_return(_once_no_return: ()) {}
+
+#[rstest]
+fn in_other_fixture(injected: u32) {
+    assert_eq!(84, injected);
+    assert_eq!(1, CALLS.load(Ordering::SeqCst));
+}
@@ -0,0 +1,89 @@
+use rstest::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backend {
+    Sqlite,
+    Postgres,
+}
+
+#[fixture]
+#[values(Backend::Sqlite, Backend::Postgres)]
+fn backend(kind: Backend) -> Backend {
+    kind
+}
+
+#[fixture]
+#[values(1, 2, 3)]
+fn engine(version: u32) -> u32 {
+    version * 10
+}
+
+pub struct Db {
+    backend: Backend,
+    engine: u32,
+    name: String,
+}
+
+#[fixture]
+fn db(#[default("db")] name: &str, #[params] backend: Backend, #[params] engine: u32) -> Db {
+    Db {
+        backend,
+        engine,
+        name: name.to_owned(),
+    }
+}
+
+fn test_name() -> String {
+    std::thread::current().name().unwrap().to_owned()
+}
+
+#[rstest]
+fn direct(#[params] backend: Backend) {
+    assert!(test_name().ends_with(&format!("{:?}", backend)));
+}
+
+#[rstest]
+fn transitive(#[params] db: Db) {
+    let name = test_name();
+    let index: u32 = name.split('_').nth(1).unwrap().parse().unwrap();
+
+    assert_eq!("db", db.name);
+    assert!(name.ends_with(&format!("{:?}", db.backend)));
+    assert_eq!(((index - 1) % 3 + 1) * 10, db.engine);
+}
+
+#[rstest]
+#[timeout(std::time::Duration::from_secs(60))]
+fn with_timeout(#[params] db: Db) {
+    assert!(test_name().ends_with(&format!("{:?}", db.backend)));
+}
+
+#[rstest]
+fn partial(#[params] #[with("other")] db: Db) {
+    assert_eq!("other", db.name);
+}
+
+#[rstest]
+#[case(Backend::Sqlite)]
+#[case(Backend::Postgres)]
+fn with_cases(#[params] _backend: Backend, #[case] other: Backend) {
+    assert!([Backend::Sqlite, Backend::Postgres].contains(&other));
+}
+
+#[test]
+fn call_with_the_first_values() {
+    let db = db::default();
+
+    assert_eq!(Backend::Sqlite, db.backend);
+    assert_eq!(10, db.engine);
+    assert_eq!(Backend::Sqlite, db::partial_1("other").backend);
+}
+
+mod other {
+    use super::*;
+
+    #[rstest]
+    fn from_other_module(#[params] engine: u32) {
+        assert_eq!(0, engine % 10);
+    }
+}
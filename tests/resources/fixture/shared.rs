@@ -0,0 +1,130 @@
+use rstest::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+thread_local! {
+    static CALLS: Cell<u32> = Cell::new(0);
+}
+
+fn calls() -> u32 {
+    CALLS.with(|c| c.get())
+}
+
+#[derive(Clone, Default)]
+struct Db(Rc<RefCell<Vec<String>>>);
+
+impl Db {
+    fn insert(&self, entry: &str) {
+        self.0.borrow_mut().push(entry.to_owned())
+    }
+
+    fn entries(&self) -> Vec<String> {
+        self.0.borrow().clone()
+    }
+}
+
+#[fixture]
+fn db() -> Db {
+    CALLS.with(|c| c.set(c.get() + 1));
+    Db::default()
+}
+
+#[fixture]
+fn repo(#[shared] db: Db) -> Db {
+    db.insert("repo");
+    db
+}
+
+#[fixture]
+fn service(#[shared] db: Db) -> Db {
+    db.insert("service");
+    db
+}
+
+#[fixture]
+fn alone(db: Db) -> Db {
+    db.insert("alone");
+    db
+}
+
+#[rstest]
+fn share_the_instance(#[shared] db: Db, repo: Db, service: Db) {
+    assert_eq!(vec!["repo", "service"], db.entries());
+    assert!(Rc::ptr_eq(&repo.0, &service.0));
+    assert_eq!(1, calls());
+}
+
+#[rstest]
+fn not_shared_arguments_get_their_instance(#[shared] db: Db, #[from(repo)] _repo: Db, alone: Db) {
+    assert_eq!(vec!["repo"], db.entries());
+    assert_eq!(vec!["alone"], alone.entries());
+    assert_eq!(2, calls());
+}
+
+#[fixture]
+#[shared]
+fn counter() -> Rc<Cell<u32>> {
+    Rc::new(Cell::new(0))
+}
+
+#[fixture]
+fn first(counter: Rc<Cell<u32>>) -> u32 {
+    counter.set(counter.get() + 1);
+    counter.get()
+}
+
+#[fixture]
+fn second(counter: Rc<Cell<u32>>) -> u32 {
+    counter.set(counter.get() + 1);
+    counter.get()
+}
+
+#[rstest]
+fn shared_fixture(first: u32, second: u32, counter: Rc<Cell<u32>>) {
+    assert_eq!((1, 2), (first, second));
+    assert_eq!(2, counter.get());
+}
+
+#[rstest]
+fn shared_fixture_is_per_test(counter: Rc<Cell<u32>>) {
+    assert_eq!(0, counter.get());
+}
+
+#[test]
+fn shared_fixture_is_per_test_also_in_the_same_thread() {
+    shared_fixture();
+    shared_fixture_is_per_test();
+}
+
+#[test]
+fn shared_fixture_is_not_shared_outside_the_tests() {
+    assert!(!Rc::ptr_eq(&counter::default(), &counter::default()));
+    assert!(!Rc::ptr_eq(&counter::builder().build(), &counter::default()));
+    assert_eq!((1, 1), (first::default(), second::default()));
+}
+
+struct Server;
+
+#[fixture]
+impl Server {
+    #[fixture]
+    #[shared]
+    fn port() -> Rc<Cell<u16>> {
+        Rc::new(Cell::new(8080))
+    }
+
+    #[fixture]
+    fn client(#[from(Self::port)] port: Rc<Cell<u16>>) -> u16 {
+        port.set(port.get() + 1);
+        port.get()
+    }
+}
+
+#[rstest]
+fn shared_associated_fixture(
+    #[from(<Server>::client)] client: u16,
+    #[from(<Server>::port)] port: Rc<Cell<u16>>,
+) {
+    assert_eq!(8081, client);
+    assert_eq!(8081, port.get());
+}
@@ -0,0 +1,271 @@
+use rstest::*;
+use rstest_fixtures::TearDownClosure;
+use std::cell::RefCell;
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = RefCell::new(vec![]);
+}
+
+fn log<S: Into<String>>(entry: S) {
+    LOG.with(|l| l.borrow_mut().push(entry.into()))
+}
+
+fn take_log() -> Vec<String> {
+    LOG.with(|l| std::mem::take(&mut *l.borrow_mut()))
+}
+
+// Not `Clone`: the tests borrow it and the teardown takes the value that they used
+struct Db(&'static str, usize);
+
+impl Db {
+    fn new(name: &'static str) -> Self {
+        Db(name, 0)
+    }
+
+    fn query(&mut self) {
+        self.1 += 1
+    }
+
+    fn drop_schema(self) {
+        if self.1 > 0 {
+            log(format!("drop {} after {} queries", self.0, self.1))
+        } else {
+            log(format!("drop {}", self.0))
+        }
+    }
+}
+
+#[fixture]
+#[teardown(|db| db.drop_schema())]
+fn db() -> Db {
+    log("create main");
+    Db::new("main")
+}
+
+// `Clone`: the tests take it and the teardown gets a clone
+#[derive(Clone)]
+struct TempFile(&'static str);
+
+#[fixture]
+#[teardown(|file| log(format!("remove {}", file.0)))]
+fn temp_file() -> TempFile {
+    log("create file");
+    TempFile("file")
+}
+
+#[fixture]
+#[teardown(|db| db.drop_schema())]
+fn named(#[default("default")] name: &'static str) -> Db {
+    log(format!("create {}", name));
+    Db::new(name)
+}
+
+#[fixture]
+#[teardown(|_| log("close connection"))]
+fn connection(db: &Db) -> String {
+    log("open connection");
+    format!("connection to {}", db.0)
+}
+
+#[fixture]
+#[teardown(|_| log("close named connection"))]
+fn named_connection(db: &Db, #[default("named")] label: &'static str) -> String {
+    log(format!("open {}", label));
+    format!("{} to {}", label, db.0)
+}
+
+// No teardown of its own: it keeps the guard of `db` anyway
+#[fixture]
+fn repo(db: &mut Db) -> String {
+    db.query();
+    format!("repo on {}", db.0)
+}
+
+fn stop_server() {
+    log("stop server")
+}
+
+#[fixture]
+fn server() -> rstest_fixtures::Fixture<&'static str, TearDownClosure<fn()>> {
+    log("start server");
+    rstest_fixtures::Fixture::new("server", TearDownClosure::from(stop_server as fn()))
+}
+
+mod user {
+    // A type of the user that is named `Fixture` too
+    #[derive(Debug, PartialEq)]
+    pub struct Fixture<A, B>(pub A, pub B);
+}
+
+#[fixture]
+fn pair() -> user::Fixture<u32, &'static str> {
+    user::Fixture(42, "pair")
+}
+
+#[rstest]
+fn use_db(db: &Db) {
+    log(format!("use {}", db.0))
+}
+
+#[rstest]
+fn use_db_value(db: &mut Db) {
+    db.query();
+    log(format!("use {}", db.0))
+}
+
+#[rstest]
+fn use_temp_file(temp_file: TempFile) {
+    log(format!("use {}", temp_file.0))
+}
+
+#[rstest]
+fn use_partial(#[with("other")] named: &Db) {
+    log(format!("use {}", named.0))
+}
+
+#[rstest]
+fn use_named(#[with(name = "other")] named: &Db) {
+    log(format!("use {}", named.0))
+}
+
+#[rstest]
+fn use_named_nested(#[with(label = "custom")] named_connection: &str) {
+    log(format!("use {}", named_connection))
+}
+
+#[rstest]
+fn use_given_db(#[with(db = &Db::new("given"))] named_connection: &str) {
+    log(format!("use {}", named_connection))
+}
+
+#[rstest]
+fn use_nested(connection: &str, server: &str) {
+    log(format!("use {} and {}", connection, server))
+}
+
+#[rstest]
+fn use_repo(repo: String) {
+    log(format!("use {}", repo))
+}
+
+#[rstest]
+fn fail(db: &Db) {
+    log(format!("use {}", db.0));
+    panic!("expected failure")
+}
+
+#[rstest]
+fn user_fixture_type_is_a_plain_value(pair: user::Fixture<u32, &str>) {
+    assert_eq!(user::Fixture(42, "pair"), pair);
+}
+
+#[test]
+fn teardown_after_test() {
+    use_db();
+
+    assert_eq!(take_log(), vec!["create main", "use main", "drop main"]);
+}
+
+#[test]
+fn teardown_the_value_used_by_the_test() {
+    use_db_value();
+
+    assert_eq!(
+        take_log(),
+        vec!["create main", "use main", "drop main after 1 queries"]
+    );
+}
+
+#[test]
+fn teardown_after_the_test_that_takes_the_value() {
+    use_temp_file();
+
+    assert_eq!(take_log(), vec!["create file", "use file", "remove file"]);
+}
+
+#[test]
+fn call_the_fixtures_functions_without_teardown() {
+    let db: Db = db::default();
+    let file: TempFile = temp_file::default();
+    let repo: String = repo::default();
+
+    assert_eq!("main", db.0);
+    assert_eq!("file", file.0);
+    assert_eq!("repo on main", repo);
+    assert_eq!(take_log(), vec!["create main", "create file", "create main"]);
+}
+
+#[test]
+fn teardown_the_dependencies_of_fixtures_without_teardown() {
+    use_repo();
+
+    assert_eq!(
+        take_log(),
+        vec!["create main", "use repo on main", "drop main after 1 queries"]
+    );
+}
+
+#[test]
+fn teardown_partial() {
+    use_partial();
+
+    assert_eq!(take_log(), vec!["create other", "use other", "drop other"]);
+}
+
+#[test]
+fn teardown_named() {
+    use_named();
+
+    assert_eq!(take_log(), vec!["create other", "use other", "drop other"]);
+}
+
+#[test]
+fn teardown_named_nested() {
+    use_named_nested();
+
+    assert_eq!(
+        take_log(),
+        vec![
+            "create main",
+            "open custom",
+            "use custom to main",
+            "close named connection",
+            "drop main"
+        ]
+    );
+}
+
+#[test]
+fn not_teardown_the_given_values() {
+    use_given_db();
+
+    assert_eq!(
+        take_log(),
+        vec!["open named", "use named to given", "close named connection"]
+    );
+}
+
+#[test]
+fn teardown_in_reverse_dependency_order() {
+    use_nested();
+
+    assert_eq!(
+        take_log(),
+        vec![
+            "create main",
+            "open connection",
+            "start server",
+            "use connection to main and server",
+            "stop server",
+            "close connection",
+            "drop main"
+        ]
+    );
+}
+
+#[test]
+fn teardown_when_test_panics() {
+    assert!(std::panic::catch_unwind(|| fail()).is_err());
+
+    assert_eq!(take_log(), vec!["create main", "use main", "drop main"]);
+}
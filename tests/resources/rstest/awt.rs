@@ -0,0 +1,72 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rstest::*;
+
+#[fixture]
+async fn base() -> u32 {
+    21
+}
+
+#[fixture]
+async fn double(#[future(awt)] base: u32) -> u32 {
+    base * 2
+}
+
+#[fixture]
+#[awt]
+async fn sum(#[future] base: u32, #[future] double: u32) -> u32 {
+    base + double
+}
+
+#[rstest]
+async fn await_argument(#[future(awt)] base: u32) {
+    assert_eq!(21, base);
+}
+
+#[rstest]
+async fn await_fixture_that_awaits(#[future(awt)] double: u32, #[future(awt)] sum: u32) {
+    assert_eq!(42, double);
+    assert_eq!(63, sum);
+}
+
+#[rstest]
+async fn await_overridden_argument(#[future(awt)] #[with(async { 5 })] double: u32) {
+    assert_eq!(10, double);
+}
+
+#[rstest]
+#[case(async { 2 }, 4)]
+#[case(async { 21 }, 42)]
+#[awt]
+async fn await_all_futures(#[future] #[case] input: u32, #[case] expected: u32, #[future] base: u32) {
+    assert_eq!(expected, input * 2);
+    assert_eq!(21, base);
+}
+
+#[rstest]
+async fn mix_awaited_and_not(#[future(awt)] base: u32, #[future] double: u32) {
+    assert_eq!(42, base * 2);
+    assert_eq!(42, double.await);
+}
+
+static READY: AtomicBool = AtomicBool::new(false);
+
+#[fixture]
+async fn wait_ready() -> u32 {
+    while !READY.load(Ordering::SeqCst) {
+        async_std::task::yield_now().await;
+    }
+    1
+}
+
+#[fixture]
+async fn set_ready() -> u32 {
+    READY.store(true, Ordering::SeqCst);
+    2
+}
+
+#[rstest]
+#[awt(join)]
+async fn join_futures(#[future] wait_ready: u32, #[future] set_ready: u32) {
+    assert_eq!(3, wait_ready + set_ready);
+}
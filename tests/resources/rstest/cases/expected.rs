@@ -0,0 +1,47 @@
+use rstest::rstest;
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Negative,
+    Zero,
+}
+
+#[rstest]
+#[case(2, 3 => 5)]
+#[case::wrong(2, 2 => 5)]
+fn sum(#[case] a: u32, #[case] b: u32) -> u32 {
+    a + b
+}
+
+#[rstest]
+#[case(10, 2 => 5)]
+#[case::zero(1, 0 => panics "attempt to divide by zero")]
+fn divide(#[case] a: u32, #[case] b: u32) -> u32 {
+    a / b
+}
+
+#[rstest]
+#[case(4 => matches Ok(2))]
+#[case::guard(16 => matches Ok(r) if r > 3)]
+#[case::negative(-4 => err(Error::Negative))]
+#[case::any_error(0 => err)]
+#[case::not_error(4 => err)]
+#[case::no_match(9 => matches Ok(2))]
+fn root(#[case] n: i32) -> Result<u32, Error> {
+    match n {
+        n if n < 0 => Err(Error::Negative),
+        0 => Err(Error::Zero),
+        n => Ok((n as f64).sqrt() as u32),
+    }
+}
+
+#[rstest(a, b, case(1, 2) => 3, case::compact(2, 2) => 4)]
+fn compact(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[rstest]
+#[case(2 => 4)]
+fn with_values(#[case] a: u32, #[values(1, 2)] _b: u32) -> u32 {
+    a * 2
+}
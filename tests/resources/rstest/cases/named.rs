@@ -0,0 +1,31 @@
+use rstest::rstest;
+
+#[rstest]
+#[case(input = 2, expected = 4)]
+#[case(expected = 9, input = 3)]
+#[case(4, expected = 16)]
+#[case::wrong(expected = 4, input = 3)]
+fn square(#[case] input: u32, #[case] expected: u32) {
+    assert_eq!(expected, input * input);
+}
+
+#[rstest]
+#[case(width = 2, height = 3 => 6)]
+#[case(2, depth = 2, height = 3 => 12)]
+fn volume(
+    #[case] width: u32,
+    #[case] height: u32,
+    #[case]
+    #[default(1)]
+    depth: u32,
+) -> u32 {
+    width * height * depth
+}
+
+#[rstest(
+    name = "{a}_plus_{b}",
+    case(b = 2, a = 1),
+)]
+fn template(#[case] a: u32, #[case] b: u32) {
+    assert_eq!(3, a + b);
+}
@@ -0,0 +1,13 @@
+use rstest::rstest;
+
+#[rstest]
+#[case(a = 1, c = 2)]
+fn unknown(#[case] a: u32, #[case] b: u32) {}
+
+#[rstest]
+#[case(1, a = 2, b = 3, b = 4)]
+fn duplicate(#[case] a: u32, #[case] b: u32) {}
+
+#[rstest]
+#[case(a = 1, 2)]
+fn positional_after_named(#[case] a: u32, #[case] b: u32) {}
@@ -0,0 +1,71 @@
+use rstest::*;
+
+#[fixture]
+fn separator() -> &'static str {
+    "-"
+}
+
+#[rstest]
+#[case("a", "b", "c", "a-b-c")]
+#[case("a", "b", _, "a-b-z")]
+#[case("a", _, _, "a-y-z")]
+#[case::wrong("a", _, _, "a-b-c")]
+fn join(
+    #[case] first: &str,
+    #[case]
+    #[default("y")]
+    second: &str,
+    #[case]
+    #[default("z")]
+    third: &str,
+    #[case] expected: &str,
+    separator: &str,
+) {
+    assert_eq!(expected, [first, second, third].join(separator));
+}
+
+#[rstest]
+#[case(1, 2, 3)]
+#[case(1, 2)]
+#[case(1)]
+fn trailing(
+    #[case] a: u32,
+    #[case]
+    #[default(10)]
+    b: u32,
+    #[case]
+    #[default(hundred())]
+    c: u32,
+) {
+    assert!(a < b && b < c);
+}
+
+#[fixture]
+fn hundred() -> u32 {
+    100
+}
+
+#[rstest]
+#[case(1, _ => 43)]
+#[case(2 => 44)]
+fn with_expected(#[case] a: u32, #[case] #[default(42)] b: u32) -> u32 {
+    a + b
+}
+
+#[rstest]
+fn just_default(#[default(42)] value: u32) {
+    assert_eq!(42, value);
+}
+
+#[fixture]
+fn second() -> &'static str {
+    "f"
+}
+
+#[rstest]
+#[case("a", "b", "a-b")]
+#[case("a", _, "a-f")]
+#[case::wrong("a", _, "a-b")]
+fn fixture_fallback(#[case] first: &str, #[case] second: &str, #[case] expected: &str) {
+    assert_eq!(expected, [first, second].join("-"));
+}
@@ -0,0 +1,13 @@
+use rstest::*;
+
+#[rstest]
+#[case(1, _)]
+fn placeholder_without_default(#[case] a: u32, #[case] b: u32) {}
+
+#[rstest]
+#[case(1)]
+fn omitted_without_default(#[case] a: u32, #[case] b: u32) {}
+
+#[rstest]
+#[case(b = 1)]
+fn named_without_default(#[case] a: u32, #[case] b: u32) {}
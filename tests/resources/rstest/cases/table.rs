@@ -0,0 +1,39 @@
+use rstest::*;
+use std::net::SocketAddr;
+
+#[rstest]
+#[cases(table = r#"
+    | input | expected | name     |
+    |-------|----------|----------|
+    | 0     | 0        | zero     |
+    | 1     | 1        | one      |
+    | 2 * 5 | 55       | ten      |
+    | 5     | 42       | wrong    |
+"#)]
+fn fibonacci(#[case] input: u32, #[case] expected: u32) {
+    fn fib(n: u32) -> u32 {
+        if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+    }
+    assert_eq!(expected, fib(input))
+}
+
+#[rstest]
+#[case(vec![1, 2], 3)]
+#[cases(table = r"
+    | values           | sum |
+    | vec![]           | 0   |
+    | (1..=3).collect() | 6   |
+")]
+fn mixed(#[case] values: Vec<u32>, #[case] sum: u32) {
+    assert_eq!(sum, values.iter().sum())
+}
+
+#[rstest]
+#[cases(table = r#"
+    | addr             | port | filter             |
+    | "127.0.0.1:8080" | 8080 | \|p: u16\| p > 0   |
+"#)]
+fn magic_conversion(#[case] addr: SocketAddr, #[case] port: u16, #[case] filter: fn(u16) -> bool) {
+    assert_eq!(port, addr.port());
+    assert!(filter(port));
+}
@@ -0,0 +1,15 @@
+use rstest::*;
+
+#[rstest]
+#[cases(table = r"
+    | a | b |
+    | 1 | 2 | 3 |
+")]
+fn wrong_cells(#[case] a: u32, #[case] b: u32) {}
+
+#[rstest]
+#[cases(table = r"
+    | a | c |
+    | 1 | 2 |
+")]
+fn unknown_column(#[case] a: u32, #[case] b: u32) {}
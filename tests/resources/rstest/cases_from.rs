@@ -0,0 +1,35 @@
+use rstest::*;
+
+fn fibonacci(input: u32) -> u32 {
+    match input {
+        0 => 0,
+        1 => 1,
+        n => fibonacci(n - 2) + fibonacci(n - 1),
+    }
+}
+
+#[rstest]
+#[cases_from("vectors/fib.csv")]
+fn csv(#[case] input: u32, #[case] expected: u32) {
+    assert_eq!(expected, fibonacci(input))
+}
+
+#[rstest]
+#[case::inline(2, 1)]
+#[cases_from("vectors/fib.json")]
+fn json(#[case] input: u32, #[case] expected: u32) {
+    assert_eq!(expected, fibonacci(input))
+}
+
+#[rstest]
+#[cases_from("vectors/words.toml")]
+fn toml(#[case] word: &str, #[case] len: usize, #[case] chars: Vec<char>) {
+    assert_eq!(len, word.len());
+    assert_eq!(chars, word.chars().collect::<Vec<_>>());
+}
+
+#[rstest]
+#[cases_from("vectors/fib.csv")]
+fn matrix(#[case] input: u32, #[case] expected: u32, #[values(0, 1)] shift: u32) {
+    assert!(fibonacci(input + shift) >= expected)
+}
@@ -0,0 +1,10 @@
+use rstest::*;
+
+#[rstest]
+async fn ambiguous_runtime() {}
+
+#[rstest(runtime = tokio)]
+async fn given_runtime() {}
+
+#[rstest]
+fn sync_test() {}
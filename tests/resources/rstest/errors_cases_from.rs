@@ -0,0 +1,13 @@
+use rstest::*;
+
+#[rstest]
+#[cases_from("vectors/bad.csv")]
+fn missed_column(#[case] input: u32, #[case] expected: u32) {}
+
+#[rstest]
+#[cases_from("vectors/fib.xls")]
+fn unsupported(#[case] input: u32) {}
+
+#[rstest]
+#[cases_from("vectors/empty.json")]
+fn empty(#[case] input: u32) {}
@@ -0,0 +1,14 @@
+use rstest::*;
+
+#[rstest(name = "{input}_yields_{output}")]
+#[case(0, 0)]
+fn unknown_placeholder(#[case] input: u32, #[case] expected: u32) {}
+
+#[rstest(names = labels)]
+#[case(0)]
+fn unknown_mode(#[case] input: u32) {}
+
+#[rstest(name = "case_{input}")]
+#[case(2)]
+#[case(1 + 1)]
+fn duplicate_names(#[case] input: u32) {}
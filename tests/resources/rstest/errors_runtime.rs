@@ -0,0 +1,7 @@
+use rstest::*;
+
+#[rstest]
+async fn no_runtime() {}
+
+#[rstest(runtime = actix)]
+async fn unknown_runtime() {}
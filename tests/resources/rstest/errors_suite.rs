@@ -0,0 +1,14 @@
+use rstest::*;
+
+#[rstest_suite(42)]
+mod with_arguments {}
+
+#[rstest_suite]
+mod contract {
+    use super::*;
+
+    #[rstest]
+    fn test<S: Default>(store: S) {}
+}
+
+contract!(u32, other = f);
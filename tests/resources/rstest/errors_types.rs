@@ -0,0 +1,13 @@
+use rstest::rstest;
+
+#[rstest]
+#[types(T = [u8], U = [u16])]
+fn not_a_type_parameter<T>(a: T) {}
+
+#[rstest]
+#[types(T = [u8], T = [u16])]
+fn duplicate<T>(a: T) {}
+
+#[rstest]
+#[types(T = [])]
+fn empty<T>(a: T) {}
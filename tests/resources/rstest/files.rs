@@ -0,0 +1,29 @@
+use rstest::*;
+use std::path::{Path, PathBuf};
+
+#[rstest]
+fn paths(#[files("data/*.txt")] path: PathBuf) {
+    assert!(path.is_absolute());
+    assert_eq!("txt", path.extension().unwrap());
+}
+
+#[rstest]
+fn exclude(#[files("data/*.txt")] #[exclude("^data/skip")] path: PathBuf) {
+    assert!(!path.file_name().unwrap().to_str().unwrap().starts_with("skip"));
+}
+
+#[rstest]
+fn read_str(#[files("data/*.txt")] #[mode = str] content: &str) {
+    assert!(content.starts_with("valid"));
+}
+
+#[rstest]
+fn read_bytes(#[files("data/**/*.bin")] #[mode = bytes] content: &[u8]) {
+    assert_eq!(&[1, 2, 3], content);
+}
+
+#[rstest]
+fn matrix(#[files("data/nested/*")] path: PathBuf, #[values(1, 2)] n: u32) {
+    assert!(path.starts_with(Path::new(env!("CARGO_MANIFEST_DIR")).join("data")));
+    assert!(n > 0);
+}
@@ -0,0 +1,7 @@
+use rstest::*;
+use std::path::PathBuf;
+
+#[rstest]
+fn each(#[files("data/*.txt")] path: PathBuf) {
+    assert!(path.exists());
+}
@@ -0,0 +1,21 @@
+use rstest::rstest;
+
+#[rstest]
+#[exclude(host = 1)]
+fn unknown_arg(#[values(80, 443)] port: u16) {}
+
+#[rstest]
+#[only(port = 8080)]
+fn unknown_value(#[values(80, 443)] port: u16) {}
+
+#[rstest]
+#[exclude(port = 80, port = 443)]
+fn no_combination(#[values(80, 443)] port: u16) {}
+
+#[rstest]
+#[exclude(port = 80)]
+#[exclude(port = 443)]
+fn remove_all(#[values(80, 443)] port: u16) {}
+
+#[rstest(combinations = random)]
+fn unknown_mode(#[values(80, 443)] port: u16) {}
@@ -0,0 +1,10 @@
+use rstest::rstest;
+
+#[rstest]
+fn duplicate(#[values(a => 1, b => 2, a => 3)] input: u32) {}
+
+#[rstest(input => [1, input_1 => 2])]
+fn default_name(input: u32) {}
+
+#[rstest]
+fn keyword(#[values(fn => 1)] input: u32) {}
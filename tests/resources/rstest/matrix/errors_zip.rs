@@ -0,0 +1,13 @@
+use rstest::rstest;
+
+#[rstest]
+fn different_lengths(#[zip] #[values(1, 2, 3)] a: u32, #[zip] #[values(1, 2)] b: u32) {}
+
+#[rstest]
+fn no_values(#[zip] a: u32, #[zip] #[values(1, 2)] b: u32) {}
+
+#[rstest]
+fn invalid_syntax(#[zip(all)] #[values(1, 2)] a: u32) {}
+
+#[rstest(combinations = zip)]
+fn zip_all(#[values(1, 2)] a: u32, #[values(1, 2, 3)] b: u32) {}
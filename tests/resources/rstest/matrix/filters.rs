@@ -0,0 +1,33 @@
+use rstest::rstest;
+
+#[rstest]
+#[exclude(tls = false, port = 443)]
+fn exclude(#[values(true, false)] tls: bool, #[values(80, 443)] port: u16) {
+    assert!(tls || port != 443);
+}
+
+#[rstest]
+#[only(backend = sqlite, size = 1)]
+#[only(backend = postgres)]
+fn only(
+    #[values(sqlite => "sqlite", postgres => "postgres")] backend: &str,
+    #[values(1, 2)] size: u32,
+) {
+    assert!(backend == "postgres" || size == 1);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[exclude(port = 443)]
+fn with_cases(#[case] n: u32, #[values(80, 443)] port: u16) {
+    assert!(n > 0);
+    assert_eq!(80, port);
+}
+
+#[rstest]
+#[exclude(tls = false, port = 80)]
+#[exclude(tls = false, port = 443)]
+fn exclude_a_whole_value(#[values(true, false)] tls: bool, #[values(80, 443)] port: u16) {
+    assert!(tls);
+}
@@ -0,0 +1,20 @@
+use rstest::rstest;
+
+#[rstest]
+fn attribute(#[values(empty => "", ascii => "abc", "xyz", emoji => "🦀")] input: &str) {
+    assert!(input.len() <= 4);
+}
+
+#[rstest(
+    expected => [four => 4, 2*3-2],
+    input => [Ciao => "ciao", "buzz"],
+)]
+fn compact(expected: usize, input: &str) {
+    assert_eq!(expected, input.len());
+}
+
+#[rstest]
+#[case(1)]
+fn with_cases(#[case] _n: u32, #[values(zero => 0, 1)] value: u32) {
+    assert!(value < 2);
+}
@@ -0,0 +1,30 @@
+use rstest::rstest;
+
+#[rstest(combinations = pairwise)]
+fn pairwise(
+    #[values(1, 2)] a: u32,
+    #[values(1, 2)] b: u32,
+    #[values(1, 2)] c: u32,
+) {
+    assert!(a + b + c > 0);
+}
+
+#[rstest(combinations = pairwise)]
+#[exclude(a = 2, b = 2)]
+fn pairwise_exclude(
+    #[values(1, 2)] a: u32,
+    #[values(1, 2)] b: u32,
+    #[values(1, 2)] c: u32,
+) {
+    assert!(a + b < 4);
+    assert!(c > 0);
+}
+
+#[rstest(combinations = nwise(3))]
+fn three_wise(
+    #[values(1, 2)] a: u32,
+    #[values(1, 2)] b: u32,
+    #[values(1, 2)] c: u32,
+) {
+    assert!(a + b + c > 0);
+}
@@ -0,0 +1,31 @@
+use rstest::rstest;
+
+#[rstest]
+fn divide(
+    #[values(1, 2, #[should_panic] 0, #[ignore] 1_000_000)] divisor: u32,
+    #[values(10, 20)] dividend: u32,
+) {
+    assert!(dividend / divisor < 100);
+}
+
+#[rstest]
+#[case(1)]
+#[case::slow(2)]
+fn with_cases(
+    #[case] factor: u32,
+    #[values(1, 10, #[should_panic(expected = "Too big")] 100)] value: u32,
+) {
+    assert!(factor * value < 100, "Too big");
+}
+
+#[rstest]
+fn zipped(
+    #[zip]
+    #[values(4, #[should_panic] 0)]
+    divisor: u32,
+    #[zip]
+    #[values(2, #[ignore] 0)]
+    expected: u32,
+) {
+    assert_eq!(expected, 8 / divisor);
+}
@@ -0,0 +1,52 @@
+use rstest::rstest;
+
+#[rstest]
+fn zipped(
+    #[zip]
+    #[values(1, 2, 3)]
+    input: u32,
+    #[zip]
+    #[values(2, 4, 6)]
+    expected: u32,
+) {
+    assert_eq!(expected, input * 2);
+}
+
+#[rstest]
+fn zipped_and_combined(
+    #[zip]
+    #[values(1, 2)]
+    input: u32,
+    #[values(1, 10)] scale: u32,
+    #[zip]
+    #[values(2, 4)]
+    expected: u32,
+) {
+    assert_eq!(expected * scale, input * 2 * scale);
+}
+
+#[rstest]
+#[exclude(input = 2)]
+fn zipped_exclude(
+    #[zip]
+    #[values(1, 2, 3)]
+    input: u32,
+    #[zip]
+    #[values(2, 5, 6)]
+    expected: u32,
+) {
+    assert_eq!(expected, input * 2);
+}
+
+#[rstest(combinations = zip)]
+fn zip_all(
+    #[values(empty => "", "a", "abc")] s: &str,
+    #[values(0, 1, 3)] len: usize,
+) {
+    assert_eq!(len, s.len());
+}
+
+#[rstest(names = values)]
+fn zipped_names(#[zip] #[values(1, 2)] input: u32, #[zip] #[values(2, 4)] expected: u32) {
+    assert_eq!(expected, input * 2);
+}
@@ -0,0 +1,38 @@
+use rstest::*;
+
+#[derive(Debug, PartialEq)]
+enum State {
+    Init,
+    Processing,
+}
+
+#[rstest(names = values)]
+#[case(-42, State::Init)]
+#[case(1, State::Processing)]
+#[case::described(2, State::Processing)]
+#[case(3, State::new())]
+fn values(#[case] input: i32, #[case] state: State) {
+    assert!(input != 0);
+    assert!(state == State::Init || state == State::Processing);
+}
+
+impl State {
+    fn new() -> Self {
+        State::Init
+    }
+}
+
+#[rstest(names = values)]
+fn values_list(#[values("a b", 'c', 1.5, State::Init, vec![1])] v: impl std::fmt::Debug) {
+    assert!(!format!("{:?}", v).is_empty());
+}
+
+#[rstest(name = "{input}_yields_{expected}")]
+#[case(0, 0)]
+#[case(1, 1)]
+#[case(2, 1)]
+#[case(2, 1)]
+#[case(3, 1 + 1)]
+fn template(#[case] input: u32, #[case] expected: u32) {
+    assert!(input >= expected);
+}
@@ -0,0 +1,38 @@
+use rstest::*;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+#[fixture]
+async fn value() -> u32 {
+    tokio::task::yield_now().await;
+    42
+}
+
+#[rstest]
+async fn use_runtime_from_features(#[future(awt)] value: u32) {
+    assert_eq!(42, value);
+    assert_eq!(RuntimeFlavor::CurrentThread, Handle::current().runtime_flavor());
+}
+
+#[rstest(runtime = tokio_multi_thread)]
+async fn use_given_runtime() {
+    assert_eq!(RuntimeFlavor::MultiThread, Handle::current().runtime_flavor());
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+async fn cases(#[case] n: u32) {
+    tokio::task::yield_now().await;
+    assert!(n > 0);
+}
+
+#[rstest]
+fn sync_test() {
+    assert!(Handle::try_current().is_err());
+}
+
+#[rstest]
+#[timeout(std::time::Duration::from_millis(50))]
+async fn timeout_by_runtime_timer() {
+    std::future::pending::<()>().await;
+}
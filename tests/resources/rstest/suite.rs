@@ -0,0 +1,99 @@
+use rstest::*;
+use std::collections::HashMap;
+
+pub trait Storage {
+    fn put(&mut self, key: &str, value: u32);
+    fn get(&self, key: &str) -> Option<u32>;
+}
+
+#[derive(Default)]
+pub struct InMemory(HashMap<String, u32>);
+
+impl Storage for InMemory {
+    fn put(&mut self, key: &str, value: u32) {
+        self.0.insert(key.to_owned(), value);
+    }
+
+    fn get(&self, key: &str) -> Option<u32> {
+        self.0.get(key).cloned()
+    }
+}
+
+/// Doesn't overwrite the values
+#[derive(Default)]
+pub struct VecStore(Vec<(String, u32)>);
+
+impl Storage for VecStore {
+    fn put(&mut self, key: &str, value: u32) {
+        self.0.push((key.to_owned(), value));
+    }
+
+    fn get(&self, key: &str) -> Option<u32> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| *v)
+    }
+}
+
+pub struct Forgetful;
+
+impl Storage for Forgetful {
+    fn put(&mut self, _key: &str, _value: u32) {}
+
+    fn get(&self, _key: &str) -> Option<u32> {
+        None
+    }
+}
+
+#[fixture]
+fn in_memory_store() -> InMemory {
+    let mut store = InMemory::default();
+    store.put("other", 0);
+    store
+}
+
+#[fixture]
+fn forgetful() -> Forgetful {
+    Forgetful
+}
+
+#[fixture]
+fn store<S: Default>() -> S {
+    S::default()
+}
+
+#[fixture]
+fn key() -> &'static str {
+    "answer"
+}
+
+#[rstest_suite]
+mod storage_contract {
+    use super::*;
+
+    #[rstest]
+    fn get_what_put<S: Storage>(mut store: S, key: &str) {
+        store.put(key, 42);
+
+        assert_eq!(Some(42), store.get(key));
+    }
+
+    #[rstest]
+    #[case("answer")]
+    #[case("")]
+    fn get_nothing<S: Storage>(store: S, #[case] key: &str) {
+        assert_eq!(None, store.get(key));
+    }
+
+    #[rstest]
+    fn overwrite<S: Storage>(mut store: S, #[values(1, 2)] value: u32) {
+        store.put(KEY, 0);
+        store.put(KEY, value);
+
+        assert_eq!(Some(value), store.get(KEY));
+    }
+
+    const KEY: &str = "key";
+}
+
+storage_contract!(InMemory, fixture = in_memory_store);
+storage_contract!(VecStore);
+storage_contract!(Forgetful as forgetful_store, fixture = forgetful);
@@ -0,0 +1,81 @@
+use rstest::*;
+use std::time::Duration;
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[rstest]
+#[timeout(ms(500))]
+fn sync_in_time() {
+    assert_eq!(4, 2 + 2);
+}
+
+#[rstest]
+#[timeout(ms(50))]
+fn sync_out_of_time() {
+    std::thread::sleep(ms(1000));
+}
+
+#[rstest]
+#[timeout(ms(500))]
+fn sync_panic_in_time() {
+    panic!("inner panic");
+}
+
+#[rstest]
+#[timeout(ms(500))]
+fn keep_thread_name() {
+    assert!(std::thread::current()
+        .name()
+        .unwrap()
+        .ends_with("keep_thread_name"));
+}
+
+#[fixture]
+fn value() -> u32 {
+    42
+}
+
+#[rstest]
+#[timeout(ms(500))]
+fn with_fixture_and_result(value: u32) -> Result<(), String> {
+    if value == 42 {
+        Ok(())
+    } else {
+        Err("wrong value".to_owned())
+    }
+}
+
+#[rstest]
+#[case::fast(10)]
+#[timeout(ms(1000))]
+#[case::override_timeout(200)]
+#[case::slow(200)]
+#[timeout(ms(100))]
+fn cases(#[case] millis: u64) {
+    std::thread::sleep(ms(millis));
+}
+
+#[rstest]
+#[timeout(ms(500))]
+async fn async_in_time() {
+    async_std::task::sleep(ms(10)).await;
+}
+
+#[rstest]
+#[timeout(ms(50))]
+async fn async_out_of_time() {
+    std::future::pending::<()>().await;
+}
+
+mod other_runtime {
+    pub use async_std::test;
+}
+
+#[rstest]
+#[timeout(ms(50))]
+#[other_runtime::test]
+async fn async_out_of_time_by_timer_thread() {
+    std::future::pending::<()>().await;
+}
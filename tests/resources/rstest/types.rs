@@ -0,0 +1,43 @@
+use rstest::*;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+#[fixture]
+fn zero<T: Default>() -> T {
+    T::default()
+}
+
+#[rstest]
+#[types(T = [u8, i64, String])]
+fn default_is_zero<T: Default + PartialEq + Debug>(zero: T) {
+    assert_eq!(T::default(), zero);
+}
+
+#[rstest]
+#[types(T = [u8, i32, f64])]
+#[case("42")]
+#[case::wrong("-1")]
+fn parse<T: FromStr>(#[case] input: &str) {
+    assert!(input.parse::<T>().is_ok());
+}
+
+#[rstest]
+#[types(T = [u16, u64])]
+fn convert<T: PartialEq<T> + From<u8> + Debug>(#[values("1", "2")] value: T) {
+    assert!(value == T::from(1) || value == T::from(2));
+}
+
+#[rstest]
+#[types(T = [u8, u32], U = [Vec<u8>])]
+#[case(1)]
+fn combined<T: Into<u64>, U: Default + AsRef<[u8]>>(#[case] value: T, zero: U) {
+    assert_eq!(0, zero.as_ref().len());
+    assert!(value.into() > 0);
+}
+
+#[rstest]
+#[types(T = [u8, u16])]
+#[case(2 => 4)]
+fn square<T: std::ops::Mul<Output = T> + Copy>(#[case] value: T) -> T {
+    value * value
+}
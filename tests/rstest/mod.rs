@@ -93,6 +93,527 @@ fn impl_input() {
         .assert(output);
 }
 
+#[test]
+fn should_await_future_arguments() {
+    let prj = prj("awt.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("await_argument")
+        .ok("await_fixture_that_awaits")
+        .ok("await_overridden_argument")
+        .ok("await_all_futures::case_1")
+        .ok("await_all_futures::case_2")
+        .ok("mix_awaited_and_not")
+        .ok("join_futures")
+        .assert(output);
+}
+
+#[test]
+fn should_use_the_runtime_selected_by_features() {
+    let prj = crate::prj_with_features(&["tokio"])
+        .set_code_file(resources(Path::new("rstest").join("runtime.rs")));
+    prj.add_dependency(
+        "tokio",
+        r#"{version="1", features=["macros", "rt", "rt-multi-thread", "time"]}"#,
+    );
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("use_runtime_from_features")
+        .ok("use_given_runtime")
+        .ok("cases::case_1")
+        .ok("cases::case_2")
+        .ok("sync_test")
+        .fail("timeout_by_runtime_timer")
+        .assert(output.clone());
+    assert_in!(
+        output.stdout.str(),
+        format!("Timeout: test `{}::timeout_by_runtime_timer` ran for", prj.get_name())
+    );
+}
+
+#[test]
+fn should_explain_how_to_select_a_runtime() {
+    let prj = crate::prj_with_features(&[])
+        .set_code_file(resources(Path::new("rstest").join("errors_runtime.rs")));
+
+    let output = prj.compile().unwrap();
+    let name = prj.get_name();
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Cannot find a runtime for async test: enable one of `async-std`, `tokio` or `smol` rstest's features, use #[rstest(runtime = <name>)] or add a test attribute like #[tokio::test]
+             --> {}/src/lib.rs:4:10
+              |
+            4 | async fn no_runtime() {{}}
+              |          ^^^^^^^^^^
+            "#,
+            name
+        )
+        .unindent()
+    );
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Unknown runtime 'actix': should be one of async_std, tokio, tokio_multi_thread, smol
+             --> {}/src/lib.rs:6:20
+              |
+            6 | #[rstest(runtime = actix)]
+              |                    ^^^^^
+            "#,
+            name
+        )
+        .unindent()
+    );
+}
+
+#[test]
+fn should_not_select_a_runtime_if_more_features_are_enabled() {
+    let prj = crate::prj_with_features(&["async-std", "tokio"])
+        .set_code_file(resources(Path::new("rstest").join("errors_ambiguous_runtime.rs")));
+    prj.add_dependency("tokio", r#"{version="1", features=["macros", "rt"]}"#);
+
+    let output = prj.compile().unwrap();
+    let name = prj.get_name();
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Cannot select a runtime for async test: more than one of rstest's runtime features is enabled (async-std, tokio). Use #[rstest(runtime = <name>)], add a test attribute like #[tokio::test] or enable just one of them
+             --> {}/src/lib.rs:4:10
+              |
+            4 | async fn ambiguous_runtime() {{}}
+              |          ^^^^^^^^^^^^^^^^^
+            "#,
+            name
+        )
+        .unindent()
+    );
+    assert_eq!(1, output.stderr.str().matches("error: Cannot select").count());
+}
+
+#[test]
+fn should_generate_a_test_for_each_file_matched_by_glob() {
+    let prj = prj("files.rs");
+    let data = prj.path().join("data");
+    std::fs::create_dir_all(data.join("nested")).unwrap();
+    for name in &["a.txt", "b.txt", "skip_c.txt", "nested/e.txt"] {
+        std::fs::write(data.join(name), format!("valid {}", name)).unwrap();
+    }
+    std::fs::write(data.join("nested/d.bin"), &[1, 2, 3]).unwrap();
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("paths::path_1_a_txt")
+        .ok("paths::path_2_b_txt")
+        .ok("paths::path_3_skip_c_txt")
+        .ok("exclude::path_1_a_txt")
+        .ok("exclude::path_2_b_txt")
+        .ok("read_str::content_1_a_txt")
+        .ok("read_str::content_2_b_txt")
+        .ok("read_str::content_3_skip_c_txt")
+        .ok("read_bytes::content_1_d_bin")
+        .ok("matrix::path_1_d_bin::n_1")
+        .ok("matrix::path_1_d_bin::n_2")
+        .ok("matrix::path_2_e_txt::n_1")
+        .ok("matrix::path_2_e_txt::n_2")
+        .assert(output);
+}
+
+#[test]
+fn should_pick_up_new_files_if_a_build_script_tracks_their_folder() {
+    let prj = prj("files_build_script.rs");
+    let data = prj.path().join("data");
+    std::fs::create_dir_all(&data).unwrap();
+    std::fs::write(data.join("a.txt"), "a").unwrap();
+    std::fs::write(
+        prj.path().join("build.rs"),
+        r#"fn main() { println!("cargo:rerun-if-changed=data"); }"#,
+    )
+    .unwrap();
+
+    let output = prj.run_tests().unwrap();
+    TestResults::new().ok("each::path_1_a_txt").assert(output);
+
+    std::fs::write(data.join("b.txt"), "b").unwrap();
+
+    let output = prj.run_tests().unwrap();
+    TestResults::new()
+        .ok("each::path_1_a_txt")
+        .ok("each::path_2_b_txt")
+        .assert(output);
+}
+
+#[test]
+fn should_name_cases_by_values_and_templates() {
+    let prj = prj("names.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("values::case_1_neg_42_Init")
+        .ok("values::case_2_1_Processing")
+        .ok("values::case_3_described")
+        .ok("values::case_4_3")
+        .ok("values_list::v_1_a_b")
+        .ok("values_list::v_2_c")
+        .ok("values_list::v_3_1_5")
+        .ok("values_list::v_4_Init")
+        .ok("values_list::v_5")
+        .ok("template::_0_yields_0")
+        .ok("template::_1_yields_1")
+        .ok("template::case_3_2_yields_1")
+        .ok("template::case_4_2_yields_1")
+        .ok("template::case_5")
+        .assert(output);
+}
+
+#[test]
+fn should_report_invalid_names_definitions() {
+    let prj = prj("errors_names.rs");
+
+    let output = prj.compile().unwrap();
+    let name = prj.get_name();
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Invalid placeholder '{{output}}' in name template: should be a #[case] argument
+             --> {}/src/lib.rs:3:17
+            "#,
+            name
+        )
+        .unindent()
+    );
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Unknown names mode 'labels': should be values
+             --> {}/src/lib.rs:7:18
+            "#,
+            name
+        )
+        .unindent()
+    );
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Duplicate test name 'case_2': the name template gives to a case the name of another one
+              --> {}/src/lib.rs:11:17
+            "#,
+            name
+        )
+        .unindent()
+    );
+}
+
+#[test]
+fn should_instantiate_generic_tests_for_each_type() {
+    let prj = prj("types.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("default_is_zero::T_u8")
+        .ok("default_is_zero::T_i64")
+        .ok("default_is_zero::T_String")
+        .ok("parse::T_u8::case_1")
+        .fail("parse::T_u8::case_2_wrong")
+        .ok("parse::T_i32::case_1")
+        .ok("parse::T_i32::case_2_wrong")
+        .ok("parse::T_f64::case_1")
+        .ok("parse::T_f64::case_2_wrong")
+        .ok("convert::T_u16::value_1")
+        .ok("convert::T_u16::value_2")
+        .ok("convert::T_u64::value_1")
+        .ok("convert::T_u64::value_2")
+        .ok("combined::T_u8::U_Vec_u8::case_1")
+        .ok("combined::T_u32::U_Vec_u8::case_1")
+        .ok("square::T_u8::case_1")
+        .ok("square::T_u16::case_1")
+        .assert(output);
+}
+
+#[test]
+fn should_report_invalid_types_lists() {
+    let prj = prj("errors_types.rs");
+
+    let output = prj.compile().unwrap();
+    let name = prj.get_name();
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: 'U' is not a type parameter of the test function
+             --> {}/src/lib.rs:4:19
+            "#,
+            name
+        )
+        .unindent()
+    );
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Duplicate types list: 'T' is already defined.
+             --> {}/src/lib.rs:8:19
+            "#,
+            name
+        )
+        .unindent()
+    );
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Empty types list: 'T' should have at least one type
+              --> {}/src/lib.rs:12:13
+            "#,
+            name
+        )
+        .unindent()
+    );
+}
+
+#[test]
+fn should_instantiate_the_suite_for_each_implementation() {
+    let prj = prj("suite.rs");
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("in_memory::get_what_put")
+        .ok("in_memory::get_nothing::case_1")
+        .ok("in_memory::get_nothing::case_2")
+        .ok("in_memory::overwrite::value_1")
+        .ok("in_memory::overwrite::value_2")
+        .ok("vec_store::get_what_put")
+        .ok("vec_store::get_nothing::case_1")
+        .ok("vec_store::get_nothing::case_2")
+        .fail("vec_store::overwrite::value_1")
+        .fail("vec_store::overwrite::value_2")
+        .fail("forgetful_store::get_what_put")
+        .ok("forgetful_store::get_nothing::case_1")
+        .ok("forgetful_store::get_nothing::case_2")
+        .fail("forgetful_store::overwrite::value_1")
+        .fail("forgetful_store::overwrite::value_2")
+        .assert(output);
+}
+
+#[test]
+fn should_report_invalid_suites() {
+    let prj = prj("errors_suite.rs");
+
+    let output = prj.compile().unwrap();
+    let name = prj.get_name();
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: #[rstest_suite] doesn't take any argument
+             --> {}/src/lib.rs:3:16
+            "#,
+            name
+        )
+        .unindent()
+    );
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Unknown suite argument 'other': should be 'fixture'
+              --> {}/src/lib.rs:14:16
+            "#,
+            name
+        )
+        .unindent()
+    );
+}
+
+fn write_vectors(prj: &Project, files: &[(&str, &str)]) {
+    let vectors = prj.path().join("vectors");
+    std::fs::create_dir_all(&vectors).unwrap();
+    for (name, content) in files {
+        std::fs::write(vectors.join(name), content.unindent()).unwrap();
+    }
+}
+
+#[test]
+fn should_load_cases_from_data_files() {
+    let prj = prj("cases_from.rs");
+    write_vectors(
+        &prj,
+        &[
+            (
+                "fib.csv",
+                r#"
+                input,expected,name
+                0,0,zero
+                1,1,one
+                10,`5 * 11`,ten
+                5,42,wrong
+                "#,
+            ),
+            (
+                "fib.json",
+                r#"[{"input": 3, "expected": 2}, {"input": 4, "expected": "3"}]"#,
+            ),
+            (
+                "words.toml",
+                r#"
+                [[case]]
+                word = "abc"
+                len = 3
+                chars = "`vec!['a', 'b', 'c']`"
+
+                [[case]]
+                name = "empty word"
+                word = ""
+                len = 0
+                chars = "`vec![]`"
+                "#,
+            ),
+        ],
+    );
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("csv::case_1_zero")
+        .ok("csv::case_2_one")
+        .ok("csv::case_3_ten")
+        .fail("csv::case_4_wrong")
+        .ok("json::case_1_inline")
+        .ok("json::case_2_row_1")
+        .ok("json::case_3_row_2")
+        .ok("toml::case_1_row_1")
+        .ok("toml::case_2_empty_word")
+        .ok("matrix::case_1_zero::shift_1")
+        .ok("matrix::case_1_zero::shift_2")
+        .ok("matrix::case_2_one::shift_1")
+        .ok("matrix::case_2_one::shift_2")
+        .ok("matrix::case_3_ten::shift_1")
+        .ok("matrix::case_3_ten::shift_2")
+        .fail("matrix::case_4_wrong::shift_1")
+        .fail("matrix::case_4_wrong::shift_2")
+        .assert(output);
+}
+
+#[test]
+fn should_point_to_the_data_file_line_on_cases_from_errors() {
+    let prj = prj("errors_cases_from.rs");
+    write_vectors(
+        &prj,
+        &[
+            ("bad.csv", "input,expected\n1,1\n2\n"),
+            ("empty.json", "[]"),
+        ],
+    );
+
+    let output = prj.compile().unwrap();
+    let name = prj.get_name();
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: vectors/bad.csv:3: found a record with 1 fields but the header has 2 fields
+             --> {}/src/lib.rs:4:14
+              |
+            4 | #[cases_from("vectors/bad.csv")]
+              |              ^^^^^^^^^^^^^^^^^
+            "#,
+            name
+        )
+        .unindent()
+    );
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: Unsupported data file format: should be a .csv, .toml or .json file
+             --> {}/src/lib.rs:8:14
+            "#,
+            name
+        )
+        .unindent()
+    );
+
+    assert_in!(
+        output.stderr.str(),
+        format!(
+            r#"
+            error: vectors/empty.json: no cases found: the data file should contain at least one case
+              --> {}/src/lib.rs:12:14
+            "#,
+            name
+        )
+        .unindent()
+    );
+}
+
+#[test]
+fn should_fail_tests_that_run_out_of_time() {
+    let prj = prj("timeout.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("sync_in_time")
+        .fail("sync_out_of_time")
+        .fail("sync_panic_in_time")
+        .ok("keep_thread_name")
+        .ok("with_fixture_and_result")
+        .ok("cases::case_1_fast")
+        .ok("cases::case_2_override_timeout")
+        .fail("cases::case_3_slow")
+        .ok("async_in_time")
+        .fail("async_out_of_time")
+        .fail("async_out_of_time_by_timer_thread")
+        .assert(output.clone());
+
+    let name = prj.get_name();
+    assert_in!(
+        output.stdout.str(),
+        format!("Timeout: test `{}::cases::case_3_slow` ran for", name)
+    );
+    assert_in!(output.stdout.str(), "more than the 100ms timeout");
+    assert_in!(output.stdout.str(), "inner panic");
+    assert_in!(
+        output.stdout.str(),
+        format!("Timeout: test `{}::async_out_of_time` ran for", name)
+    );
+    assert_in!(
+        output.stdout.str(),
+        format!(
+            "Timeout: test `{}::async_out_of_time_by_timer_thread` ran for",
+            name
+        )
+    );
+}
+
 #[test]
 fn should_reject_no_item_function() {
     let (output, name) = run_test("reject_no_item_function.rs");
@@ -481,6 +1002,192 @@ mod cases {
             .assert(output);
     }
 
+    #[test]
+    fn should_use_cases_tables() {
+        let (output, _) = run_test(res("table.rs"));
+
+        TestResults::new()
+            .ok("fibonacci::case_1_zero")
+            .ok("fibonacci::case_2_one")
+            .ok("fibonacci::case_3_ten")
+            .fail("fibonacci::case_4_wrong")
+            .ok("mixed::case_1")
+            .ok("mixed::case_2")
+            .ok("mixed::case_3")
+            .ok("magic_conversion::case_1")
+            .assert(output);
+    }
+
+    #[test]
+    fn should_report_cases_tables_errors() {
+        let (output, name) = run_test(res("table_errors.rs"));
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Wrong number of cells: expected 2 like the header's columns but found 3
+                 --> {}/src/lib.rs:4:17
+                "#,
+                name
+            )
+            .unindent()
+        );
+        assert_in!(
+            output.stderr.str(),
+            "error: Column 'c' is not a #[case] argument"
+        );
+        assert_in!(
+            output.stderr.str(),
+            "error: Missed column for #[case] argument 'b'"
+        );
+    }
+
+    #[test]
+    fn should_check_the_expected_results() {
+        let (output, _) = run_test(res("expected.rs"));
+        let stdout = output.stdout.str().to_string();
+
+        TestResults::new()
+            .ok("sum::case_1")
+            .fail("sum::case_2_wrong")
+            .ok("divide::case_1")
+            .ok("divide::case_2_zero - should panic")
+            .ok("root::case_1")
+            .ok("root::case_2_guard")
+            .ok("root::case_3_negative")
+            .ok("root::case_4_any_error")
+            .fail("root::case_5_not_error")
+            .fail("root::case_6_no_match")
+            .ok("compact::case_1")
+            .ok("compact::case_2_compact")
+            .ok("with_values::case_1::_b_1")
+            .ok("with_values::case_1::_b_2")
+            .assert(output);
+
+        assert_in!(
+            stdout,
+            "Expected an error that matches `_` but got Ok(2)"
+        );
+        assert_in!(
+            stdout,
+            "Expected a result that matches `Ok(2)` but got Ok(3)"
+        );
+    }
+
+    #[test]
+    fn should_use_fixtures_and_defaults_of_omitted_arguments() {
+        let (output, _) = run_test(res("omitted.rs"));
+
+        TestResults::new()
+            .ok("join::case_1")
+            .ok("join::case_2")
+            .ok("join::case_3")
+            .fail("join::case_4_wrong")
+            .ok("trailing::case_1")
+            .ok("trailing::case_2")
+            .ok("trailing::case_3")
+            .ok("with_expected::case_1")
+            .ok("with_expected::case_2")
+            .ok("just_default")
+            .ok("fixture_fallback::case_1")
+            .ok("fixture_fallback::case_2")
+            .fail("fixture_fallback::case_3_wrong")
+            .assert(output);
+    }
+
+    #[test]
+    fn should_resolve_named_values() {
+        let (output, _) = run_test(res("named.rs"));
+
+        TestResults::new()
+            .ok("square::case_1")
+            .ok("square::case_2")
+            .ok("square::case_3")
+            .fail("square::case_4_wrong")
+            .ok("volume::case_1")
+            .ok("volume::case_2")
+            .ok("template::_1_plus_2")
+            .assert(output);
+    }
+
+    #[test]
+    fn should_report_invalid_named_values() {
+        let prj = prj(res("named_errors.rs"));
+
+        let output = prj.compile().unwrap();
+        let name = prj.get_name();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: 'c' is not a #[case] argument: should be one of 'a', 'b'
+                 --> {}/src/lib.rs:4:15
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Duplicate value for 'a' case argument.
+                 --> {}/src/lib.rs:8:11
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Duplicate value for 'b' case argument.
+                 --> {}/src/lib.rs:8:25
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Positional values should precede the named ones: found a positional value after 'a'
+                  --> {}/src/lib.rs:12:15
+                "#,
+                name
+            )
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn should_report_missed_fixtures_of_omitted_arguments() {
+        let prj = prj(res("omitted_errors.rs"));
+
+        let output = prj.run_tests().unwrap();
+        let name = prj.get_name();
+        let stderr = output.stderr.str();
+
+        // The placeholder
+        assert_in!(stderr, format!(" --> {}/src/lib.rs:4:11\n", name));
+        // The cases that omit the argument
+        assert_in!(stderr, format!(" --> {}/src/lib.rs:8:8\n", name));
+        assert_in!(stderr, format!("  --> {}/src/lib.rs:12:8\n", name));
+        assert_eq!(
+            3,
+            stderr.count("error[E0433]"),
+            "Should report just the missed fixtures:\n{}",
+            stderr
+        );
+    }
+
     #[test]
     fn trace_just_one_test() {
         let (output, _) = run_test(res("dump_just_one_case.rs"));
@@ -614,18 +1321,19 @@ mod cases {
         }
 
         #[test]
-        fn with_less_arguments() {
+        fn with_less_arguments_without_fixtures() {
             let (output, _) = execute();
             let stderr = output.stderr.str();
 
+            // The omitted arguments are resolved by their fixture: the missed fixture is
+            // reported on the case.
             assert_in!(
                 stderr,
                 "
                   |
                 4 | #[rstest(a, b, case(42), case(1, 2), case(43))]
-                  |                     ^^
-                "
-                .unindent()
+                  |                     ^^ "
+                    .unindent()
             );
 
             assert_in!(
@@ -633,9 +1341,8 @@ mod cases {
                 "
                   |
                 4 | #[rstest(a, b, case(42), case(1, 2), case(43))]
-                  |                                           ^^
-                "
-                .unindent()
+                  |                                           ^^ "
+                    .unindent()
             );
         }
 
@@ -644,11 +1351,11 @@ mod cases {
             let (output, _) = execute();
             let stderr = output.stderr.str();
 
-            // Exactly 4 cases are wrong
+            // Exactly 2 cases give too much arguments
             assert_eq!(
-                4,
+                2,
                 stderr.count("Wrong case signature: should match the given parameters list."),
-                "Should contain message exactly 4 occurrences in error message:\n{}",
+                "Should contain message exactly 2 occurrences in error message:\n{}",
                 stderr
             );
         }
@@ -808,6 +1515,306 @@ mod matrix {
             .assert(output);
     }
 
+    #[test]
+    fn should_use_values_names() {
+        let (output, _) = run_test(res("named.rs"));
+
+        TestResults::new()
+            .ok("attribute::empty")
+            .ok("attribute::ascii")
+            .ok("attribute::input_3")
+            .ok("attribute::emoji")
+            .ok("compact::four::Ciao")
+            .ok("compact::four::input_2")
+            .ok("compact::expected_2::Ciao")
+            .ok("compact::expected_2::input_2")
+            .ok("with_cases::case_1::zero")
+            .ok("with_cases::case_1::value_2")
+            .assert(output);
+    }
+
+    #[test]
+    fn should_report_invalid_values_names() {
+        let prj = prj(res("errors_named.rs"));
+
+        let output = prj.compile().unwrap();
+        let name = prj.get_name();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Duplicate name 'a' in values list
+                 --> {}/src/lib.rs:4:23
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Duplicate name 'a' in values list
+                 --> {}/src/lib.rs:4:39
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Duplicate name 'input_1' in values list
+                 --> {}/src/lib.rs:6:23
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Invalid name 'fn': should be a valid identifier and not a keyword
+                  --> {}/src/lib.rs:10:21
+                "#,
+                name
+            )
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn should_filter_combinations() {
+        let (output, _) = run_test(res("filters.rs"));
+
+        TestResults::new()
+            .ok("exclude::tls_1::port_1")
+            .ok("exclude::tls_1::port_2")
+            .ok("exclude::tls_2::port_1")
+            .ok("only::sqlite::size_1")
+            .ok("only::postgres::size_1")
+            .ok("only::postgres::size_2")
+            .ok("with_cases::case_1::port_1")
+            .ok("with_cases::case_2::port_1")
+            .ok("exclude_a_whole_value::tls_1::port_1")
+            .ok("exclude_a_whole_value::tls_1::port_2")
+            .assert(output);
+    }
+
+    #[test]
+    fn should_report_invalid_filters() {
+        let prj = prj(res("errors_filters.rs"));
+
+        let output = prj.compile().unwrap();
+        let name = prj.get_name();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: 'host' is not a #[values] argument: #[exclude] can filter just the values lists' combinations
+                 --> {}/src/lib.rs:4:11
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: No value of 'port' matches '8080'
+                 --> {}/src/lib.rs:8:15
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: This #[exclude] rule doesn't match any combination
+                  --> {}/src/lib.rs:12:3
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: #[exclude] and #[only] rules remove all the combinations
+                  --> {}/src/lib.rs:16:3
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Unknown combinations mode 'random': should be all, pairwise, nwise(<n>) or zip
+                  --> {}/src/lib.rs:20:25
+                "#,
+                name
+            )
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn should_generate_just_pairwise_combinations() {
+        let (output, _) = run_test(res("pairwise.rs"));
+
+        TestResults::new()
+            .ok("pairwise::a_1::b_1::c_1")
+            .ok("pairwise::a_1::b_2::c_2")
+            .ok("pairwise::a_2::b_1::c_2")
+            .ok("pairwise::a_2::b_2::c_1")
+            .ok("pairwise_exclude::a_1::b_1::c_1")
+            .ok("pairwise_exclude::a_1::b_2::c_1")
+            .ok("pairwise_exclude::a_1::b_2::c_2")
+            .ok("pairwise_exclude::a_2::b_1::c_1")
+            .ok("pairwise_exclude::a_2::b_1::c_2")
+            .ok("three_wise::a_1::b_1::c_1")
+            .ok("three_wise::a_1::b_1::c_2")
+            .ok("three_wise::a_1::b_2::c_1")
+            .ok("three_wise::a_1::b_2::c_2")
+            .ok("three_wise::a_2::b_1::c_1")
+            .ok("three_wise::a_2::b_1::c_2")
+            .ok("three_wise::a_2::b_2::c_1")
+            .ok("three_wise::a_2::b_2::c_2")
+            .assert(output);
+    }
+
+    #[test]
+    fn should_zip_values_lists() {
+        let (output, _) = run_test(res("zip.rs"));
+
+        TestResults::new()
+            .ok("zipped::zip_1")
+            .ok("zipped::zip_2")
+            .ok("zipped::zip_3")
+            .ok("zipped_and_combined::zip_1::scale_1")
+            .ok("zipped_and_combined::zip_1::scale_2")
+            .ok("zipped_and_combined::zip_2::scale_1")
+            .ok("zipped_and_combined::zip_2::scale_2")
+            .ok("zipped_exclude::zip_1")
+            .ok("zipped_exclude::zip_3")
+            .ok("zip_all::zip_1_empty")
+            .ok("zip_all::zip_2")
+            .ok("zip_all::zip_3")
+            .ok("zipped_names::zip_1_1_2")
+            .ok("zipped_names::zip_2_2_4")
+            .assert(output);
+    }
+
+    #[test]
+    fn should_report_invalid_zipped_lists() {
+        let prj = prj(res("errors_zip.rs"));
+
+        let output = prj.compile().unwrap();
+        let name = prj.get_name();
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Zipped values lists should have the same length: 'b' has 2 values but 'a' has 3
+                 --> {}/src/lib.rs:4:79
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: 'a' is not a #[values] argument: #[zip] can pair just values lists
+                 --> {}/src/lib.rs:7:21
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Invalid zip syntax: should be just #[zip]
+                  --> {}/src/lib.rs:10:19
+                "#,
+                name
+            )
+            .unindent()
+        );
+
+        assert_in!(
+            output.stderr.str(),
+            format!(
+                r#"
+                error: Zipped values lists should have the same length: 'b' has 3 values but 'a' has 2
+                  --> {}/src/lib.rs:13:55
+                "#,
+                name
+            )
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn should_apply_values_attributes() {
+        let (output, _) = run_test(res("values_attrs.rs"));
+        let stdout = output.stdout.str();
+
+        assert_eq!(Some(0), output.status.code(), "Unexpected fails:\n{}", stdout);
+        assert_in!(stdout, "running 16 tests");
+        for test in &[
+            "divide::divisor_1::dividend_1",
+            "divide::divisor_1::dividend_2",
+            "divide::divisor_2::dividend_1",
+            "divide::divisor_2::dividend_2",
+            "with_cases::case_1::value_1",
+            "with_cases::case_1::value_2",
+            "with_cases::case_2_slow::value_1",
+            "with_cases::case_2_slow::value_2",
+            "zipped::zip_1",
+        ] {
+            assert_in!(stdout, format!("test {} ... ok", test));
+        }
+        for test in &[
+            "divide::divisor_3::dividend_1",
+            "divide::divisor_3::dividend_2",
+            "with_cases::case_1::value_3",
+            "with_cases::case_2_slow::value_3",
+        ] {
+            assert_in!(stdout, format!("test {} - should panic ... ok", test));
+        }
+        for test in &[
+            "divide::divisor_4::dividend_1",
+            "divide::divisor_4::dividend_2",
+            "zipped::zip_2",
+        ] {
+            assert_in!(stdout, format!("test {} ... ignored", test));
+        }
+    }
+
     #[test]
     fn use_args_attributes() {
         let (output, _) = run_test(res("use_attr.rs"));